@@ -0,0 +1,159 @@
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+const mods = [Mod.Control, Mod.Shift];
+
+test.serial('a disarmed hotkey delivers no events until armed', async t => {
+  const { id } = manager.register(mods, KeyCode.KeyK);
+
+  t.true(manager.disarm(id));
+  t.true(manager.simulate(id, EventType.Pressed));
+  await sleep(20);
+  t.is(events.length, 0);
+
+  t.true(manager.arm(id));
+  t.true(manager.simulate(id, EventType.Pressed));
+  await sleep(20);
+  t.deepEqual(
+    events.map(event => [event.id, event.eventType]),
+    [[id, EventType.Pressed]],
+  );
+
+  manager.unregister(mods, KeyCode.KeyK);
+  events.length = 0;
+});
+
+test.serial('registering again starts armed', async t => {
+  const { id } = manager.register(mods, KeyCode.KeyK);
+  manager.disarm(id);
+  manager.unregister(mods, KeyCode.KeyK);
+
+  t.false(manager.arm(id));
+  t.false(manager.disarm(id));
+
+  manager.register(mods, KeyCode.KeyK);
+  t.true(manager.simulate(id, EventType.Pressed));
+  await sleep(20);
+  t.is(events.length, 1);
+
+  manager.unregister(mods, KeyCode.KeyK);
+  events.length = 0;
+});
+
+test.serial('presses during the cooldown are ignored', async t => {
+  const { id } = manager.register(mods, KeyCode.KeyC);
+  t.true(manager.setCooldown(id, 150));
+
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(200);
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Released, EventType.Pressed],
+  );
+
+  events.length = 0;
+  t.true(manager.setCooldown(id, 0));
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+  t.is(events.length, 2);
+
+  manager.unregister(mods, KeyCode.KeyC);
+  t.false(manager.setCooldown(id, 100));
+  events.length = 0;
+});
+
+test.serial('a deferred Released is withheld until the deferral ends', async t => {
+  const { id } = manager.register(mods, KeyCode.KeyD);
+  t.true(manager.deferReleased(id, true));
+
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Pressed],
+  );
+
+  t.true(manager.deferReleased(id, false));
+  await sleep(20);
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Pressed, EventType.Released],
+  );
+
+  manager.unregister(mods, KeyCode.KeyD);
+  t.false(manager.deferReleased(id, true));
+  events.length = 0;
+});
+
+test.serial('a withheld Released can be dropped instead', async t => {
+  const { id } = manager.register(mods, KeyCode.KeyD);
+  manager.deferReleased(id, true);
+
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  manager.deferReleased(id, false, { dropWithheld: true });
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Pressed, EventType.Released],
+  );
+
+  manager.unregister(mods, KeyCode.KeyD);
+  events.length = 0;
+});
+
+test.serial('the watchdog Released honours deferral and disarming', async t => {
+  const { id } = manager.register(mods, KeyCode.KeyG);
+  manager.setReleaseWatchdog(50);
+
+  manager.deferReleased(id, true);
+  manager.simulate(id, EventType.Pressed);
+  await sleep(150);
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed],
+  );
+  manager.deferReleased(id, false);
+  await sleep(20);
+  t.deepEqual(
+    events.map(event => [event.eventType, event.synthetic]),
+    [
+      [EventType.Pressed, true],
+      [EventType.Released, true],
+    ],
+  );
+  events.length = 0;
+
+  manager.simulate(id, EventType.Pressed);
+  manager.disarm(id);
+  await sleep(150);
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed],
+  );
+
+  manager.arm(id);
+  manager.setReleaseWatchdog(null);
+  manager.unregister(mods, KeyCode.KeyG);
+  events.length = 0;
+});
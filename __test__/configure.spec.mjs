@@ -0,0 +1,85 @@
+import test from 'ava'
+
+import { EventType, getHotkeyId, KeyCode, Mod, QueuePolicy } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+const mods = [Mod.Control, Mod.Shift];
+const locks = [Mod.CapsLock, Mod.NumLock, Mod.ScrollLock, Mod.FnLock, Mod.SymbolLock];
+
+test.serial('configure applies several settings together', async t => {
+  manager.configure({ emitLifecycle: true, lateEventGraceMs: 500 });
+
+  const { id } = manager.register(mods, KeyCode.KeyG);
+  manager.unregister(mods, KeyCode.KeyG);
+  t.true(manager.simulate(id, EventType.Released));
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => [event.id, event.eventType]),
+    [
+      [id, EventType.Registered],
+      [id, EventType.Unregistered],
+      [id, EventType.Released],
+    ],
+  );
+  events.length = 0;
+});
+
+test.serial('configure keeps the settings left unset', async t => {
+  manager.configure({ ignoredModifiers: [] });
+
+  try {
+    const { id } = manager.register([Mod.Control, Mod.NumLock], KeyCode.KeyG);
+    t.is(id, getHotkeyId(KeyCode.KeyG, [Mod.Control, Mod.NumLock]));
+    manager.unregister([Mod.Control, Mod.NumLock], KeyCode.KeyG);
+    t.true(manager.simulate(id, EventType.Released));
+    await sleep(20);
+
+    t.deepEqual(
+      events.map(event => event.eventType),
+      [EventType.Registered, EventType.Unregistered, EventType.Released],
+    );
+  } finally {
+    manager.configure({ emitLifecycle: false, lateEventGraceMs: 0, ignoredModifiers: locks });
+    events.length = 0;
+  }
+});
+
+test.serial('currentConfig reflects configure and the individual setters', t => {
+  const saved = manager.currentConfig();
+  const options = {
+    bufferPreinit: false,
+    emitLifecycle: true,
+    lateEventGraceMs: 250,
+    releaseWatchdogMs: 5000,
+    queueLimit: 64,
+    queuePolicy: QueuePolicy.DropOldest,
+    ignoreOsRepeat: false,
+    ignoredModifiers: [Mod.CapsLock],
+    instanceName: 'settings-window',
+    consoleTrace: false,
+  };
+
+  try {
+    manager.configure(options);
+    t.deepEqual(manager.currentConfig(), options);
+
+    manager.setInstanceName('');
+    manager.setQueueLimit(0, QueuePolicy.DropNewest);
+    t.deepEqual(manager.currentConfig(), {
+      ...options,
+      queueLimit: 0,
+      queuePolicy: QueuePolicy.DropNewest,
+      instanceName: '',
+    });
+  } finally {
+    manager.configure(saved);
+  }
+  t.deepEqual(manager.currentConfig(), saved);
+  t.deepEqual(saved.ignoredModifiers, locks);
+});
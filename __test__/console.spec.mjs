@@ -0,0 +1,27 @@
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+test.serial('console trace keeps delivering events to the callback', async t => {
+  const mods = [Mod.Control, Mod.Shift];
+  const { id } = manager.register(mods, KeyCode.KeyJ);
+
+  manager.enableConsoleTrace(true);
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+  manager.enableConsoleTrace(false);
+
+  t.deepEqual(
+    events.filter(event => event.id === id).map(event => event.eventType),
+    [EventType.Pressed, EventType.Released],
+  );
+
+  manager.unregister(mods, KeyCode.KeyJ);
+});
@@ -0,0 +1,16 @@
+import test from 'ava'
+
+import { hotk, hotkError } from '../index.js'
+
+import { hooks } from './hooks.mjs'
+
+// The manager is created once per process, so this lives in its own file.
+(hooks ? test : test.skip)('the reason the manager could not be created is kept', t => {
+  process.env.HOTK_FAIL_CREATE = '1';
+
+  t.is(hotk(), null);
+  t.regex(hotkError(), /^Failed to create the hotkey backend: simulated by HOTK_FAIL_CREATE$/);
+  // The failure is remembered rather than retried.
+  delete process.env.HOTK_FAIL_CREATE;
+  t.is(hotk(), null);
+});
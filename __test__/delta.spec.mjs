@@ -0,0 +1,55 @@
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+test.serial('deltaMs is the time since the previous event of the same hotkey', async t => {
+  const mods = [Mod.Control, Mod.Shift];
+  const { id } = manager.register(mods, KeyCode.KeyD);
+  const { id: otherId } = manager.register(mods, KeyCode.KeyF);
+
+  manager.simulate(id, EventType.Pressed);
+  await sleep(60);
+  manager.simulate(otherId, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(120);
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+
+  const deltas = events.filter(event => event.id === id).map(event => event.deltaMs);
+  t.is(deltas.length, 3);
+  t.is(deltas[0], 0);
+  t.true(deltas[1] >= 55 && deltas[1] < 110, `${deltas[1]}`);
+  t.true(deltas[2] >= 115 && deltas[2] < 220, `${deltas[2]}`);
+  // Other hotkeys keep their own timing.
+  t.is(events.find(event => event.id === otherId).deltaMs, 0);
+
+  manager.unregister(mods, KeyCode.KeyD);
+  manager.unregister(mods, KeyCode.KeyF);
+});
+
+test.serial('timestamp is when the event reached the crate', async t => {
+  const mods = [Mod.Control, Mod.Shift];
+  const { id } = manager.register(mods, KeyCode.KeyT);
+  events.length = 0;
+
+  const before = Date.now();
+  manager.simulate(id, EventType.Pressed);
+  const after = Date.now();
+  await sleep(60);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+
+  const [pressed, released] = events;
+  t.is(typeof pressed.timestamp, 'number');
+  t.true(pressed.timestamp >= before && pressed.timestamp < after + 1, `${pressed.timestamp}`);
+  t.true(released.timestamp - pressed.timestamp >= 55, `${released.timestamp - pressed.timestamp}`);
+
+  manager.unregister(mods, KeyCode.KeyT);
+  events.length = 0;
+});
@@ -0,0 +1,91 @@
+import test from 'ava'
+
+import { EventType, hotk, InitResult, KeyCode, Mod } from '../index.js'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+// `init` is process-wide, so this lives in its own file.
+test.serial('listening time is tracked from init until destroy', async t => {
+  const manager = hotk();
+  t.is(manager.listeningSince(), null);
+  t.is(manager.listeningUptimeMs(), null);
+
+  const before = Date.now();
+  manager.init(() => {});
+  manager.unref();
+  const since = manager.listeningSince();
+  t.true(since >= before && since <= Date.now());
+
+  await sleep(30);
+  t.true(manager.listeningUptimeMs() >= 25);
+
+  // Replacing the callback keeps listening.
+  manager.init(() => {});
+  manager.unref();
+  t.is(manager.listeningSince(), since);
+
+  manager.destroy();
+  t.is(manager.listeningSince(), null);
+  t.is(manager.listeningUptimeMs(), null);
+});
+
+test.serial('no callback fires after destroy', async t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Shift];
+  const { id } = manager.register(mods, KeyCode.F7);
+  const events = [];
+  const batches = [];
+
+  manager.init(event => events.push(event));
+  manager.setBatchCallback(batch => batches.push(batch), { intervalMs: 1000 });
+  manager.simulate(id, EventType.Pressed);
+  manager.destroy();
+
+  // A late event reaching the still installed global handler.
+  t.true(manager.simulate(id, EventType.Released));
+  await sleep(50);
+  t.deepEqual(events, []);
+  t.deepEqual(batches, []);
+
+  // Listening can start over.
+  t.is(manager.init(event => events.push(event)), InitResult.Installed);
+  manager.unref();
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed],
+  );
+
+  manager.destroy();
+  manager.unregister(mods, KeyCode.F7);
+});
+
+test.serial('listening can be destroyed and started again repeatedly', async t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Shift];
+  const { id } = manager.register(mods, KeyCode.F8);
+  const handled = [];
+  manager.on(mods, KeyCode.F9, event => handled.push(event));
+
+  for (let round = 0; round < 3; round++) {
+    const events = [];
+    t.is(manager.init(event => events.push(event)), InitResult.Installed);
+    manager.unref();
+    manager.simulate(id, EventType.Pressed);
+    await sleep(20);
+    t.deepEqual(events.map(event => event.id), [id]);
+
+    manager.destroy();
+    manager.simulate(id, EventType.Released);
+    await sleep(20);
+    t.is(events.length, 1);
+  }
+
+  // Handlers attached with `on` are dropped too.
+  t.false(manager.off(mods, KeyCode.F9));
+  t.deepEqual(handled, []);
+
+  manager.unregister(mods, KeyCode.F8);
+  manager.unregister(mods, KeyCode.F9);
+});
@@ -0,0 +1,96 @@
+import test from 'ava'
+
+import { EventType, KeyCode, Mod, QueuePolicy } from '../index.js'
+
+import { hooks } from './hooks.mjs'
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+(hooks ? test.serial : test.skip)('events that fail to convert are counted and skipped', async t => {
+  const mods = [Mod.Control, Mod.Shift];
+  const { id } = manager.register(mods, KeyCode.KeyX);
+  t.is(manager.droppedEvents(), 0);
+
+  manager.simulateConversionFailures(1);
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+  t.is(manager.droppedEvents(), 1);
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Released],
+  );
+
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+  t.is(manager.droppedEvents(), 1);
+  t.is(events.length, 2);
+
+  manager.unregister(mods, KeyCode.KeyX);
+});
+
+const codes = [KeyCode.F13, KeyCode.F14, KeyCode.F15, KeyCode.F16, KeyCode.F17];
+
+// Fires one press per code in a single burst, before the JS thread can drain the queue.
+const burst = async policy => {
+  const mods = [Mod.Control, Mod.Alt];
+  const ids = codes.map(code => manager.register(mods, code).id);
+  const dropped = manager.droppedEvents();
+  events.length = 0;
+
+  manager.setQueueLimit(2, policy);
+  try {
+    ids.forEach(id => manager.simulate(id, EventType.Pressed));
+    await sleep(20);
+  } finally {
+    manager.setQueueLimit(0, policy);
+    codes.forEach(code => manager.unregister(mods, code));
+  }
+
+  return { ids, dropped: manager.droppedEvents() - dropped, received: events.map(event => event.id) };
+};
+
+test.serial('a full queue drops the newest events with DropNewest', async t => {
+  const { ids, dropped, received } = await burst(QueuePolicy.DropNewest);
+
+  t.deepEqual(received, ids.slice(0, 2));
+  t.is(dropped, 3);
+});
+
+test.serial('a full queue drops the oldest events with DropOldest', async t => {
+  const { ids, dropped, received } = await burst(QueuePolicy.DropOldest);
+
+  t.deepEqual(received, ids.slice(-2));
+  t.is(dropped, 3);
+});
+
+test.serial('exempt hotkeys are never dropped by a full queue', async t => {
+  const mods = [Mod.Control, Mod.Alt];
+  const [critical, other] = [KeyCode.F18, KeyCode.F19].map(code => manager.register(mods, code).id);
+  t.true(manager.setQueueExempt(critical, true));
+  t.true(manager.isQueueExempt(critical));
+  t.false(manager.isQueueExempt(other));
+  events.length = 0;
+
+  manager.setQueueLimit(2, QueuePolicy.DropNewest);
+  try {
+    for (let i = 0; i < 3; i++) {
+      manager.simulate(other, EventType.Pressed);
+      manager.simulate(critical, EventType.Pressed);
+    }
+    await sleep(20);
+  } finally {
+    manager.setQueueLimit(0, QueuePolicy.DropNewest);
+  }
+
+  t.is(events.filter(event => event.id === critical).length, 3);
+  t.is(events.filter(event => event.id === other).length, 1);
+
+  manager.unregister(mods, KeyCode.F18);
+  manager.unregister(mods, KeyCode.F19);
+  t.false(manager.isQueueExempt(critical));
+  t.false(manager.setQueueExempt(critical, true));
+});
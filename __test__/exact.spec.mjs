@@ -0,0 +1,84 @@
+import { execFileSync } from 'node:child_process'
+
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+const onWindows = process.platform === 'win32';
+
+const { events, manager } = listen();
+
+// Presses (flags 0) or releases (flags 2) Ctrl and Shift, as the user would.
+const ctrlShift = flags => {
+  const script = `
+    $sig = '[DllImport("user32.dll")] public static extern void keybd_event(byte vk, byte scan, uint flags, UIntPtr extra);'
+    $user32 = Add-Type -MemberDefinition $sig -Name User32 -Namespace Hotk -PassThru
+    $user32::keybd_event(0x11, 0, ${flags}, [UIntPtr]::Zero)
+    $user32::keybd_event(0x10, 0, ${flags}, [UIntPtr]::Zero)
+  `;
+  execFileSync('powershell', ['-NoProfile', '-NonInteractive', '-Command', script]);
+};
+
+(onWindows ? test.serial : test.skip)('exactModifiers drops events while an extra modifier is held', async t => {
+  const exact = manager.register([Mod.Control], KeyCode.F23, { exactModifiers: true });
+  const loose = manager.register([Mod.Control], KeyCode.F24);
+
+  ctrlShift(0);
+  try {
+    manager.simulate(exact.id, EventType.Pressed);
+    manager.simulate(exact.id, EventType.Released);
+    manager.simulate(loose.id, EventType.Pressed);
+  } finally {
+    ctrlShift(2);
+  }
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => [event.id, event.eventType]),
+    [[loose.id, EventType.Pressed]],
+  );
+
+  events.length = 0;
+  manager.unregister([Mod.Control], KeyCode.F23);
+  manager.unregister([Mod.Control], KeyCode.F24);
+});
+
+(onWindows ? test.serial : test.skip)('ignored modifiers are left out of the exactModifiers check', async t => {
+  const locks = [Mod.CapsLock, Mod.NumLock, Mod.ScrollLock, Mod.FnLock, Mod.SymbolLock];
+  const { id } = manager.register([Mod.Control], KeyCode.F23, { exactModifiers: true });
+
+  manager.setIgnoredModifiers([Mod.Shift, ...locks]);
+  ctrlShift(0);
+  try {
+    manager.simulate(id, EventType.Pressed);
+  } finally {
+    ctrlShift(2);
+    manager.setIgnoredModifiers(locks);
+  }
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => [event.id, event.eventType]),
+    [[id, EventType.Pressed]],
+  );
+
+  events.length = 0;
+  manager.unregister([Mod.Control], KeyCode.F23);
+});
+
+(onWindows ? test.skip : test.serial)('exactModifiers never drops events off Windows', async t => {
+  const { id } = manager.register([Mod.Control], KeyCode.F23, { exactModifiers: true });
+
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+  t.deepEqual(
+    events.map(event => [event.id, event.eventType]),
+    [[id, EventType.Pressed]],
+  );
+
+  events.length = 0;
+  manager.unregister([Mod.Control], KeyCode.F23);
+});
@@ -0,0 +1,51 @@
+import test from 'ava'
+
+import { EventType, hotk, KeyCode, Mod } from '../index.js'
+
+test('destroy with flush delivers queued events before returning', t => {
+  const manager = hotk();
+  const response = manager.register([Mod.Control, Mod.Shift], KeyCode.F9);
+  const events = [];
+
+  manager.init(event => events.push(event));
+
+  t.true(manager.simulate(response.id, EventType.Pressed));
+  t.true(manager.simulate(response.id, EventType.Released));
+  t.is(events.length, 0);
+
+  manager.destroy({ flush: true });
+
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Released],
+  );
+  events.forEach(event => t.is(event.id, response.id));
+
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.F9);
+});
+
+test('a throwing callback does not drop the events queued after it', t => {
+  const manager = hotk();
+  const response = manager.register([Mod.Control, Mod.Shift], KeyCode.F10);
+  const events = [];
+
+  manager.init(event => {
+    events.push(event);
+    if (events.length === 1) {
+      throw new Error('callback failure');
+    }
+  });
+
+  t.true(manager.simulate(response.id, EventType.Pressed));
+  t.true(manager.simulate(response.id, EventType.Released));
+
+  t.throws(() => manager.destroy({ flush: true }), { message: 'callback failure' });
+
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Released],
+  );
+  t.is(manager.listeningSince(), null);
+
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.F10);
+});
@@ -0,0 +1,16 @@
+import test from 'ava'
+
+import { hotk, InitResult } from '../index.js'
+
+import { hooks } from './hooks.mjs'
+
+// The manager is created once per process, so this lives in its own file.
+(hooks ? test : test.skip)('init fails when the global event handler cannot be installed', t => {
+  process.env.HOTK_FAIL_EVENT_HANDLER = '1';
+  const manager = hotk();
+
+  t.is(manager.init(() => {}), InitResult.Failed);
+  // No callback was kept: the failure is reported again rather than `Replaced`.
+  t.is(manager.init(() => {}), InitResult.Failed);
+  t.false(manager.unref());
+});
@@ -0,0 +1,4 @@
+import { HotkManager } from '../index.js'
+
+// Builds with the `test-hooks` feature (`yarn build:test`) add the fault injection hooks some specs need.
+export const hooks = 'simulateLayoutChange' in HotkManager.prototype;
@@ -1,6 +1,14 @@
+import { execFileSync } from 'node:child_process'
+import { once } from 'node:events'
+import { Worker } from 'node:worker_threads'
+
 import test from 'ava'
 
-import { hotk, KeyCode, Mod } from '../index.js'
+import { BackendPriority, capabilities, combosConflict, descToAccelerator, ErrorKind, eventToDesc, eventToJson, EventType, formatAccelerator, getHotkeyId, hasInputPermission, hotk, hotkError, isModifierKey, keyCategory, KeyCategory, KeyCode, keyCodeToHuman, Mod, modsToHuman, modToHuman, Operation, parseAccelerator, parseHotkey, platformPrimaryMeta, requestInputPermission, RegistrationResult, ResponseCode, splitAccelerator, toMenuAccelerator, validateCombo, validateKeymap, validModifiersFor, WarningCode } from '../index.js'
+
+import { hooks } from './hooks.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
 
 test('_', async t => {
   const manager = hotk();
@@ -11,3 +19,1274 @@ test('_', async t => {
 
   t.truthy(response.isOk());
 });
+
+test('hotkError is null once the manager is created', t => {
+  t.truthy(hotk());
+  t.is(hotkError(), null);
+});
+
+test.serial('applyKeymapAsync resolves in order and reports progress', async t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Control, Mod.Shift], code: KeyCode.F1 },
+    { mods: [Mod.Control, Mod.Shift], code: KeyCode.F2 },
+    { mods: [Mod.Control, Mod.Shift], code: KeyCode.F3 },
+  ];
+  const progress = [];
+
+  const responses = await manager.applyKeymapAsync(descs, p => progress.push(p));
+  await new Promise(resolve => setImmediate(resolve));
+
+  t.is(responses.length, descs.length);
+  responses.forEach((response, i) => {
+    t.is(response.id, getHotkeyId(descs[i].code, descs[i].mods));
+  });
+  t.true(progress.length > 0);
+  t.deepEqual(progress[progress.length - 1], { done: 3, total: 3 });
+
+  descs.forEach(desc => manager.unregister(desc.mods, desc.code));
+});
+
+test('validateKeymap reports every problem at once', t => {
+  const reserved =
+    process.platform === 'darwin'
+      ? { mods: [Mod.Meta], code: KeyCode.Space }
+      : { mods: [Mod.Control, Mod.Alt], code: KeyCode.Delete };
+
+  const report = validateKeymap([
+    { mods: [Mod.Control], code: KeyCode.KeyA },
+    { mods: [Mod.Control], code: KeyCode.ShiftLeft },
+    { mods: [], code: KeyCode.Unidentified },
+    { mods: [Mod.Hyper], code: KeyCode.KeyB },
+    reserved,
+    { mods: [Mod.Control], code: KeyCode.KeyA },
+    { mods: [Mod.Alt, Mod.Shift], code: KeyCode.KeyC },
+  ]);
+
+  t.false(report.valid);
+  t.deepEqual(
+    report.entries.map(entry => entry.valid),
+    [true, false, false, false, false, false, true],
+  );
+  t.deepEqual(
+    report.entries.map(entry => entry.index),
+    [0, 1, 2, 3, 4, 5, 6],
+  );
+  t.regex(report.entries[1].reason, /modifier/);
+  t.regex(report.entries[2].reason, /not supported/);
+  t.regex(report.entries[3].reason, /Hyper/);
+  t.regex(report.entries[4].reason, /Reserved/);
+  t.regex(report.entries[5].reason, /Duplicate of entry 0/);
+  t.is(report.entries[0].reason, undefined);
+});
+
+test.serial('register reports every warning for a combination', t => {
+  const manager = hotk();
+
+  const bare = manager.register([], KeyCode.Escape);
+  manager.unregister([], KeyCode.Escape);
+
+  t.deepEqual(
+    bare.warnings.map(warning => warning.code),
+    [WarningCode.NoModifier, WarningCode.RiskyKey],
+  );
+  t.is(bare.warning, bare.warnings[0].message);
+  bare.warnings.forEach(warning => t.true(warning.severity >= 1 && warning.severity <= 3));
+
+  const hyper = manager.register([Mod.Control, Mod.Hyper], KeyCode.KeyH);
+  manager.unregister([Mod.Control, Mod.Hyper], KeyCode.KeyH);
+
+  t.deepEqual(
+    hyper.warnings.map(warning => warning.code),
+    [WarningCode.UnsupportedModifier],
+  );
+
+  const clean = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyH);
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyH);
+
+  t.deepEqual(clean.warnings, []);
+  t.falsy(clean.warning);
+});
+
+const onWindows = process.platform === 'win32';
+
+(onWindows ? test.serial : test.skip)('setBackendPriority changes the backend thread priority', t => {
+  const manager = hotk();
+
+  t.true(manager.setBackendPriority(BackendPriority.BelowNormal));
+  t.is(manager.backendPriority(), BackendPriority.BelowNormal);
+
+  t.true(manager.setBackendPriority(BackendPriority.Normal));
+  t.is(manager.backendPriority(), BackendPriority.Normal);
+});
+
+(onWindows ? test.serial : test.skip)('every manager shares a single backend thread', t => {
+  const first = hotk();
+  const second = hotk();
+
+  t.true(first.setBackendPriority(BackendPriority.BelowNormal));
+  t.is(second.backendPriority(), BackendPriority.BelowNormal);
+  t.deepEqual(second.recreate(), []);
+  t.true(first.isHealthy());
+  t.is(first.backendPriority(), BackendPriority.Normal);
+});
+
+test.serial('register rejects combinations that have no valid key', t => {
+  const manager = hotk();
+  const invalid = [
+    { mods: [], code: KeyCode.Unidentified },
+    { mods: [Mod.Control], code: KeyCode.Unidentified },
+    { mods: [], code: KeyCode.ShiftLeft },
+    { mods: [Mod.Alt], code: KeyCode.ControlRight },
+  ];
+
+  invalid.forEach(({ mods, code }) => {
+    t.regex(validateCombo(mods, code), /Unidentified|modifier/);
+    const response = manager.register(mods, code);
+    t.false(response.isOk());
+    t.is(response.error, validateCombo(mods, code));
+    t.is(manager.registrants(response.id), 0);
+  });
+
+  t.is(validateCombo([], KeyCode.Escape), null);
+  t.is(validateCombo([Mod.Control, Mod.Shift], KeyCode.KeyK), null);
+  t.is(validateCombo([Mod.Hyper], KeyCode.F5), null);
+});
+
+test.serial('IME and language keys have their own category and warning', t => {
+  const imeKeys = [
+    KeyCode.Convert,
+    KeyCode.NonConvert,
+    KeyCode.KanaMode,
+    KeyCode.Lang1,
+    KeyCode.Lang2,
+    KeyCode.Lang3,
+    KeyCode.Lang4,
+    KeyCode.Lang5,
+    KeyCode.Hiragana,
+    KeyCode.Katakana,
+  ];
+
+  imeKeys.forEach(code => t.is(keyCategory(code), KeyCategory.Ime));
+  t.is(keyCategory(KeyCode.KeyA), KeyCategory.Letter);
+  t.is(keyCategory(KeyCode.F5), KeyCategory.Function);
+
+  const manager = hotk();
+  const response = manager.register([Mod.Control], KeyCode.Lang1);
+  manager.unregister([Mod.Control], KeyCode.Lang1);
+
+  t.true(response.warnings.some(warning => warning.code === WarningCode.ImeKey));
+  t.false(validateKeymap([{ mods: [Mod.Control], code: KeyCode.Hiragana }]).valid);
+});
+
+(onWindows ? test : test.skip)('currentLayout reports the active keyboard layout', t => {
+  const layout = hotk().currentLayout();
+
+  t.is(typeof layout, 'string');
+  t.true(layout.length > 0);
+});
+
+(onWindows && hooks ? test.serial : test.skip)('setOnLayoutChange fires when the layout changes', async t => {
+  const manager = hotk();
+
+  const layout = await new Promise(resolve => {
+    t.true(manager.setOnLayoutChange(resolve));
+    t.true(manager.simulateLayoutChange());
+  });
+  manager.setOnLayoutChange(null);
+
+  t.is(layout, manager.currentLayout());
+});
+
+(onWindows ? test : test.skip)('isComboHeld is false when no key is held', t => {
+  const manager = hotk();
+
+  t.false(manager.isComboHeld([Mod.Control, Mod.Alt], KeyCode.F21));
+  t.false(manager.isComboHeld([], KeyCode.F22));
+});
+
+(onWindows ? test.skip : test)('isComboHeld is unsupported off Windows', t => {
+  t.false(hotk().isComboHeld([Mod.Control], KeyCode.KeyA));
+});
+
+(onWindows ? test.serial : test.skip)('setWatchUnmapped installs and removes the keyboard hook', async t => {
+  const manager = hotk();
+  const attempts = [];
+  // Presses and releases Ctrl+F22, which is not registered.
+  const script = `
+    $sig = '[DllImport("user32.dll")] public static extern void keybd_event(byte vk, byte scan, uint flags, UIntPtr extra);'
+    $user32 = Add-Type -MemberDefinition $sig -Name User32 -Namespace Hotk -PassThru
+    $user32::keybd_event(0x11, 0, 0, [UIntPtr]::Zero)
+    $user32::keybd_event(0x85, 0, 0, [UIntPtr]::Zero)
+    $user32::keybd_event(0x85, 0, 2, [UIntPtr]::Zero)
+    $user32::keybd_event(0x11, 0, 2, [UIntPtr]::Zero)
+  `;
+
+  t.false(manager.setWatchUnmapped(true));
+  t.true(manager.setWatchUnmapped(true, desc => attempts.push(desc)));
+  execFileSync('powershell', ['-NoProfile', '-NonInteractive', '-Command', script]);
+  await sleep(50);
+  t.true(manager.setWatchUnmapped(false));
+  t.deepEqual(attempts, [{ mods: [Mod.Control], code: KeyCode.F22 }]);
+
+  execFileSync('powershell', ['-NoProfile', '-NonInteractive', '-Command', script]);
+  await sleep(50);
+  t.is(attempts.length, 1);
+});
+
+(onWindows ? test.skip : test)('setWatchUnmapped is unsupported off Windows', t => {
+  const manager = hotk();
+
+  t.false(manager.setWatchUnmapped(true, () => {}));
+  t.false(manager.setWatchUnmapped(false));
+});
+
+(onWindows ? test : test.skip)('capabilities of the Windows backend', t => {
+  t.deepEqual(capabilities(), {
+    backend: 'windows',
+    sideSpecificModifiers: false,
+    mediaKeys: true,
+    liveKeyState: true,
+    focusGating: false,
+    layoutQueries: true,
+    lowLevelHook: true,
+    osRepeatControl: true,
+  });
+});
+
+(onWindows ? test.skip : test)('capabilities of the plain backend', t => {
+  t.deepEqual(capabilities(), {
+    backend: process.platform === 'darwin' ? 'macos' : 'x11',
+    sideSpecificModifiers: false,
+    mediaKeys: true,
+    liveKeyState: false,
+    focusGating: false,
+    layoutQueries: false,
+    lowLevelHook: false,
+    osRepeatControl: false,
+  });
+
+  const manager = hotk();
+  t.is(manager.currentLayout(), null);
+  t.false(manager.setIgnoreOsRepeat(true));
+});
+
+test.serial('registerChecked registers once and then is a no-op', t => {
+  const manager = hotk();
+  const id = getHotkeyId(KeyCode.KeyU, [Mod.Control, Mod.Shift]);
+
+  t.is(manager.registerChecked([Mod.Control, Mod.Shift], KeyCode.KeyU), RegistrationResult.Registered);
+  t.is(manager.registerChecked([Mod.Control, Mod.Shift], KeyCode.KeyU), RegistrationResult.AlreadyRegistered);
+  t.is(manager.registrants(id), 1);
+  t.is(manager.registerChecked([Mod.Control], KeyCode.Unidentified), RegistrationResult.Failed);
+
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyU);
+});
+
+test.serial('registerRaw registers a key code by name', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Shift];
+
+  const response = manager.registerRaw(mods, 'KeyJ');
+  t.true(response.isOk());
+  t.is(response.id, getHotkeyId(KeyCode.KeyJ, mods));
+  t.true(manager.isRegistered(mods, KeyCode.KeyJ));
+  t.true(manager.unregister(mods, KeyCode.KeyJ).isOk());
+
+  const invalid = manager.registerRaw(mods, 'NotAKey');
+  t.false(invalid.isOk());
+  t.is(invalid.errorKind, ErrorKind.InvalidArgument);
+  t.is(invalid.id, 0);
+  t.false(manager.registerRaw(mods, 'keyj').isOk());
+});
+
+test.serial('registerChecked from several threads registers exactly once', async t => {
+  const manager = hotk();
+  const start = new Int32Array(new SharedArrayBuffer(4));
+  const source = `
+    const { workerData, parentPort } = require('node:worker_threads');
+    const { hotk, KeyCode, Mod } = require(workerData.addon);
+    const manager = hotk();
+    Atomics.wait(workerData.start, 0, 0);
+    parentPort.postMessage(manager.registerChecked([Mod.Control, Mod.Shift], KeyCode.KeyY));
+  `;
+  const addon = new URL('../index.js', import.meta.url).pathname;
+  const workers = Array.from({ length: 8 }, () => new Worker(source, { eval: true, workerData: { addon, start } }));
+  const results = workers.map(worker => once(worker, 'message').then(([result]) => result));
+
+  await Promise.all(workers.map(worker => once(worker, 'online')));
+  await sleep(50);
+  Atomics.store(start, 0, 1);
+  Atomics.notify(start, 0);
+
+  const counts = {};
+  for (const result of await Promise.all(results)) {
+    counts[result] = (counts[result] ?? 0) + 1;
+  }
+  t.deepEqual(counts, {
+    [RegistrationResult.Registered]: 1,
+    [RegistrationResult.AlreadyRegistered]: 7,
+  });
+
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyY);
+  t.false(manager.isRegistered([Mod.Control, Mod.Shift], KeyCode.KeyY));
+});
+
+(onWindows ? test.skip : test.serial)('setOnLayoutChange is unsupported off Windows', t => {
+  const manager = hotk();
+
+  t.false(manager.setOnLayoutChange(() => {}));
+  if (hooks) {
+    t.false(manager.simulateLayoutChange());
+  }
+  t.is(manager.currentLayout(), null);
+  manager.setOnLayoutChange(null);
+});
+
+(onWindows ? test.serial : test.skip)('onError installs and removes the callback', t => {
+  const manager = hotk();
+
+  t.true(manager.onError(() => {}));
+  t.true(manager.onError(null));
+});
+
+(onWindows ? test.skip : test.serial)('onError is unsupported off Windows', t => {
+  const manager = hotk();
+
+  t.false(manager.onError(() => {}));
+  t.false(manager.onError(null));
+});
+
+(onWindows ? test.serial : test.skip)('foreign WM_USER messages do not stall the backend', t => {
+  const manager = hotk();
+
+  // Post a bare WM_USER to every thread of this process, as another component might.
+  const script = `
+    $sig = '[DllImport("user32.dll")] public static extern bool PostThreadMessageW(uint id, uint msg, UIntPtr w, IntPtr l);'
+    $user32 = Add-Type -MemberDefinition $sig -Name User32 -Namespace Hotk -PassThru
+    (Get-Process -Id ${process.pid}).Threads | ForEach-Object {
+      [void]$user32::PostThreadMessageW([uint32]$_.Id, 0x400, [UIntPtr]::Zero, [IntPtr]::Zero)
+    }
+  `;
+  execFileSync('powershell', ['-NoProfile', '-NonInteractive', '-Command', script]);
+
+  // A loop that took the foreign message for an action would block waiting for it
+  // and stop pumping messages, so the registration would never be answered.
+  t.true(manager.register([Mod.Control, Mod.Shift], KeyCode.F10).isOk());
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.F10);
+});
+
+test.serial('unregister frees the combination for a new registration', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt, Mod.Shift];
+
+  t.true(manager.register(mods, KeyCode.KeyU).isOk());
+  t.true(manager.unregister(mods, KeyCode.KeyU).isOk());
+  t.true(manager.register(mods, KeyCode.KeyU).isOk());
+  t.true(manager.unregister(mods, KeyCode.KeyU).isOk());
+});
+
+test.serial('unregistering an unknown hotkey reports the backend error', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt, Mod.Shift];
+
+  t.true(manager.register(mods, KeyCode.KeyV).isOk());
+  t.true(manager.unregister(mods, KeyCode.KeyV).isOk());
+
+  const again = manager.unregister(mods, KeyCode.KeyV);
+  t.false(again.isOk());
+  t.is(again.code, ResponseCode.Error);
+  t.regex(again.error, /unregister/i);
+});
+
+test.serial('isRegistered follows register and unregister', t => {
+  const manager = hotk();
+
+  t.false(manager.isRegistered([Mod.Control, Mod.Alt], KeyCode.KeyI));
+  manager.register([Mod.Control, Mod.Alt], KeyCode.KeyI);
+  t.true(manager.isRegistered([Mod.Control, Mod.Alt], KeyCode.KeyI));
+  t.true(manager.isRegistered([Mod.Alt, Mod.Control], KeyCode.KeyI));
+  t.false(manager.isRegistered([Mod.Control], KeyCode.KeyI));
+
+  manager.unregister([Mod.Control, Mod.Alt], KeyCode.KeyI);
+  t.false(manager.isRegistered([Mod.Control, Mod.Alt], KeyCode.KeyI));
+});
+
+test.serial('ignored modifiers are stripped from registrations', t => {
+  const manager = hotk();
+  const locks = [Mod.CapsLock, Mod.NumLock, Mod.ScrollLock, Mod.FnLock, Mod.SymbolLock];
+
+  const stripped = manager.register([Mod.Control, Mod.NumLock], KeyCode.KeyN);
+  t.is(stripped.id, getHotkeyId(KeyCode.KeyN, [Mod.Control]));
+  t.deepEqual(manager.hotkeyForId(stripped.id).mods, [Mod.Control]);
+  t.true(manager.isRegistered([Mod.Control], KeyCode.KeyN));
+  t.true(manager.unregister([Mod.Control, Mod.CapsLock], KeyCode.KeyN).isOk());
+
+  manager.setIgnoredModifiers([]);
+  try {
+    const kept = manager.register([Mod.Control, Mod.NumLock], KeyCode.KeyN);
+    t.is(kept.id, getHotkeyId(KeyCode.KeyN, [Mod.Control, Mod.NumLock]));
+    t.false(manager.isRegistered([Mod.Control], KeyCode.KeyN));
+    manager.unregister([Mod.Control, Mod.NumLock], KeyCode.KeyN);
+  } finally {
+    manager.setIgnoredModifiers(locks);
+  }
+});
+
+test.serial('unregisterAll removes every hotkey', t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Control, Mod.Alt], code: KeyCode.KeyG },
+    { mods: [Mod.Shift, Mod.Alt], code: KeyCode.F6 },
+  ];
+  descs.forEach(desc => manager.register(desc.mods, desc.code));
+  // A second registrant doesn't keep it registered.
+  manager.register(descs[0].mods, descs[0].code);
+  const ids = descs.map(desc => getHotkeyId(desc.code, desc.mods));
+
+  const responses = manager.unregisterAll();
+  const own = responses.filter(response => ids.includes(response.id));
+  t.deepEqual(own.map(response => response.id), [...ids].sort((a, b) => a - b));
+  own.forEach(response => {
+    t.true(response.isOk());
+    t.is(response.operation, Operation.Unregister);
+  });
+  t.deepEqual(manager.listRegistered(), []);
+  t.deepEqual(manager.unregisterAll(), []);
+
+  descs.forEach(desc => t.true(manager.register(desc.mods, desc.code).isOk()));
+  descs.forEach(desc => manager.unregister(desc.mods, desc.code));
+});
+
+test.serial('registerMany returns index-aligned responses', t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Control, Mod.Alt], code: KeyCode.KeyE },
+    { mods: [Mod.Control], code: KeyCode.Unidentified },
+    { mods: [Mod.Shift, Mod.Alt], code: KeyCode.F5 },
+    { mods: [Mod.Alt, Mod.Control], code: KeyCode.KeyE },
+  ];
+
+  const responses = manager.registerMany(descs);
+  t.is(responses.length, descs.length);
+  responses.forEach((response, i) => {
+    t.is(response.id, getHotkeyId(descs[i].code, descs[i].mods));
+    t.is(response.operation, Operation.Register);
+  });
+  t.deepEqual(responses.map(response => response.isOk()), [true, false, true, true]);
+  t.true(manager.isRegistered(descs[0].mods, descs[0].code));
+  t.true(manager.isRegistered(descs[2].mods, descs[2].code));
+
+  // The repeated combination counts as a second registrant.
+  t.true(manager.unregister(descs[0].mods, descs[0].code).isOk());
+  t.true(manager.isRegistered(descs[0].mods, descs[0].code));
+  t.true(manager.unregister(descs[0].mods, descs[0].code).isOk());
+  t.false(manager.isRegistered(descs[0].mods, descs[0].code));
+  manager.unregister(descs[2].mods, descs[2].code);
+
+  t.deepEqual(manager.registerMany([]), []);
+});
+
+test.serial('setOnKey fires for every combination on the key', async t => {
+  const manager = hotk();
+  const keyEvents = [];
+  const ctrlA = manager.register([Mod.Control, Mod.Alt], KeyCode.KeyA);
+  const shiftA = manager.register([Mod.Shift, Mod.Alt], KeyCode.KeyA);
+  const ctrlB = manager.register([Mod.Control, Mod.Alt], KeyCode.KeyB);
+
+  manager.setOnKey(KeyCode.KeyA, event => keyEvents.push(event));
+  manager.simulate(ctrlA.id, EventType.Pressed);
+  manager.simulate(shiftA.id, EventType.Pressed);
+  manager.simulate(ctrlB.id, EventType.Pressed);
+  await sleep(20);
+
+  t.deepEqual(keyEvents.map(event => event.id), [ctrlA.id, shiftA.id]);
+  keyEvents.forEach(event => t.is(event.code, KeyCode.KeyA));
+
+  manager.setOnKey(KeyCode.KeyA, null);
+  manager.simulate(ctrlA.id, EventType.Released);
+  await sleep(20);
+  t.is(keyEvents.length, 2);
+
+  manager.unregister([Mod.Control, Mod.Alt], KeyCode.KeyA);
+  manager.unregister([Mod.Shift, Mod.Alt], KeyCode.KeyA);
+  manager.unregister([Mod.Control, Mod.Alt], KeyCode.KeyB);
+});
+
+test.serial('failed responses carry an errorKind', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt, Mod.Shift];
+
+  const ok = manager.register(mods, KeyCode.KeyZ);
+  t.is(ok.errorKind, null);
+  t.is(manager.register([Mod.Control], KeyCode.Unidentified).errorKind, ErrorKind.Unsupported);
+  t.true(manager.unregister(mods, KeyCode.KeyZ).isOk());
+  t.is(manager.unregister(mods, KeyCode.KeyZ).errorKind, ErrorKind.NotRegistered);
+  t.is(manager.registerIn('no-such-profile', mods, KeyCode.KeyZ).errorKind, ErrorKind.InvalidArgument);
+});
+
+test.serial('responses tell which operation they describe', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt, Mod.Shift];
+
+  const registered = manager.register(mods, KeyCode.KeyO);
+  const again = manager.register(mods, KeyCode.KeyO);
+  const unregistered = manager.unregister(mods, KeyCode.KeyO);
+  const unregisteredAgain = manager.unregister(mods, KeyCode.KeyO);
+
+  t.is(registered.operation, Operation.Register);
+  t.is(again.operation, Operation.Register);
+  t.is(unregistered.operation, Operation.Unregister);
+  t.is(unregisteredAgain.operation, Operation.Unregister);
+});
+
+test.serial('reserveCapacity keeps registrations working', t => {
+  const manager = hotk();
+  const codes = [KeyCode.F13, KeyCode.F14, KeyCode.F15, KeyCode.F16];
+
+  manager.reserveCapacity(500);
+
+  codes.forEach(code => t.true(manager.register([Mod.Control, Mod.Alt], code).isOk()));
+  codes.forEach(code => manager.unregister([Mod.Control, Mod.Alt], code));
+});
+
+test.serial('activateProfile switches which hotkeys are registered', async t => {
+  const manager = hotk();
+  const game = { mods: [Mod.Control, Mod.Alt], code: KeyCode.KeyG };
+  const editor = { mods: [Mod.Control, Mod.Alt], code: KeyCode.KeyE };
+  const gameId = getHotkeyId(game.code, game.mods);
+  const editorId = getHotkeyId(editor.code, editor.mods);
+  const gameEvents = [];
+
+  t.true(manager.createProfile('game'));
+  t.true(manager.createProfile('editor'));
+  t.false(manager.createProfile('game'));
+  t.false(manager.registerIn('missing', game.mods, game.code).isOk());
+
+  manager.registerIn('game', game.mods, game.code, event => gameEvents.push(event));
+  manager.registerIn('editor', editor.mods, editor.code);
+  t.false(manager.simulate(gameId, EventType.Pressed));
+
+  t.is(manager.activateProfile('game').length, 1);
+  t.is(manager.activeProfile(), 'game');
+  t.true(manager.simulate(gameId, EventType.Pressed));
+  t.false(manager.simulate(editorId, EventType.Pressed));
+
+  await new Promise(resolve => setTimeout(resolve, 50));
+  t.deepEqual(
+    gameEvents.map(event => [event.id, event.eventType]),
+    [[gameId, EventType.Pressed]],
+  );
+
+  t.true(manager.activateProfile('editor').every(response => response.isOk()));
+  t.is(manager.activeProfile(), 'editor');
+  t.false(manager.simulate(gameId, EventType.Pressed));
+  t.true(manager.simulate(editorId, EventType.Pressed));
+  t.is(manager.activateProfile('missing'), null);
+
+  manager.unregister(editor.mods, editor.code);
+});
+
+test.serial('registerDeferred reports each result through the callback', async t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Control, Mod.Alt], code: KeyCode.Digit1 },
+    { mods: [Mod.Control, Mod.Alt], code: KeyCode.Digit2 },
+    { mods: [Mod.Control, Mod.Alt], code: KeyCode.Digit3 },
+  ];
+
+  const responses = await new Promise((resolve, reject) => {
+    const received = [];
+    const timeout = setTimeout(() => reject(new Error('deferred results not reported')), 2000);
+    manager.setOnDeferredResult(response => {
+      received.push(response);
+      if (received.length === descs.length) {
+        clearTimeout(timeout);
+        resolve(received);
+      }
+    });
+
+    const ids = descs.map(desc => manager.registerDeferred(desc.mods, desc.code));
+    t.deepEqual(
+      ids,
+      descs.map(desc => getHotkeyId(desc.code, desc.mods)),
+    );
+  });
+  manager.setOnDeferredResult(null);
+
+  t.deepEqual(
+    responses.map(response => response.id),
+    descs.map(desc => getHotkeyId(desc.code, desc.mods)),
+  );
+  responses.forEach(response => {
+    t.true(response.isOk());
+    t.is(response.operation, Operation.Register);
+  });
+
+  descs.forEach(desc => manager.unregister(desc.mods, desc.code));
+});
+
+test('modsToHuman joins modifiers with the platform convention', t => {
+  const mods = [Mod.Meta, Mod.Shift, Mod.Control, Mod.Shift, Mod.Super];
+
+  if (process.platform === 'darwin') {
+    t.is(modsToHuman(mods), '⌃⇧⌘');
+  } else if (onWindows) {
+    t.is(modsToHuman(mods), 'Ctrl+Shift+Win');
+  } else {
+    t.is(modsToHuman(mods), 'Ctrl+Shift+Super');
+  }
+  t.is(modsToHuman([]), '');
+});
+
+test.serial('identical combos share one OS registration', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt];
+  const id = getHotkeyId(KeyCode.KeyK, mods);
+
+  // A second OS registration of the same combo would fail as already registered.
+  t.true(manager.register(mods, KeyCode.KeyK).isOk());
+  t.true(manager.register(mods, KeyCode.KeyK).isOk());
+  t.is(manager.registrants(id), 2);
+
+  t.true(manager.unregister(mods, KeyCode.KeyK).isOk());
+  t.is(manager.registrants(id), 1);
+  t.true(manager.simulate(id, EventType.Pressed));
+
+  t.true(manager.unregister(mods, KeyCode.KeyK).isOk());
+  t.is(manager.registrants(id), 0);
+  t.false(manager.simulate(id, EventType.Pressed));
+});
+
+test.serial('wouldConflict detects combinations registered in this process', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Super];
+
+  t.false(manager.wouldConflict(mods, KeyCode.KeyW));
+  manager.register(mods, KeyCode.KeyW);
+  t.true(manager.wouldConflict(mods, KeyCode.KeyW));
+  t.true(manager.wouldConflict([Mod.Meta, Mod.Control, Mod.CapsLock], KeyCode.KeyW));
+  t.false(manager.wouldConflict([Mod.Control], KeyCode.KeyW));
+
+  manager.unregister(mods, KeyCode.KeyW);
+  t.false(manager.wouldConflict(mods, KeyCode.KeyW));
+});
+
+test('computed ids never collide and leave the top bit free', t => {
+  const modSets = [[], [Mod.Control], [Mod.Alt, Mod.Shift], [Mod.Super], [Mod.Control, Mod.Alt, Mod.Shift, Mod.Super, Mod.Hyper]];
+  const ids = new Map();
+
+  for (const code of Object.values(KeyCode)) {
+    for (const mods of modSets) {
+      const id = getHotkeyId(code, mods);
+      const combo = `${mods.join('+')}+${code}`;
+      t.is(ids.get(id) ?? combo, combo);
+      t.true(id < 0x80000000);
+      ids.set(id, combo);
+    }
+  }
+});
+
+test.serial('combinations sharing an id are the same hotkey, not a collision', t => {
+  const manager = hotk();
+  const meta = manager.register([Mod.Meta, Mod.Alt], KeyCode.KeyG);
+  const sup = manager.register([Mod.Super, Mod.Alt], KeyCode.KeyG);
+
+  t.is(sup.id, meta.id);
+  t.true(sup.isOk());
+  t.not(sup.errorKind, ErrorKind.IdCollision);
+  t.is(manager.registrants(meta.id), 2);
+
+  manager.unregister([Mod.Meta, Mod.Alt], KeyCode.KeyG);
+  manager.unregister([Mod.Super, Mod.Alt], KeyCode.KeyG);
+  t.false(manager.isRegistered([Mod.Meta, Mod.Alt], KeyCode.KeyG));
+});
+
+test.serial('idempotent registrations reuse the existing one', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt];
+
+  const first = manager.register(mods, KeyCode.KeyI, { idempotent: true });
+  const again = manager.register(mods, KeyCode.KeyI, { idempotent: true });
+  t.true(again.isOk());
+  t.is(again.id, first.id);
+  t.is(manager.registrants(first.id), 1);
+  t.is(manager.pendingActions(), 0);
+
+  t.true(manager.unregister(mods, KeyCode.KeyI).isOk());
+  t.false(manager.isRegistered(mods, KeyCode.KeyI));
+});
+
+test.serial('register succeeds right after the manager is created', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt];
+
+  t.true(manager.register(mods, KeyCode.KeyW).isOk());
+  manager.unregister(mods, KeyCode.KeyW);
+});
+
+test.serial('releaseOsGrabs frees the OS registrations until reacquireOsGrabs', t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Control, Mod.Alt], code: KeyCode.KeyR },
+    { mods: [Mod.Control, Mod.Alt], code: KeyCode.KeyT },
+  ];
+  const ids = descs.map(desc => manager.register(desc.mods, desc.code).id).sort((a, b) => a - b);
+
+  // Other tests may have left hotkeys registered.
+  const released = manager.releaseOsGrabs().filter(response => ids.includes(response.id));
+  t.deepEqual(
+    released.map(response => [response.id, response.operation, response.isOk()]),
+    ids.map(id => [id, Operation.Unregister, true]),
+  );
+  t.deepEqual(manager.releaseOsGrabs(), []);
+  // Still registered as far as the manager is concerned.
+  ids.forEach(id => t.is(manager.registrants(id), 1));
+  t.true(manager.exportAccelerators().includes('Control+Alt+KeyR'));
+
+  // Forgetting a released hotkey doesn't touch the OS.
+  t.true(manager.unregister(descs[1].mods, descs[1].code).isOk());
+  t.is(manager.registrants(getHotkeyId(descs[1].code, descs[1].mods)), 0);
+
+  const reacquired = manager.reacquireOsGrabs().filter(response => ids.includes(response.id));
+  t.deepEqual(
+    reacquired.map(response => [response.id, response.operation, response.isOk()]),
+    [[getHotkeyId(descs[0].code, descs[0].mods), Operation.Register, true]],
+  );
+  t.deepEqual(manager.reacquireOsGrabs(), []);
+
+  // Registered at the OS level again.
+  t.true(manager.unregister(descs[0].mods, descs[0].code).isOk());
+});
+
+test.serial('suspend pauses the hotkeys until resume, keeping them listed', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt];
+  const { id } = manager.register(mods, KeyCode.KeyP);
+
+  t.deepEqual(manager.suspend(), []);
+  t.true(manager.isRegistered(mods, KeyCode.KeyP));
+  t.true(manager.listRegistered().some(desc => desc.code === KeyCode.KeyP));
+  // The OS grab is freed, there is nothing left to release.
+  t.deepEqual(manager.releaseOsGrabs(), []);
+
+  t.deepEqual(manager.resume(), []);
+  t.deepEqual(manager.reacquireOsGrabs(), []);
+  t.is(manager.registrants(id), 1);
+
+  t.true(manager.unregister(mods, KeyCode.KeyP).isOk());
+});
+
+test.serial('setBatchCallback delivers the events of an interval together', async t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt];
+  const { id } = manager.register(mods, KeyCode.KeyB);
+  const batches = [];
+
+  manager.setBatchCallback(events => batches.push(events.map(event => event.eventType)), { intervalMs: 100 });
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+  t.deepEqual(batches, []);
+  await sleep(150);
+  t.deepEqual(batches, [[EventType.Pressed, EventType.Released, EventType.Pressed]]);
+
+  // A full batch is delivered without waiting for the interval.
+  batches.length = 0;
+  manager.setBatchCallback(events => batches.push(events.map(event => event.eventType)), {
+    intervalMs: 1000,
+    maxSize: 2,
+  });
+  manager.simulate(id, EventType.Released);
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(50);
+  t.deepEqual(batches, [[EventType.Released, EventType.Pressed]]);
+
+  // Clearing it delivers what is still waiting.
+  manager.setBatchCallback(null);
+  await sleep(50);
+  t.deepEqual(batches, [[EventType.Released, EventType.Pressed], [EventType.Released]]);
+
+  manager.unregister(mods, KeyCode.KeyB);
+});
+
+test('validModifiersFor constrains modifiers to the key', t => {
+  const command = process.platform === 'darwin' ? Mod.Meta : Mod.Super;
+  const standard = [Mod.Control, Mod.Alt, Mod.Shift, command];
+  const locks = [Mod.CapsLock, Mod.NumLock, Mod.ScrollLock, Mod.FnLock, Mod.SymbolLock];
+
+  t.deepEqual(validModifiersFor(KeyCode.KeyA), standard);
+  t.deepEqual(validModifiersFor(KeyCode.NumpadAdd), standard);
+  [KeyCode.Numpad0, KeyCode.Numpad7, KeyCode.NumpadDecimal].forEach(code => {
+    const mods = validModifiersFor(code);
+    t.false(mods.includes(Mod.Shift));
+    locks.forEach(lock => t.false(mods.includes(lock)));
+  });
+
+  t.deepEqual(validModifiersFor(KeyCode.ShiftLeft), []);
+  t.deepEqual(validModifiersFor(KeyCode.Unidentified), []);
+});
+
+test('combosConflict follows the OS matching rules', t => {
+  const ctrlA = { mods: [Mod.Control], code: KeyCode.KeyA };
+
+  t.true(combosConflict(ctrlA, { mods: [Mod.Control], code: KeyCode.KeyA }));
+  t.false(combosConflict(ctrlA, { mods: [Mod.Alt], code: KeyCode.KeyB }));
+  // Modifiers are matched exactly, so a superset is a different hotkey.
+  t.false(combosConflict(ctrlA, { mods: [Mod.Control, Mod.Shift], code: KeyCode.KeyA }));
+  // Unless the extra modifier is one the OS ignores.
+  t.true(combosConflict(ctrlA, { mods: [Mod.Control, Mod.Hyper], code: KeyCode.KeyA }));
+  t.true(combosConflict({ mods: [Mod.Meta], code: KeyCode.KeyK }, { mods: [Mod.Super], code: KeyCode.KeyK }));
+
+  const command = process.platform === 'darwin' ? Mod.Meta : Mod.Control;
+  t.true(combosConflict({ mods: [Mod.CommandOrControl], code: KeyCode.KeyS }, { mods: [command], code: KeyCode.KeyS }));
+  t.is(
+    combosConflict({ mods: [Mod.Control], code: KeyCode.Enter }, { mods: [Mod.Control], code: KeyCode.NumpadEnter }),
+    onWindows,
+  );
+});
+
+(process.platform === 'darwin' ? test : test.skip)('modToHuman uses symbols on macOS', t => {
+  t.is(modToHuman(Mod.Meta), '⌘');
+  t.is(modToHuman(Mod.Control), '⌃');
+  t.is(modToHuman(Mod.Alt), '⌥');
+  t.is(modToHuman(Mod.Shift), '⇧');
+  t.is(modToHuman(Mod.CommandOrControl), '⌘');
+});
+
+(onWindows ? test : test.skip)('modToHuman uses Windows names', t => {
+  t.is(modToHuman(Mod.Meta), 'Win');
+  t.is(modToHuman(Mod.Super), 'Win');
+  t.is(modToHuman(Mod.Control), 'Ctrl');
+  t.is(modToHuman(Mod.CommandOrControl), 'Ctrl');
+});
+
+(process.platform === 'linux' ? test : test.skip)('modToHuman uses Linux names', t => {
+  t.is(modToHuman(Mod.Meta), 'Super');
+  t.is(modToHuman(Mod.Control), 'Ctrl');
+  t.is(modToHuman(Mod.AltGraph), 'AltGr');
+  t.is(modToHuman(Mod.CommandOrControl), 'Ctrl');
+});
+
+test('keyCodeToHuman labels every identified key', t => {
+  t.is(keyCodeToHuman(KeyCode.KeyA), 'a');
+  t.is(keyCodeToHuman(KeyCode.F5), 'f5');
+  t.is(keyCodeToHuman(KeyCode.ArrowUp), '↑');
+  t.is(keyCodeToHuman(KeyCode.Numpad0), 'Num 0');
+  t.is(keyCodeToHuman(KeyCode.PageUp), 'Page Up');
+  t.is(keyCodeToHuman(KeyCode.Escape), 'Esc');
+  t.is(keyCodeToHuman(KeyCode.Unidentified), null);
+
+  const unlabeled = Object.values(KeyCode).filter(code => keyCodeToHuman(code) === null);
+  t.deepEqual(unlabeled, [KeyCode.Unidentified]);
+});
+
+test('eventToDesc keeps the code and modifiers of an event', t => {
+  const mods = [Mod.Control, Mod.Shift];
+  const event = {
+    id: getHotkeyId(KeyCode.KeyP, mods),
+    code: KeyCode.KeyP,
+    mods,
+    eventType: EventType.Released,
+  };
+
+  t.deepEqual(eventToDesc(event), { code: KeyCode.KeyP, mods });
+});
+
+test('eventToJson serializes every field of an event', t => {
+  const mods = [Mod.Shift, Mod.Control];
+  const id = getHotkeyId(KeyCode.KeyP, mods);
+  const json = eventToJson({
+    id,
+    code: KeyCode.KeyP,
+    mods,
+    eventType: EventType.MultiTap,
+    deltaMs: 12.5,
+    synthetic: true,
+    taps: 2,
+    timestamp: 1700000000000.25,
+  });
+
+  t.deepEqual(Object.keys(JSON.parse(json)), ['id', 'code', 'mods', 'accelerator', 'eventType', 'deltaMs', 'synthetic', 'taps', 'timestamp']);
+  t.deepEqual(JSON.parse(json), {
+    id,
+    code: 'KeyP',
+    mods: ['Shift', 'Control'],
+    accelerator: 'Control+Shift+KeyP',
+    eventType: 'MultiTap',
+    deltaMs: 12.5,
+    synthetic: true,
+    taps: 2,
+    timestamp: 1700000000000.25,
+  });
+
+  const minimal = JSON.parse(eventToJson({ id, code: KeyCode.KeyP, mods: [], eventType: EventType.Pressed }));
+  t.is(minimal.eventType, 'Pressed');
+  t.deepEqual(minimal.mods, []);
+  t.is(minimal.deltaMs, null);
+  t.is(minimal.synthetic, null);
+  t.is(minimal.taps, null);
+  t.is(minimal.timestamp, null);
+});
+
+(process.platform === 'darwin' ? test : test.skip)('hasInputPermission reports the macOS trust state', t => {
+  t.is(typeof hasInputPermission(), 'boolean');
+});
+
+(process.platform === 'darwin' ? test.skip : test)('input permission is always granted off macOS', t => {
+  t.true(hasInputPermission());
+  t.true(requestInputPermission());
+});
+
+(onWindows ? test.serial : test.skip)('back-to-back registrations all reach the backend', t => {
+  const manager = hotk();
+  const codes = [
+    KeyCode.KeyA, KeyCode.KeyB, KeyCode.KeyC, KeyCode.KeyD, KeyCode.KeyF,
+    KeyCode.KeyH, KeyCode.KeyI, KeyCode.KeyJ, KeyCode.KeyM, KeyCode.KeyN,
+  ];
+  const mods = [Mod.Control, Mod.Alt, Mod.Shift];
+
+  for (let round = 0; round < 5; round++) {
+    codes.forEach(code => t.true(manager.register(mods, code).isOk()));
+    codes.forEach(code => t.true(manager.unregister(mods, code).isOk()));
+  }
+});
+
+test('toMenuAccelerator uses the menu accelerator dialect', t => {
+  const onMac = process.platform === 'darwin';
+  const primary = onMac ? Mod.Meta : Mod.Control;
+
+  t.is(toMenuAccelerator({ mods: [Mod.Shift, Mod.CommandOrControl], code: KeyCode.KeyA }), 'CmdOrCtrl+Shift+A');
+  t.is(toMenuAccelerator({ mods: [primary, Mod.Alt], code: KeyCode.Digit1 }), 'CmdOrCtrl+Alt+1');
+  t.is(toMenuAccelerator({ mods: [Mod.CommandOrControl, primary], code: KeyCode.ArrowUp }), 'CmdOrCtrl+Up');
+  t.is(
+    toMenuAccelerator({ mods: [onMac ? Mod.Control : Mod.Super, Mod.Shift], code: KeyCode.F5 }),
+    onMac ? 'Ctrl+Shift+F5' : 'Shift+Super+F5',
+  );
+  t.is(toMenuAccelerator({ mods: [], code: KeyCode.Numpad0 }), 'num0');
+  t.is(toMenuAccelerator({ mods: [Mod.Alt], code: KeyCode.AudioVolumeUp }), 'Alt+VolumeUp');
+  t.is(toMenuAccelerator({ mods: [Mod.Control], code: KeyCode.Backslash }), onMac ? 'Ctrl+\\' : 'CmdOrCtrl+\\');
+  t.is(toMenuAccelerator({ mods: [Mod.Hyper], code: KeyCode.KeyA }), null);
+  t.is(toMenuAccelerator({ mods: [Mod.Control], code: KeyCode.F25 }), null);
+});
+
+test('formatAccelerator is stable and round-trips through parseAccelerator', t => {
+  const descs = [
+    { mods: [Mod.Meta, Mod.Shift, Mod.Alt, Mod.Control], code: KeyCode.KeyK },
+    { mods: [Mod.Control, Mod.Alt, Mod.Shift, Mod.Meta], code: KeyCode.KeyK },
+    { mods: [Mod.Shift, Mod.Meta, Mod.Control, Mod.Alt], code: KeyCode.KeyK },
+  ];
+
+  descs.forEach(desc => t.is(formatAccelerator(desc), 'Control+Alt+Shift+Meta+KeyK'));
+  t.is(formatAccelerator({ mods: [], code: KeyCode.F1 }), 'F1');
+  t.deepEqual(parseAccelerator(formatAccelerator(descs[0])), descs[1]);
+});
+
+test('splitAccelerator returns the modifiers and key code', t => {
+  t.deepEqual(splitAccelerator('Shift+Control+Alt+KeyK'), {
+    mods: [Mod.Control, Mod.Alt, Mod.Shift],
+    code: KeyCode.KeyK,
+  });
+  t.deepEqual(splitAccelerator('Control+Control+F4'), { mods: [Mod.Control], code: KeyCode.F4 });
+  t.deepEqual(splitAccelerator('Escape'), { mods: [], code: KeyCode.Escape });
+  t.deepEqual(splitAccelerator('Win+KeyA'), parseHotkey('Win+KeyA'));
+
+  t.is(splitAccelerator('Control+Nope'), null);
+  t.is(splitAccelerator(''), null);
+});
+
+(process.platform === 'darwin' ? test : test.skip)('the primary meta is Meta on macOS', t => {
+  t.is(platformPrimaryMeta(), Mod.Meta);
+  t.deepEqual(parseHotkey('Super+KeyK'), { mods: [Mod.Meta], code: KeyCode.KeyK });
+});
+
+(process.platform === 'darwin' ? test.skip : test)('the primary meta is Super off macOS', t => {
+  t.is(platformPrimaryMeta(), Mod.Super);
+  t.deepEqual(parseHotkey('Super+KeyK'), { mods: [Mod.Super], code: KeyCode.KeyK });
+});
+
+test('parseHotkey reads Win and Cmd as the primary meta', t => {
+  const meta = platformPrimaryMeta();
+
+  t.deepEqual(parseHotkey('Win+Shift+KeyK'), { mods: [meta, Mod.Shift], code: KeyCode.KeyK });
+  t.deepEqual(parseHotkey('Cmd+Space'), { mods: [meta], code: KeyCode.Space });
+  t.deepEqual(parseHotkey('Meta+Space'), { mods: [Mod.Meta], code: KeyCode.Space });
+  t.is(getHotkeyId(KeyCode.Space, [Mod.Meta]), getHotkeyId(KeyCode.Space, [Mod.Super]));
+});
+
+test('parseAccelerator is case-insensitive and accepts aliases', t => {
+  t.deepEqual(parseAccelerator('Control+Shift+KeyK'), { mods: [Mod.Control, Mod.Shift], code: KeyCode.KeyK });
+  t.deepEqual(parseAccelerator('ctrl + ALT + keyk'), { mods: [Mod.Control, Mod.Alt], code: KeyCode.KeyK });
+  t.deepEqual(parseAccelerator('Cmd+Space'), { mods: [platformPrimaryMeta()], code: KeyCode.Space });
+  t.deepEqual(parseAccelerator('win+f5'), { mods: [platformPrimaryMeta()], code: KeyCode.F5 });
+  t.deepEqual(parseAccelerator('meta+f5'), { mods: [Mod.Meta], code: KeyCode.F5 });
+  t.deepEqual(parseAccelerator('Escape'), { mods: [], code: KeyCode.Escape });
+
+  t.is(parseAccelerator('Ctrl+Shift'), null);
+  t.is(parseAccelerator('Ctrl+'), null);
+  t.is(parseAccelerator('Ctrl+Nope+KeyK'), null);
+  t.is(parseAccelerator(''), null);
+});
+
+test.serial('exportAccelerators round-trips through parseHotkey', t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Shift, Mod.Control], code: KeyCode.KeyX },
+    { mods: [Mod.Alt, Mod.Control, Mod.Alt], code: KeyCode.Digit7 },
+    { mods: [Mod.Meta, Mod.Shift], code: KeyCode.F11 },
+  ];
+  descs.forEach(desc => manager.register(desc.mods, desc.code));
+
+  const exported = manager.exportAccelerators();
+  t.true(exported.includes('Control+Shift+KeyX'));
+  t.true(exported.includes('Control+Alt+Digit7'));
+  t.true(exported.includes('Shift+Meta+F11'));
+
+  const parsed = exported.map(parseHotkey);
+  parsed.forEach(desc => manager.unregister(desc.mods, desc.code));
+  parsed.forEach(desc => t.true(manager.register(desc.mods, desc.code).isOk()));
+
+  t.deepEqual(manager.exportAccelerators(), exported);
+  t.is(parseHotkey('Control+Nope'), null);
+  t.is(parseHotkey('Control+'), null);
+
+  descs.forEach(desc => manager.unregister(desc.mods, desc.code));
+});
+
+test.serial('keymapFingerprint ignores registration order', t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Control, Mod.Alt], code: KeyCode.KeyF },
+    { mods: [Mod.Shift, Mod.Alt], code: KeyCode.Digit4 },
+    { mods: [Mod.Control, Mod.Shift], code: KeyCode.F6 },
+  ];
+  const empty = manager.keymapFingerprint();
+  t.regex(empty, /^[0-9a-f]{16}$/);
+
+  descs.forEach(desc => manager.register(desc.mods, desc.code));
+  const fingerprint = manager.keymapFingerprint();
+  descs.forEach(desc => manager.unregister(desc.mods, desc.code));
+
+  [...descs].reverse().forEach(desc => manager.register([...desc.mods].reverse(), desc.code));
+  t.is(manager.keymapFingerprint(), fingerprint);
+
+  manager.unregister(descs[0].mods, descs[0].code);
+  t.not(manager.keymapFingerprint(), fingerprint);
+  t.not(manager.keymapFingerprint(), empty);
+
+  descs.slice(1).forEach(desc => manager.unregister(desc.mods, desc.code));
+  t.is(manager.keymapFingerprint(), empty);
+});
+
+test.serial('listRegistered returns the registered combinations sorted by id', t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Control, Mod.Alt], code: KeyCode.KeyM },
+    { mods: [Mod.Shift, Mod.Alt], code: KeyCode.F7 },
+  ];
+  descs.forEach(desc => manager.register(desc.mods, desc.code));
+
+  const listed = manager.listRegistered();
+  descs.forEach(desc => {
+    t.deepEqual(
+      listed.filter(entry => descToAccelerator(entry) === descToAccelerator(desc)),
+      [desc],
+    );
+  });
+  const ids = listed.map(desc => getHotkeyId(desc.code, desc.mods));
+  t.deepEqual(ids, [...ids].sort((a, b) => a - b));
+  t.deepEqual(manager.listRegistered(), listed);
+  t.deepEqual(listed.map(descToAccelerator), manager.exportAccelerators());
+
+  descs.forEach(desc => manager.unregister(desc.mods, desc.code));
+});
+
+test.serial('labelsById matches descToAccelerator and exportAccelerators', t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Shift, Mod.Control], code: KeyCode.KeyL },
+    { mods: [Mod.Alt, Mod.Meta], code: KeyCode.F9 },
+  ];
+  descs.forEach(desc => manager.register(desc.mods, desc.code));
+
+  const labels = manager.labelsById();
+  descs.forEach(desc => {
+    const id = getHotkeyId(desc.code, desc.mods);
+    t.deepEqual(
+      labels.filter(label => label.id === id),
+      [{ id, label: descToAccelerator(desc) }],
+    );
+  });
+  t.deepEqual(
+    labels.map(label => label.label),
+    manager.exportAccelerators(),
+  );
+
+  descs.forEach(desc => manager.unregister(desc.mods, desc.code));
+});
+
+test.serial('hotkeyForId matches the hotkey register used', t => {
+  const manager = hotk();
+  // `global_hotkey` modifier bits.
+  const CONTROL = 0x8;
+  const SHIFT = 0x200;
+  const SUPER = 0x2000;
+
+  const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyY);
+  t.deepEqual(manager.hotkeyForId(id), {
+    rawId: id,
+    code: KeyCode.KeyY,
+    mods: [Mod.Control, Mod.Shift],
+    modifiersBits: CONTROL | SHIFT,
+  });
+
+  const meta = manager.register([Mod.Meta], KeyCode.F8);
+  t.is(manager.hotkeyForId(meta.id).modifiersBits, SUPER);
+  t.is(manager.hotkeyForId(meta.id).rawId, getHotkeyId(KeyCode.F8, [Mod.Super]));
+
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyY);
+  manager.unregister([Mod.Meta], KeyCode.F8);
+  t.is(manager.hotkeyForId(id), null);
+});
+
+test.serial('exportKeymapTs generates one constant per hotkey', t => {
+  const manager = hotk();
+  const descs = [
+    { mods: [Mod.Shift, Mod.Control], code: KeyCode.KeyX },
+    { mods: [Mod.Alt], code: KeyCode.Digit7 },
+  ];
+  descs.forEach(desc => manager.register(desc.mods, desc.code));
+
+  const ts = manager.exportKeymapTs();
+  t.true(ts.startsWith("import { KeyCode, Mod } from '@hotk/core';\n"));
+
+  // Evaluate the module as JS: drop the import and the TS-only `as const`.
+  const body = ts
+    .split('\n')
+    .filter(line => line.startsWith('export const '))
+    .map(line => line.replace('export const ', 'exported.').replace(' as const;', ';'))
+    .join('\n');
+  const exported = new Function('Mod', 'KeyCode', `const exported = {};\n${body}\nreturn exported;`)(Mod, KeyCode);
+
+  t.deepEqual(exported.Control_Shift_KeyX, { mods: [Mod.Control, Mod.Shift], code: KeyCode.KeyX });
+  t.deepEqual(exported.Alt_Digit7, { mods: [Mod.Alt], code: KeyCode.Digit7 });
+
+  descs.forEach(desc => manager.unregister(desc.mods, desc.code));
+});
+
+test('isModifierKey recognizes modifier keys', t => {
+  [
+    KeyCode.ControlLeft,
+    KeyCode.ControlRight,
+    KeyCode.ShiftLeft,
+    KeyCode.ShiftRight,
+    KeyCode.AltLeft,
+    KeyCode.AltRight,
+    KeyCode.MetaLeft,
+    KeyCode.MetaRight,
+    KeyCode.Fn,
+    KeyCode.CapsLock,
+    KeyCode.NumLock,
+    KeyCode.ScrollLock,
+  ].forEach(code => t.true(isModifierKey(code), code));
+
+  [KeyCode.KeyA, KeyCode.KeyZ, KeyCode.F1, KeyCode.F12, KeyCode.Space].forEach(code =>
+    t.false(isModifierKey(code), code),
+  );
+});
+
+test.serial('pendingActions is back to zero once a registration completes', t => {
+  const manager = hotk();
+
+  t.true(manager.register([Mod.Control, Mod.Alt], KeyCode.KeyQ).isOk());
+  t.is(manager.pendingActions(), 0);
+  manager.unregister([Mod.Control, Mod.Alt], KeyCode.KeyQ);
+  t.is(manager.pendingActions(), 0);
+});
+
+test.serial('isHealthy while the backend is running', t => {
+  const manager = hotk();
+
+  t.true(manager.isHealthy());
+  t.true(manager.register([Mod.Control, Mod.Alt], KeyCode.KeyQ).isOk());
+  t.true(manager.isHealthy());
+  manager.unregister([Mod.Control, Mod.Alt], KeyCode.KeyQ);
+});
+
+test.serial('recreate keeps the registered hotkeys', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt];
+
+  t.true(manager.register(mods, KeyCode.KeyQ).isOk());
+  t.deepEqual(manager.recreate(), []);
+  t.true(manager.isHealthy());
+  t.true(manager.isRegistered(mods, KeyCode.KeyQ));
+  t.true(manager.unregister(mods, KeyCode.KeyQ).isOk());
+});
+
+test.serial('registerHandle unregisters exactly its registration', t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Alt];
+
+  const first = manager.registerHandle(mods, KeyCode.KeyH);
+  const second = manager.registerHandle(mods, KeyCode.KeyH);
+  t.true(first.registered);
+  t.is(first.id, getHotkeyId(KeyCode.KeyH, mods));
+  t.is(first.error, null);
+  t.is(manager.registrants(first.id), 2);
+
+  t.true(first.unregister().isOk());
+  t.false(first.registered);
+  t.true(manager.isRegistered(mods, KeyCode.KeyH));
+  t.is(first.unregister().errorKind, ErrorKind.NotRegistered);
+  t.is(manager.registrants(first.id), 1);
+
+  t.true(second.unregister().isOk());
+  t.false(manager.isRegistered(mods, KeyCode.KeyH));
+});
+
+test.serial('registerHandle reports a failed registration', t => {
+  const handle = hotk().registerHandle([Mod.Control], KeyCode.Unidentified);
+
+  t.false(handle.registered);
+  t.is(typeof handle.error, 'string');
+  t.is(handle.unregister().errorKind, ErrorKind.NotRegistered);
+});
+
+test.serial('lockStats counts every lock taken', t => {
+  const manager = hotk();
+  const id = getHotkeyId(KeyCode.F24, [Mod.Control, Mod.Alt]);
+
+  const before = manager.lockStats();
+  t.false(manager.simulate(id, EventType.Pressed));
+  const after = manager.lockStats();
+  t.true(after.manager.acquisitions > before.manager.acquisitions);
+  t.true(after.dispatch.acquisitions > before.dispatch.acquisitions);
+  t.is(after.manager.contended, before.manager.contended);
+});
+
+(hooks ? test.serial : test.skip)('lockStats counts the calls that waited for the manager lock', t => {
+  const manager = hotk();
+
+  const before = manager.lockStats().manager;
+  manager.simulateLockContention(50);
+  manager.isRegistered([Mod.Control, Mod.Alt], KeyCode.F24);
+  const after = manager.lockStats().manager;
+  t.is(after.contended, before.contended + 1);
+  t.true(after.maxWaitMs >= 25, `${after.maxWaitMs}`);
+});
+
+test('CommandOrControl stays portable through accelerators', t => {
+  const portable = 'CommandOrControl+Shift+KeyK';
+  const desc = parseHotkey(portable);
+
+  t.deepEqual(desc, { mods: [Mod.CommandOrControl, Mod.Shift], code: KeyCode.KeyK });
+  t.is(descToAccelerator(desc), portable);
+  t.is(descToAccelerator({ mods: [Mod.Shift, Mod.CommandOrControl], code: KeyCode.KeyK }), portable);
+
+  const concrete = process.platform === 'darwin' ? Mod.Meta : Mod.Control;
+  t.is(getHotkeyId(desc.code, desc.mods), getHotkeyId(KeyCode.KeyK, [concrete, Mod.Shift]));
+  t.is(modsToHuman([Mod.CommandOrControl, concrete]), modsToHuman([concrete]));
+});
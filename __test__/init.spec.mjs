@@ -0,0 +1,41 @@
+import test from 'ava'
+
+import { EventType, hotk, InitResult, KeyCode, Mod } from '../index.js'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+// The first `init` of the process is observed here, so this lives in its own file.
+test.serial('init reports whether the callback was installed', t => {
+  const manager = hotk();
+  const onEvent = () => {};
+
+  t.is(manager.init('not a function'), InitResult.Failed);
+  t.is(manager.init(onEvent), InitResult.Installed);
+  t.is(manager.init(onEvent), InitResult.Replaced);
+  t.is(hotk().init(() => {}), InitResult.Replaced);
+
+  manager.unref();
+});
+
+test.serial('init replaces the callback with the latest one', async t => {
+  const manager = hotk();
+  const mods = [Mod.Control, Mod.Shift];
+  const { id } = manager.register(mods, KeyCode.KeyR);
+  const first = [];
+  const second = [];
+
+  manager.init(event => first.push(event));
+  manager.simulate(id, EventType.Pressed);
+  // Not delivered yet: goes to the callback installed next.
+  t.is(manager.init(event => second.push(event)), InitResult.Replaced);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+
+  t.deepEqual(first, []);
+  t.deepEqual(
+    second.map(event => event.eventType),
+    [EventType.Pressed, EventType.Released],
+  );
+
+  manager.unregister(mods, KeyCode.KeyR);
+});
@@ -0,0 +1,49 @@
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+const mods = [Mod.Control, Mod.Shift];
+
+test.serial('late events are dropped by default', async t => {
+  const { id } = manager.register(mods, KeyCode.KeyL);
+  manager.unregister(mods, KeyCode.KeyL);
+
+  t.false(manager.simulate(id, EventType.Released));
+  await sleep(20);
+  t.is(events.length, 0);
+});
+
+test.serial('late events within the grace window are delivered', async t => {
+  manager.setLateEventGraceMs(500);
+  const { id } = manager.register(mods, KeyCode.KeyL);
+  manager.unregister(mods, KeyCode.KeyL);
+
+  t.true(manager.simulate(id, EventType.Released));
+  await sleep(20);
+  t.deepEqual(
+    events.map(event => [event.id, event.code, event.eventType]),
+    [[id, KeyCode.KeyL, EventType.Released]],
+  );
+
+  manager.setLateEventGraceMs(0);
+  events.length = 0;
+});
+
+test.serial('late events outside the grace window are dropped', async t => {
+  manager.setLateEventGraceMs(30);
+  const { id } = manager.register(mods, KeyCode.KeyL);
+  manager.unregister(mods, KeyCode.KeyL);
+
+  await sleep(80);
+  t.false(manager.simulate(id, EventType.Released));
+  await sleep(20);
+  t.is(events.length, 0);
+
+  manager.setLateEventGraceMs(0);
+});
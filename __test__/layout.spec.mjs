@@ -0,0 +1,55 @@
+import { execFileSync } from 'node:child_process'
+
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { hooks } from './hooks.mjs'
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+const onWindows = process.platform === 'win32';
+
+const { events, manager } = listen();
+
+// Presses and releases Ctrl + Alt + A.
+const pressCtrlAltA = () => {
+  const script = `
+    $sig = '[DllImport("user32.dll")] public static extern void keybd_event(byte vk, byte scan, uint flags, UIntPtr extra);'
+    $user32 = Add-Type -MemberDefinition $sig -Name User32 -Namespace Hotk -PassThru
+    $user32::keybd_event(0x11, 0, 0, [UIntPtr]::Zero)
+    $user32::keybd_event(0x12, 0, 0, [UIntPtr]::Zero)
+    $user32::keybd_event(0x41, 0, 0, [UIntPtr]::Zero)
+    $user32::keybd_event(0x41, 0, 2, [UIntPtr]::Zero)
+    $user32::keybd_event(0x12, 0, 2, [UIntPtr]::Zero)
+    $user32::keybd_event(0x11, 0, 2, [UIntPtr]::Zero)
+  `;
+  execFileSync('powershell', ['-NoProfile', '-NonInteractive', '-Command', script]);
+};
+
+(onWindows && hooks ? test.serial : test.skip)('a registered hotkey keeps firing after a layout change', async t => {
+  const mods = [Mod.Control, Mod.Alt];
+  const { id } = manager.register(mods, KeyCode.KeyA);
+
+  const changed = await new Promise(resolve => {
+    const timeout = setTimeout(() => resolve(false), 2000);
+    manager.setOnLayoutChange(() => {
+      clearTimeout(timeout);
+      resolve(true);
+    });
+    manager.simulateLayoutChange();
+  });
+  manager.setOnLayoutChange(null);
+  t.true(changed);
+  t.true(manager.isRegistered(mods, KeyCode.KeyA));
+
+  pressCtrlAltA();
+  await sleep(100);
+  t.deepEqual(
+    events.filter(event => event.id === id).map(event => event.eventType),
+    [EventType.Pressed, EventType.Released],
+  );
+
+  events.length = 0;
+  manager.unregister(mods, KeyCode.KeyA);
+});
@@ -0,0 +1,43 @@
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+const mods = [Mod.Control, Mod.Shift];
+
+test.serial('lifecycle events are off by default', async t => {
+  manager.register(mods, KeyCode.KeyY);
+  manager.unregister(mods, KeyCode.KeyY);
+  await sleep(20);
+
+  t.deepEqual(events, []);
+});
+
+test.serial('lifecycle events appear in the stream once enabled', async t => {
+  manager.setEmitLifecycle(true);
+
+  const { id } = manager.register(mods, KeyCode.KeyY);
+  manager.register(mods, KeyCode.KeyY);
+  manager.simulate(id, EventType.Pressed);
+  manager.unregister(mods, KeyCode.KeyY);
+  manager.unregister(mods, KeyCode.KeyY);
+  t.false(manager.simulate(id, EventType.Registered));
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => [event.id, event.eventType, event.synthetic]),
+    [
+      [id, EventType.Registered, true],
+      [id, EventType.Pressed, true],
+      [id, EventType.Unregistered, true],
+    ],
+  );
+
+  manager.setEmitLifecycle(false);
+  events.length = 0;
+});
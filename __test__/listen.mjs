@@ -0,0 +1,11 @@
+import { hotk } from '../index.js'
+
+// The manager and its `init` callback are shared by the whole process and ava runs each spec file in its own
+// worker, so a spec file installs one callback for all of its tests, which take turns through `test.serial`.
+export function listen() {
+  const events = [];
+  const manager = hotk();
+  manager.init(event => events.push(event));
+  manager.unref();
+  return { events, manager };
+}
@@ -0,0 +1,94 @@
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+const mods = [Mod.Alt, Mod.Shift];
+
+test.serial('holding past the threshold fires a LongPress before the release', async t => {
+  const { id } = manager.registerLongPress(mods, KeyCode.Space, 50);
+
+  manager.simulate(id, EventType.Pressed);
+  await sleep(80);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.LongPress, EventType.Released],
+  );
+  t.true(events[1].synthetic);
+  t.true(events[1].deltaMs >= 50);
+
+  manager.unregister(mods, KeyCode.Space);
+  events.length = 0;
+});
+
+test.serial('short presses only fire Pressed and Released', async t => {
+  const { id } = manager.registerLongPress(mods, KeyCode.Space, 200);
+
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Released],
+  );
+
+  manager.unregister(mods, KeyCode.Space);
+  events.length = 0;
+});
+
+test.serial('a withheld release still ends the hold', async t => {
+  const { id } = manager.registerLongPress(mods, KeyCode.Space, 50);
+  manager.deferReleased(id, true);
+
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  manager.deferReleased(id, false);
+  await sleep(80);
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Released, EventType.Pressed, EventType.Released],
+  );
+
+  manager.unregister(mods, KeyCode.Space);
+  events.length = 0;
+});
+
+test.serial('a hold ended by the watchdog does not leak into the next press', async t => {
+  const { id } = manager.registerLongPress(mods, KeyCode.Space, 50);
+  manager.setReleaseWatchdog(100);
+
+  manager.simulate(id, EventType.Pressed);
+  await sleep(200);
+  manager.simulate(id, EventType.Released);
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => [event.eventType, event.synthetic]),
+    [
+      [EventType.Pressed, true],
+      [EventType.LongPress, true],
+      [EventType.Released, true],
+      [EventType.Pressed, true],
+      [EventType.Released, true],
+    ],
+  );
+
+  manager.setReleaseWatchdog(null);
+  manager.unregister(mods, KeyCode.Space);
+  events.length = 0;
+});
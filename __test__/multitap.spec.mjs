@@ -0,0 +1,59 @@
+import test from 'ava'
+
+import { ErrorKind, EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+const mods = [Mod.Control, Mod.Shift];
+
+test.serial('presses within the window fire a MultiTap', async t => {
+  const { id } = manager.registerMultiTap(mods, KeyCode.KeyT, 2, 150);
+
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Released, EventType.Pressed, EventType.MultiTap, EventType.Released],
+  );
+  const tap = events[3];
+  t.is(tap.id, id);
+  t.is(tap.taps, 2);
+  t.true(tap.synthetic);
+
+  manager.unregister(mods, KeyCode.KeyT);
+  events.length = 0;
+});
+
+test.serial('a press after the window starts counting again', async t => {
+  const { id } = manager.registerMultiTap(mods, KeyCode.KeyT, 2, 50);
+
+  manager.simulate(id, EventType.Pressed);
+  await sleep(100);
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+  t.false(events.some(event => event.eventType === EventType.MultiTap));
+
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+  t.is(events.filter(event => event.eventType === EventType.MultiTap).length, 1);
+
+  manager.unregister(mods, KeyCode.KeyT);
+  events.length = 0;
+});
+
+test.serial('a multi-tap needs at least two taps', t => {
+  const response = manager.registerMultiTap(mods, KeyCode.KeyT, 1, 300);
+
+  t.false(response.isOk());
+  t.is(response.errorKind, ErrorKind.InvalidArgument);
+  t.false(manager.isRegistered(mods, KeyCode.KeyT));
+  t.false(manager.simulate(response.id, EventType.MultiTap));
+});
@@ -0,0 +1,57 @@
+import test from 'ava'
+
+import { EventType, getHotkeyId, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+const mods = [Mod.Control, Mod.Shift];
+
+test.serial('on registers the combination and takes its events from init', async t => {
+  const handled = [];
+  t.true(manager.on(mods, KeyCode.KeyH, event => handled.push(event)));
+  t.true(manager.isRegistered(mods, KeyCode.KeyH));
+  const id = getHotkeyId(KeyCode.KeyH, mods);
+
+  const { id: other } = manager.register(mods, KeyCode.KeyJ);
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(other, EventType.Pressed);
+  await sleep(20);
+
+  t.deepEqual(handled.map(event => event.id), [id]);
+  t.deepEqual(events.map(event => event.id), [other]);
+
+  manager.unregister(mods, KeyCode.KeyJ);
+  manager.off(mods, KeyCode.KeyH);
+  manager.unregister(mods, KeyCode.KeyH);
+  events.length = 0;
+});
+
+test.serial('on does not register an already registered combination again', t => {
+  const { id } = manager.register(mods, KeyCode.KeyH);
+  t.true(manager.on(mods, KeyCode.KeyH, () => {}));
+  t.is(manager.registrants(id), 1);
+
+  manager.off(mods, KeyCode.KeyH);
+  manager.unregister(mods, KeyCode.KeyH);
+});
+
+test.serial('off sends the events back to init', async t => {
+  const handled = [];
+  manager.on(mods, KeyCode.KeyH, event => handled.push(event));
+  const id = getHotkeyId(KeyCode.KeyH, mods);
+
+  t.true(manager.off(mods, KeyCode.KeyH));
+  t.false(manager.off(mods, KeyCode.KeyH));
+  manager.simulate(id, EventType.Pressed);
+  await sleep(20);
+
+  t.is(handled.length, 0);
+  t.deepEqual(events.map(event => event.id), [id]);
+
+  manager.unregister(mods, KeyCode.KeyH);
+  events.length = 0;
+});
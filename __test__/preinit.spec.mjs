@@ -0,0 +1,32 @@
+import test from 'ava'
+
+import { EventType, hotk, KeyCode, Mod } from '../index.js'
+
+// Buffering only applies until the first `init` of the process, so this lives in its own ava worker.
+test('events fired before init are delivered once init is called', async t => {
+  const manager = hotk();
+  manager.setBufferPreinit(true);
+
+  const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyB);
+  t.true(manager.simulate(id, EventType.Pressed));
+  t.true(manager.simulate(id, EventType.Released));
+
+  const events = [];
+  await new Promise(resolve => {
+    manager.init(event => {
+      events.push(event);
+      if (events.length === 2) resolve();
+    });
+  });
+  manager.unref();
+
+  t.deepEqual(
+    events.map(event => [event.id, event.eventType]),
+    [
+      [id, EventType.Pressed],
+      [id, EventType.Released],
+    ],
+  );
+
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyB);
+});
@@ -0,0 +1,57 @@
+import { execFileSync } from 'node:child_process'
+
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+const onWindows = process.platform === 'win32';
+
+const { events, manager } = listen();
+
+// Holds Ctrl + Alt + F22 through several key repeats, as holding the keys down would.
+const holdCtrlAltF22 = () => {
+  const script = `
+    $sig = '[DllImport("user32.dll")] public static extern void keybd_event(byte vk, byte scan, uint flags, UIntPtr extra);'
+    $user32 = Add-Type -MemberDefinition $sig -Name User32 -Namespace Hotk -PassThru
+    $user32::keybd_event(0x11, 0, 0, [UIntPtr]::Zero)
+    $user32::keybd_event(0x12, 0, 0, [UIntPtr]::Zero)
+    1..5 | ForEach-Object {
+      $user32::keybd_event(0x85, 0, 0, [UIntPtr]::Zero)
+      Start-Sleep -Milliseconds 30
+    }
+    $user32::keybd_event(0x85, 0, 2, [UIntPtr]::Zero)
+    $user32::keybd_event(0x12, 0, 2, [UIntPtr]::Zero)
+    $user32::keybd_event(0x11, 0, 2, [UIntPtr]::Zero)
+  `;
+  execFileSync('powershell', ['-NoProfile', '-NonInteractive', '-Command', script]);
+};
+
+(onWindows ? test.serial : test.skip)('a held hotkey produces a single Pressed by default', async t => {
+  const mods = [Mod.Control, Mod.Alt];
+  const { id } = manager.register(mods, KeyCode.F22);
+
+  holdCtrlAltF22();
+  await sleep(200);
+  t.deepEqual(
+    events.filter(event => event.id === id).map(event => event.eventType),
+    [EventType.Pressed, EventType.Released],
+  );
+
+  events.length = 0;
+  t.true(manager.setIgnoreOsRepeat(false));
+  holdCtrlAltF22();
+  await sleep(200);
+  t.true(events.filter(event => event.eventType === EventType.Pressed).length > 1);
+
+  manager.setIgnoreOsRepeat(true);
+  events.length = 0;
+  manager.unregister(mods, KeyCode.F22);
+});
+
+(onWindows ? test.skip : test)('setIgnoreOsRepeat is unsupported off Windows', t => {
+  t.false(manager.setIgnoreOsRepeat(false));
+  t.false(manager.setIgnoreOsRepeat(true));
+});
@@ -0,0 +1,82 @@
+import test from 'ava'
+
+import { ErrorKind, EventType, getHotkeyId, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+const mods = [Mod.Control, Mod.Shift];
+const steps = [
+  { mods, code: KeyCode.KeyK },
+  { mods, code: KeyCode.KeyS },
+];
+const first = getHotkeyId(KeyCode.KeyK, mods);
+const second = getHotkeyId(KeyCode.KeyS, mods);
+
+const sequenceEvents = () => events.filter(event => event.eventType === EventType.Sequence);
+
+test.serial('steps pressed in order fire a Sequence', async t => {
+  const { id } = manager.registerSequence(steps, 200);
+  t.true(manager.isRegistered(mods, KeyCode.KeyK));
+  t.true(manager.isRegistered(mods, KeyCode.KeyS));
+
+  manager.simulate(first, EventType.Pressed);
+  manager.simulate(first, EventType.Released);
+  manager.simulate(second, EventType.Pressed);
+  manager.simulate(second, EventType.Released);
+  await sleep(20);
+
+  t.deepEqual(
+    events.map(event => [event.id, event.eventType]),
+    [
+      [first, EventType.Pressed],
+      [first, EventType.Released],
+      [second, EventType.Pressed],
+      [id, EventType.Sequence],
+      [second, EventType.Released],
+    ],
+  );
+  t.is(events[3].code, KeyCode.KeyS);
+  t.true(events[3].synthetic);
+
+  t.true(manager.unregisterSequence(id));
+  t.false(manager.isRegistered(mods, KeyCode.KeyK));
+  t.false(manager.unregisterSequence(id));
+  events.length = 0;
+});
+
+test.serial('an out of order or late press starts the sequence over', async t => {
+  const { id } = manager.registerSequence(steps, 50);
+  const other = manager.register(mods, KeyCode.KeyJ).id;
+
+  manager.simulate(second, EventType.Pressed);
+  manager.simulate(first, EventType.Pressed);
+  manager.simulate(other, EventType.Pressed);
+  manager.simulate(second, EventType.Pressed);
+  manager.simulate(first, EventType.Pressed);
+  await sleep(100);
+  manager.simulate(second, EventType.Pressed);
+  await sleep(20);
+  t.deepEqual(sequenceEvents(), []);
+
+  manager.simulate(first, EventType.Pressed);
+  manager.simulate(first, EventType.Pressed);
+  manager.simulate(second, EventType.Pressed);
+  await sleep(20);
+  t.deepEqual(sequenceEvents().map(event => event.id), [id]);
+
+  manager.unregister(mods, KeyCode.KeyJ);
+  manager.unregisterSequence(id);
+  events.length = 0;
+});
+
+test.serial('a sequence with a failing step registers nothing', t => {
+  const response = manager.registerSequence([steps[0], { mods, code: KeyCode.Unidentified }], 200);
+
+  t.false(response.isOk());
+  t.false(manager.isRegistered(mods, KeyCode.KeyK));
+  t.is(manager.registerSequence([steps[0]], 200).errorKind, ErrorKind.InvalidArgument);
+});
@@ -0,0 +1,14 @@
+import test from 'ava'
+
+import { hotk, hotkError } from '../index.js'
+
+import { hooks } from './hooks.mjs'
+
+// The manager is created once per process, so this lives in its own file.
+(process.platform === 'win32' && hooks ? test : test.skip)('a backend thread reporting an invalid id fails creation', t => {
+  process.env.HOTK_INVALID_THREAD_ID = '1';
+
+  t.is(hotk(), null);
+  t.is(hotkError(), 'Invalid hotkey backend thread id 0');
+  delete process.env.HOTK_INVALID_THREAD_ID;
+});
@@ -0,0 +1,53 @@
+import { mkdtempSync, readFileSync } from 'node:fs'
+import { tmpdir } from 'node:os'
+import { join } from 'node:path'
+
+import test from 'ava'
+
+import { EventType, hotk, KeyCode, Mod } from '../index.js'
+
+test.serial('event trace writes a line per dispatched event', t => {
+  const manager = hotk();
+  const path = join(mkdtempSync(join(tmpdir(), 'hotk-')), 'events.log');
+  const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyT);
+
+  t.is(manager.startEventTrace(path).code, 'Ok');
+  manager.simulate(id, EventType.Pressed);
+  manager.simulate(id, EventType.Released);
+  t.true(manager.stopEventTrace());
+  t.false(manager.stopEventTrace());
+
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyT);
+
+  const lines = readFileSync(path, 'utf8').trim().split('\n');
+  t.is(lines.length, 2);
+  t.regex(lines[0], new RegExp(`^\\d+ ${id} Pressed Control\\+Shift\\+KeyT$`));
+  t.regex(lines[1], new RegExp(`^\\d+ ${id} Released Control\\+Shift\\+KeyT$`));
+});
+
+test.serial('event trace reports files it cannot open', t => {
+  const result = hotk().startEventTrace(join(tmpdir(), 'hotk-missing-dir', 'nested', 'events.log'));
+
+  t.is(result.code, 'Error');
+  t.regex(result.error, /Failed to open/);
+});
+
+test.serial('event trace lines carry the instance name', t => {
+  const manager = hotk();
+  const path = join(mkdtempSync(join(tmpdir(), 'hotk-')), 'events.log');
+  const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyT);
+
+  manager.setInstanceName('worker-1');
+  t.is(manager.startEventTrace(path).code, 'Ok');
+  manager.simulate(id, EventType.Pressed);
+  manager.setInstanceName('');
+  manager.simulate(id, EventType.Released);
+  manager.stopEventTrace();
+
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyT);
+
+  const lines = readFileSync(path, 'utf8').trim().split('\n');
+  t.is(lines.length, 2);
+  t.regex(lines[0], new RegExp(`^\\[worker-1\\] \\d+ ${id} Pressed Control\\+Shift\\+KeyT$`));
+  t.regex(lines[1], new RegExp(`^\\d+ ${id} Released Control\\+Shift\\+KeyT$`));
+});
@@ -0,0 +1,79 @@
+import { readdirSync } from 'node:fs'
+
+import test from 'ava'
+
+import { EventType, KeyCode, Mod } from '../index.js'
+
+import { listen } from './listen.mjs'
+
+const sleep = ms => new Promise(resolve => setTimeout(resolve, ms));
+
+const { events, manager } = listen();
+
+test.serial('the watchdog releases a hotkey stuck pressed', async t => {
+  const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyW);
+  manager.setReleaseWatchdog(50);
+
+  manager.simulate(id, EventType.Pressed);
+  await sleep(200);
+
+  t.deepEqual(
+    events.map(event => [event.eventType, event.synthetic]),
+    [
+      [EventType.Pressed, true],
+      [EventType.Released, true],
+    ],
+  );
+
+  // The late real Released is not delivered a second time.
+  manager.simulate(id, EventType.Released);
+  await sleep(20);
+  t.is(events.length, 2);
+
+  manager.setReleaseWatchdog(null);
+  events.length = 0;
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyW);
+});
+
+test.serial('the watchdog is off by default and cancelled by resetKeyState', async t => {
+  const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyR);
+
+  manager.simulate(id, EventType.Pressed);
+  await sleep(100);
+
+  manager.setReleaseWatchdog(50);
+  manager.simulate(id, EventType.Pressed);
+  manager.resetKeyState();
+  await sleep(200);
+
+  t.deepEqual(
+    events.map(event => event.eventType),
+    [EventType.Pressed, EventType.Pressed],
+  );
+
+  manager.setReleaseWatchdog(null);
+  events.length = 0;
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyR);
+});
+
+(process.platform === 'linux' ? test.serial : test.skip)('the watchdog uses a single thread for repeated presses', async t => {
+  const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyQ);
+  const threads = () => readdirSync('/proc/self/task').length;
+  manager.setReleaseWatchdog(50);
+
+  const before = threads();
+  for (let i = 0; i < 20; i++) {
+    manager.simulate(id, EventType.Pressed);
+  }
+  t.true(threads() <= before + 1);
+  await sleep(200);
+
+  t.deepEqual(
+    events.filter(event => event.eventType === EventType.Released).map(event => event.synthetic),
+    [true],
+  );
+
+  manager.setReleaseWatchdog(null);
+  events.length = 0;
+  manager.unregister([Mod.Control, Mod.Shift], KeyCode.KeyQ);
+});
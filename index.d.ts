@@ -5,7 +5,11 @@
 
 /**
  * Represents keyboard key codes used to define hotkeys.
- * Each variant corresponds to a specific physical key.
+ * Each variant corresponds to a specific physical key, named after its position on a US layout.
+ *
+ * Hotkeys are never resolved by character, nor re-resolved when the keyboard layout changes: a registered
+ * hotkey keeps firing after a layout switch. On Windows the backend registers the virtual key of the code,
+ * so on layouts that move letters (e.g. AZERTY) the key triggering `KeyA` is the one labeled A.
 */
 export const enum KeyCode {
   Backquote = 'Backquote',
@@ -243,14 +247,141 @@ export declare function modKeys(): Array<string>
 /**
  * Converts a KeyCode enum variant to a human-readable string representation.
  *
- * This is useful for displaying key codes in a user-friendly format.
+ * This is useful for displaying key codes in a user-friendly format: characters for the typing area
+ * (`"a"`, `"/"`), arrows for arrow keys (`"↑"`) and short names for the rest (`"Page Up"`, `"Num 0"`, `"Esc"`).
  *
  * @param {KeyCode} key_code - The key code to convert.
- * @returns {string | null} A human-readable string representing the key code, or null if no mapping exists.
+ * @returns {string | null} A human-readable string representing the key code, or null for `Unidentified`.
+ *
+ * @example
+ * ```js
+ * import { keyCodeToHuman, KeyCode } from '@hotk/core';
+ *
+ * keyCodeToHuman(KeyCode.ArrowUp); // "↑"
+ * keyCodeToHuman(KeyCode.Numpad0); // "Num 0"
+ * ```
 */
 export declare function keyCodeToHuman(keyCode: KeyCode): string | null
+/**
+ * Checks whether a key code is a modifier key (e.g. `ControlLeft`, `ShiftRight`, `Fn`, `CapsLock`).
+ *
+ * Modifier keys can't be the main key of a hotkey; use it to prevent selecting one in a UI.
+ *
+ * @param {KeyCode} code - The key code to check.
+ * @returns {boolean} `true` for modifier keys.
+ *
+ * @example
+ * ```js
+ * import { isModifierKey, KeyCode } from '@hotk/core';
+ *
+ * isModifierKey(KeyCode.ShiftLeft); // true
+ * isModifierKey(KeyCode.KeyA); // false
+ * ```
+*/
+export declare function isModifierKey(code: KeyCode): boolean
+/**
+ * Converts a modifier to its display label using the platform convention.
+ *
+ * macOS uses the symbols of its menus (`⌘`, `⌥`, `⇧`, `⌃`); Windows and Linux use names (`Ctrl`, `Alt`),
+ * with the OS key read as `Win` on Windows and `Super` on Linux. `CommandOrControl` is labelled as the
+ * modifier it resolves to.
+ *
+ * @param {Mod} modifier - The modifier to convert.
+ * @returns {string} The label, e.g. `"⌘"` for `Meta` on macOS and `"Win"` on Windows.
+ *
+ * @example
+ * ```js
+ * import { modToHuman, Mod } from '@hotk/core';
+ *
+ * const chips = [Mod.Control, Mod.Shift].map(modToHuman); // ['Ctrl', 'Shift'] (['⌃', '⇧'] on macOS)
+ * ```
+*/
+export declare function modToHuman(modifier: Mod): string
+/**
+ * Returns the conventional name of the OS key on this platform: `Meta` (Command) on macOS, `Super`
+ * (the Windows key) elsewhere.
+ *
+ * Both register the same hotkey, as `Meta` is folded into `Super`; this is the one to show and store so
+ * bindings read naturally. `parseHotkey` and `parseAccelerator` resolve `Win`, `Cmd` and `Super` to it.
+ *
+ * @example
+ * ```js
+ * import { hotk, platformPrimaryMeta, KeyCode } from '@hotk/core';
+ *
+ * hotk().register([platformPrimaryMeta()], KeyCode.Space); // Command+Space on macOS, Win+Space elsewhere
+ * ```
+*/
+export declare function platformPrimaryMeta(): Mod
+/**
+ * Converts a list of modifiers to a display string using the platform convention.
+ *
+ * Modifiers are put in canonical order and duplicates are dropped. On macOS the symbols are joined
+ * without a separator (`⌃⇧⌘`); elsewhere the names are joined with `+` (`Ctrl+Shift+Win`).
+ *
+ * @param {Mod[]} mods - The modifiers to convert.
+ * @returns {string} The display string, empty if `mods` is empty.
+ *
+ * @example
+ * ```js
+ * import { modsToHuman, Mod } from '@hotk/core';
+ *
+ * modsToHuman([Mod.Shift, Mod.Control]); // "Ctrl+Shift" ("⌃⇧" on macOS)
+ * ```
+*/
+export declare function modsToHuman(mods: Array<Mod>): string
+/**
+ * Broad group a key code belongs to.
+ *
+ * - `Letter`, `Digit`, `Punctuation`: Keys of the main typing area.
+ * - `Modifier`: Keys that act as modifiers when held (e.g. `ShiftLeft`, `CapsLock`).
+ * - `Editing`: Whitespace and editing keys (e.g. `Enter`, `Delete`, `Copy`).
+ * - `Navigation`: Arrows, `Home`, `End` and paging keys.
+ * - `Function`: `F1` to `F35`.
+ * - `Numpad`: Keys of the numeric keypad.
+ * - `Media`: Playback and volume keys.
+ * - `Application`: Browser, mail and application launch keys.
+ * - `System`: Power, display and other system keys.
+ * - `Ime`: Input method and language keys (e.g. `Convert`, `Lang1`, `Hiragana`), rarely registerable.
+ * - `Unknown`: Keys that could not be identified.
+*/
+export const enum KeyCategory {
+  Letter = 'Letter',
+  Digit = 'Digit',
+  Punctuation = 'Punctuation',
+  Modifier = 'Modifier',
+  Editing = 'Editing',
+  Navigation = 'Navigation',
+  Function = 'Function',
+  Numpad = 'Numpad',
+  Media = 'Media',
+  Application = 'Application',
+  System = 'System',
+  Ime = 'Ime',
+  Unknown = 'Unknown'
+}
+/**
+ * Returns the category a key code belongs to.
+ *
+ * @param {KeyCode} code - The key code to classify.
+ * @returns {KeyCategory} The category of the key.
+ *
+ * @example
+ * ```js
+ * import { keyCategory, KeyCategory, KeyCode } from '@hotk/core';
+ *
+ * keyCategory(KeyCode.Lang1) === KeyCategory.Ime; // true
+ * ```
+*/
+export declare function keyCategory(code: KeyCode): KeyCategory
 /**
  * Modifier keys used in hotkey combinations.
+ *
+ * `CommandOrControl` is a portable pseudo-modifier: it stays as is in descriptors and accelerators,
+ * and only resolves to `Meta` on macOS and `Control` elsewhere when the hotkey is registered.
+ *
+ * `Meta` and `Super` are distinct upstream flags, but every backend folds `Meta` into `Super`: both are
+ * the OS key (Command on macOS, Windows elsewhere) and register the same hotkey. See `platformPrimaryMeta`
+ * for the one to use.
 */
 export const enum Mod {
   Control = 'Control',
@@ -266,7 +397,8 @@ export const enum Mod {
   SymbolLock = 'SymbolLock',
   Hyper = 'Hyper',
   Shift = 'Shift',
-  Super = 'Super'
+  Super = 'Super',
+  CommandOrControl = 'CommandOrControl'
 }
 /**
  * Descriptor for a hotkey combination.
@@ -277,12 +409,123 @@ export interface Desc {
   code: KeyCode
   mods: Array<Mod>
 }
+/**
+ * Converts a hotkey descriptor to its canonical accelerator string, e.g. `Control+Shift+KeyK`.
+ *
+ * Modifiers are always emitted in the same order and without duplicates, so equal combinations
+ * give equal strings. The result can be parsed back with `parseHotkey`.
+ *
+ * @param {Desc} desc - The combination to convert.
+ * @returns {string} The accelerator string.
+ *
+ * @example
+ * ```js
+ * import { descToAccelerator, KeyCode, Mod } from '@hotk/core';
+ *
+ * descToAccelerator({ mods: [Mod.Shift, Mod.Control], code: KeyCode.KeyK }); // "Control+Shift+KeyK"
+ * ```
+*/
+export declare function descToAccelerator(desc: Desc): string
+/**
+ * Formats a hotkey descriptor as a stable accelerator string, e.g. to key a config map by binding.
+ *
+ * Same output as `descToAccelerator`: modifiers in a fixed order (Control, Alt, Shift, Meta, …) whatever
+ * the input order, so equal bindings always give equal strings. The result parses back with
+ * `parseAccelerator`.
+ *
+ * @param {Desc} desc - The combination to format.
+ * @returns {string} The accelerator string.
+ *
+ * @example
+ * ```js
+ * import { formatAccelerator, KeyCode, Mod } from '@hotk/core';
+ *
+ * formatAccelerator({ mods: [Mod.Shift, Mod.Control], code: KeyCode.KeyK }); // "Control+Shift+KeyK"
+ * ```
+*/
+export declare function formatAccelerator(desc: Desc): string
+/**
+ * Converts a hotkey descriptor to an accelerator for the menus of Electron and Tauri, e.g. `CmdOrCtrl+Shift+A`.
+ *
+ * Unlike `descToAccelerator`, keys use the menu names (`A`, `1`, `Up`, `num0`, `VolumeUp`) and the modifier
+ * `CommandOrControl` resolves to on this platform is written `CmdOrCtrl`: `Control` outside macOS and `Meta`
+ * or `Super` on macOS. The OS key is `Super` elsewhere.
+ *
+ * @param {Desc} desc - The combination to convert.
+ * @returns {string | null} The menu accelerator, or `null` if a modifier (e.g. `Hyper`) or the key
+ *   (e.g. `F25`) has no name in the menu dialect.
+ *
+ * @example
+ * ```js
+ * import { toMenuAccelerator, KeyCode, Mod } from '@hotk/core';
+ *
+ * toMenuAccelerator({ mods: [Mod.CommandOrControl, Mod.Shift], code: KeyCode.KeyA }); // "CmdOrCtrl+Shift+A"
+ * ```
+*/
+export declare function toMenuAccelerator(desc: Desc): string | null
+/**
+ * Parses an accelerator string produced by `descToAccelerator` back into a hotkey descriptor.
+ *
+ * `Win`, `Cmd` and `Super` are read as the OS key of this platform, see `platformPrimaryMeta`.
+ *
+ * @param {string} accelerator - `Mod` names followed by a `KeyCode` name, joined with `+`.
+ * @returns {Desc | null} The descriptor, or `null` if a token is not a known modifier or key code.
+ *
+ * @example
+ * ```js
+ * import { parseHotkey } from '@hotk/core';
+ *
+ * parseHotkey('Control+Shift+KeyK'); // { mods: ['Control', 'Shift'], code: 'KeyK' }
+ * ```
+*/
+export declare function parseHotkey(accelerator: string): Desc | null
+/**
+ * Splits an accelerator into its modifiers and key code, for UIs rendering them separately
+ * (e.g. modifier chips and a key badge).
+ *
+ * Accepts the same strings as `parseHotkey`. Modifiers come back in display order and without duplicates.
+ *
+ * @param {string} accelerator - `Mod` names followed by a `KeyCode` name, joined with `+`.
+ * @returns {Desc | null} The parts, or `null` if the accelerator can't be parsed.
+ *
+ * @example
+ * ```js
+ * import { splitAccelerator } from '@hotk/core';
+ *
+ * const { mods, code } = splitAccelerator('Shift+Control+KeyK'); // ['Control', 'Shift'], 'KeyK'
+ * ```
+*/
+export declare function splitAccelerator(accelerator: string): Desc | null
+/**
+ * Parses a hand-written accelerator, e.g. from a settings file, into a hotkey descriptor.
+ *
+ * Tokens are joined with `+` and matched case-insensitively: every token but the last is a `Mod`
+ * (also accepting `Ctrl` for `Control`, and `Cmd`, `Win` or `Super` for `platformPrimaryMeta`), the last one
+ * a `KeyCode`.
+ *
+ * @param {string} input - The accelerator, e.g. `ctrl+shift+KeyK`.
+ * @returns {Desc | null} The descriptor, or `null` if a token is unknown or the key code is missing.
+ *
+ * @example
+ * ```js
+ * import { parseAccelerator } from '@hotk/core';
+ *
+ * parseAccelerator('Ctrl+Shift+keyk'); // { mods: ['Control', 'Shift'], code: 'KeyK' }
+ * ```
+*/
+export declare function parseAccelerator(input: string): Desc | null
 /**
  * Computes a unique identifier for the given key combination.
  *
  * This function takes a key code and a list of modifier keys,
  * and returns a stable numeric ID that uniquely identifies
  * that specific hotkey combination.
+ *
+ * The id is not a lossy hash: the modifiers fill the upper 16 bits and the key the lower 16,
+ * so two combinations only share an id when they register the same hotkey (e.g. `Meta` and `Super`).
+ * Ids never have the top bit set; that range is left for ids not tied to a single combination, like those
+ * of `HotkManager.registerSequence`, so they can't collide with computed ones. Registration still checks
+ * that an id isn't taken by a different combination and fails with `IdCollision` instead of overwriting it.
 */
 export declare function getHotkeyId(code: KeyCode, mods: Array<Mod>): number
 /**
@@ -291,10 +534,20 @@ export declare function getHotkeyId(code: KeyCode, mods: Array<Mod>): number
  * Possible values:
  * - `Pressed`: The hotkey was pressed.
  * - `Released`: The hotkey was released.
+ * - `Registered`: The hotkey was registered, see `HotkManager.setEmitLifecycle`.
+ * - `Unregistered`: The hotkey was unregistered, see `HotkManager.setEmitLifecycle`.
+ * - `MultiTap`: The hotkey was pressed the configured number of times in a row, see `HotkManager.registerMultiTap`.
+ * - `LongPress`: The hotkey was held for the configured time before being released, see `HotkManager.registerLongPress`.
+ * - `Sequence`: The steps of a sequence were pressed in order, see `HotkManager.registerSequence`.
 */
 export const enum EventType {
   Pressed = 0,
-  Released = 1
+  Released = 1,
+  Registered = 2,
+  Unregistered = 3,
+  MultiTap = 4,
+  LongPress = 5,
+  Sequence = 6
 }
 /**
  * Represents a global hotkey event.
@@ -304,12 +557,111 @@ export const enum EventType {
  * - `code` (KeyCode): The key code associated with the hotkey.
  * - `mods` (Mod[]): An array of modifier keys (e.g., Control, Shift).
  * - `event_type` (EventType): The type of the event (pressed or released).
+ * - `delta_ms` (number | undefined): Milliseconds since the previous event of the same hotkey, `0` for its first.
+ * - `synthetic` (boolean | undefined): `true` if the crate manufactured the event (e.g. a watchdog `Released`
+ *   or one fed through `simulate`), `false` if it was delivered by the OS.
+ * - `taps` (number | undefined): For `MultiTap` events, how many presses triggered it.
+ * - `timestamp` (number | undefined): When the event reached the crate, in milliseconds since the Unix epoch
+ *   with sub-millisecond precision. Unlike `Date.now()` in the callback, it doesn't include the delivery delay.
 */
 export interface Event {
   id: number
   code: KeyCode
   mods: Array<Mod>
   eventType: EventType
+  deltaMs?: number
+  synthetic?: boolean
+  taps?: number
+  timestamp?: number
+}
+/**
+ * Extracts the combination that fired an event, e.g. to persist it.
+ *
+ * @param {Event} event - An event received by the `init` callback.
+ * @returns {Desc} The key code and modifiers of the event.
+ *
+ * @example
+ * ```js
+ * import { hotk, eventToDesc } from '@hotk/core';
+ *
+ * hotk().init(event => save(eventToDesc(event)));
+ * ```
+*/
+export declare function eventToDesc(event: Event): Desc
+/**
+ * Serializes an event to a single-line JSON object, for logging or sending it to another process.
+ *
+ * The shape is stable: every key is always present, in this order, with `null` for absent values.
+ * - `id` (number): The hotkey id.
+ * - `code` (string): The key code, e.g. `"KeyK"`.
+ * - `mods` (string[]): The modifiers, e.g. `["Control", "Shift"]`.
+ * - `accelerator` (string): The canonical accelerator, e.g. `"Control+Shift+KeyK"`.
+ * - `eventType` (string): The name of the event type, e.g. `"Pressed"`, which unlike the numeric
+ *   `EventType` value doesn't depend on the version of the package.
+ * - `deltaMs` (number | null): Milliseconds since the previous event of the same hotkey.
+ * - `synthetic` (boolean | null): Whether the crate manufactured the event.
+ * - `taps` (number | null): For `MultiTap` events, how many presses triggered it.
+ * - `timestamp` (number | null): When the event reached the crate, in milliseconds since the Unix epoch.
+ *
+ * @example
+ * ```js
+ * import { hotk, eventToJson } from '@hotk/core';
+ *
+ * hotk().init(event => process.send(eventToJson(event)));
+ * // {"id":1234,"code":"KeyK","mods":["Control","Shift"],"accelerator":"Control+Shift+KeyK","eventType":"Pressed",...}
+ * ```
+*/
+export declare function eventToJson(event: Event): string
+/**
+ * Which events a full delivery queue drops, see `HotkManager.setQueueLimit`.
+ *
+ * - `DropNewest`: Incoming events are dropped, keeping the ones already waiting.
+ * - `DropOldest`: The oldest waiting event is dropped to make room for the incoming one.
+*/
+export const enum QueuePolicy {
+  DropNewest = 'DropNewest',
+  DropOldest = 'DropOldest'
+}
+/**
+ * Scheduling priority of the thread running the hotkey backend.
+ *
+ * Only the Windows backend runs its own thread, so this has no effect on other platforms.
+*/
+export const enum BackendPriority {
+  Idle = 'Idle',
+  Lowest = 'Lowest',
+  BelowNormal = 'BelowNormal',
+  Normal = 'Normal',
+  AboveNormal = 'AboveNormal',
+  Highest = 'Highest'
+}
+/**
+ * Identifies why a hotkey operation failed, so callers can branch on it rather than on the message.
+ *
+ * - `AlreadyRegistered`: The combination is already registered, by this or another application.
+ * - `NotRegistered`: The combination is not registered, so it can't be unregistered.
+ * - `OsFailure`: The OS denied the operation.
+ * - `Unsupported`: The key or combination can't be registered on this platform.
+ * - `InvalidArgument`: An argument is invalid, e.g. an unknown profile.
+ * - `IdCollision`: The id of the combination is already taken by a different combination.
+*/
+export const enum ErrorKind {
+  AlreadyRegistered = 'AlreadyRegistered',
+  NotRegistered = 'NotRegistered',
+  OsFailure = 'OsFailure',
+  Unsupported = 'Unsupported',
+  InvalidArgument = 'InvalidArgument',
+  IdCollision = 'IdCollision'
+}
+/**
+ * The hotkey operation a response describes.
+ *
+ * - `Register`: The response comes from registering a hotkey.
+ * - `Unregister`: The response comes from unregistering a hotkey.
+*/
+export const enum Operation {
+  Register = 'Register',
+  Unregister = 'Unregister'
 }
 /**
  * Represents the possible response codes for hotkey operations.
@@ -322,43 +674,439 @@ export const enum ResponseCode {
   Error = 'Error'
 }
 /**
- * Returns a singleton instance of `HotkManager`.
+ * Contention of a single lock, see `LockStats`.
  *
- * All `HotkManager` instances share the same internal state, allowing global coordination of hotkeys across your application.
+ * Properties:
+ * - `acquisitions` (number): Times the lock was taken.
+ * - `contended` (number): Times a taker had to wait because the lock was held elsewhere.
+ * - `maxWaitMs` (number): Longest of those waits, in milliseconds.
+*/
+export interface LockCounters {
+  acquisitions: number
+  contended: number
+  maxWaitMs: number
+}
+/**
+ * Contention of the locks of the manager, see `HotkManager.lockStats`.
+ *
+ * Properties:
+ * - `manager` (LockCounters): The manager lock, taken by every method and by background registrations
+ *   (`applyKeymapAsync`, deferred registrations).
+ * - `dispatch` (LockCounters): The dispatch state, shared by the thread receiving OS events, the JS thread
+ *   delivering them and the methods changing how they are dispatched.
+*/
+export interface LockStats {
+  manager: LockCounters
+  dispatch: LockCounters
+}
+/**
+ * Outcome of `HotkManager.startEventTrace`.
+ *
+ * Properties:
+ * - `code` (ResponseCode): `Ok` if the trace started, `Error` otherwise.
+ * - `error` (string, optional): Why the trace file could not be opened.
+*/
+export interface TraceResult {
+  code: ResponseCode
+  error?: string
+}
+/**
+ * Outcome of `HotkManager.init`.
+ *
+ * - `Installed`: The callback was installed and will receive events.
+ * - `Replaced`: The callback was installed in place of a previous one.
+ * - `Failed`: The callback could not be installed.
+*/
+export const enum InitResult {
+  Installed = 'Installed',
+  Replaced = 'Replaced',
+  Failed = 'Failed'
+}
+/**
+ * Outcome of `HotkManager.registerChecked`.
+ *
+ * - `Registered`: The hotkey was not registered and now is.
+ * - `AlreadyRegistered`: The hotkey was already registered; nothing was done.
+ * - `Failed`: The hotkey could not be registered.
+*/
+export const enum RegistrationResult {
+  Registered = 'Registered',
+  AlreadyRegistered = 'AlreadyRegistered',
+  Failed = 'Failed'
+}
+/**
+ * Identifies the kind of a registration warning.
+ *
+ * - `NoModifier`: The hotkey has no modifiers, so the key is captured system-wide.
+ * - `RiskyKey`: The key is one other applications rely on (e.g. `Escape`, `Enter`).
+ * - `UnsupportedModifier`: A modifier is ignored by the OS on this platform.
+ * - `ReservedCombo`: The combination is reserved by the operating system.
+ * - `ImeKey`: The key is an input method or language key, usually consumed by the IME before it can fire.
+*/
+export const enum WarningCode {
+  NoModifier = 'NoModifier',
+  RiskyKey = 'RiskyKey',
+  UnsupportedModifier = 'UnsupportedModifier',
+  ReservedCombo = 'ReservedCombo',
+  ImeKey = 'ImeKey'
+}
+/**
+ * A non-fatal problem detected while registering a hotkey.
+ *
+ * Properties:
+ * - `code` (WarningCode): What the warning is about.
+ * - `severity` (number): How serious it is: `1` informative, `2` likely unwanted, `3` likely not to work.
+ * - `message` (string): Human-readable description.
+*/
+export interface Warning {
+  code: WarningCode
+  severity: number
+  message: string
+}
+/**
+ * Progress of a keymap being applied in the background.
+ *
+ * Properties:
+ * - `done` (number): How many entries have been processed so far.
+ * - `total` (number): How many entries the keymap contains.
+*/
+export interface KeymapProgress {
+  done: number
+  total: number
+}
+/**
+ * Validation result for a single keymap entry.
+ *
+ * Properties:
+ * - `index` (number): Position of the entry in the validated keymap.
+ * - `valid` (boolean): Whether the entry can be registered as is.
+ * - `reason` (string | undefined): Why the entry is invalid.
+*/
+export interface ValidationEntry {
+  index: number
+  valid: boolean
+  reason?: string
+}
+/**
+ * Result of validating a whole keymap.
+ *
+ * Properties:
+ * - `valid` (boolean): `true` when every entry is valid.
+ * - `entries` (ValidationEntry[]): One entry per validated descriptor, in order.
+*/
+export interface ValidationReport {
+  valid: boolean
+  entries: Array<ValidationEntry>
+}
+/**
+ * Checks whether a combination can be registered at all, returning why it can't.
+ *
+ * A combination is rejected when its key is `Unidentified` or a modifier key (e.g. `ShiftLeft`), since no
+ * `HotKey` can be built from it. `register` runs the same check before reaching the OS.
+ * Platform support is left to the OS backend, see `validateKeymap` to check it upfront.
+ *
+ * @returns {string | null} The reason, or `null` if the combination is valid.
  *
  * @example
  * ```js
- * import { hotk } from '@hotk/core';
+ * import { validateCombo, Mod, KeyCode } from '@hotk/core';
  *
- * const manager = hotk();
+ * validateCombo([Mod.Control], KeyCode.KeyA); // null
+ * validateCombo([Mod.Control], KeyCode.ShiftLeft); // "ShiftLeft is a modifier key"
  * ```
 */
-export declare function hotk(): HotkManager | null
+export declare function validateCombo(mods: Array<Mod>, code: KeyCode): string | null
 /**
- * Represents a response from a hotkey operation.
+ * Checks a whole keymap for problems without registering anything.
+ *
+ * Every entry is checked for:
+ * - A modifier key (e.g. `ShiftLeft`) used as the main key.
+ * - Keys or modifiers the current platform can't register.
+ * - Combinations reserved by the operating system.
+ * - Duplicates of an earlier entry.
+ *
+ * @example
+ * ```js
+ * import { validateKeymap, Mod, KeyCode } from '@hotk/core';
+ *
+ * const report = validateKeymap([
+ *   { mods: [Mod.Control], code: KeyCode.KeyA },
+ *   { mods: [Mod.Control], code: KeyCode.KeyA },
+ * ]);
+ *
+ * for (const entry of report.entries.filter((e) => !e.valid)) {
+ *   console.log(`#${entry.index}: ${entry.reason}`);
+ * }
+ * ```
 */
-export declare class HotkReponse {
-  code: ResponseCode
-  id: number
-  error?: string
-  /**
-  * Checks whether the response indicates success.
-  *
-  * @returns `true` if the operation was successful, otherwise `false`.
-  */
-  isOk(): boolean
+export declare function validateKeymap(descs: Array<Desc>): ValidationReport
+/**
+ * Checks whether the process may observe global keyboard input.
+ *
+ * On macOS global hotkeys need the Accessibility / Input Monitoring permission; without it registration
+ * succeeds but no event is ever received. Other platforms need no permission and always return `true`.
+ *
+ * @returns {boolean} `true` if the process is trusted for input.
+ *
+ * @example
+ * ```js
+ * import { hasInputPermission, requestInputPermission } from '@hotk/core';
+ *
+ * if (!hasInputPermission()) {
+ *   requestInputPermission();
+ * }
+ * ```
+*/
+export declare function hasInputPermission(): boolean
+/**
+ * Asks the user to grant input permission, showing the system prompt if it is not granted yet.
+ *
+ * The prompt does not block: the returned value is the permission at the time of the call, so
+ * poll `hasInputPermission` to know when it is granted. Other platforms always return `true`.
+ *
+ * @returns {boolean} `true` if the permission is already granted.
+*/
+export declare function requestInputPermission(): boolean
+/**
+ * Returns the modifiers that can meaningfully be combined with `code` on this platform,
+ * so modifier pickers can constrain their choices.
+ *
+ * Only modifiers the OS honours are listed (`Meta` on macOS, `Super` elsewhere, for the same key), and
+ * none for keys that can't be registered. Numpad digits and the decimal key leave out `Shift`, which
+ * toggles them to their navigation meaning while NumLock is on.
+ *
+ * @example
+ * ```js
+ * import { validModifiersFor, KeyCode } from '@hotk/core';
+ *
+ * validModifiersFor(KeyCode.KeyA); // ['Control', 'Alt', 'Shift', 'Super'] on Windows and Linux
+ * ```
+*/
+export declare function validModifiersFor(code: KeyCode): Array<Mod>
+/**
+ * Checks whether two combinations would be grabbed as the same hotkey by the OS, so they can't both
+ * be registered or one would shadow the other.
+ *
+ * Every backend matches modifiers exactly: `Control+Shift+KeyA` doesn't conflict with `Control+KeyA`.
+ * Combinations still conflict when they only differ in what the OS ignores: modifiers it doesn't grab
+ * (e.g. `Hyper`), `Meta` and `Super` (the same key), `CommandOrControl` and its resolved modifier,
+ * and on Windows keys sharing a virtual key (`NumpadEnter` and `Enter`).
+ *
+ * @example
+ * ```js
+ * import { combosConflict, KeyCode, Mod } from '@hotk/core';
+ *
+ * combosConflict({ mods: [Mod.Control], code: KeyCode.KeyA }, { mods: [Mod.Control, Mod.Hyper], code: KeyCode.KeyA }); // true
+ * combosConflict({ mods: [Mod.Control], code: KeyCode.KeyA }, { mods: [Mod.Control, Mod.Shift], code: KeyCode.KeyA }); // false
+ * ```
+*/
+export declare function combosConflict(a: Desc, b: Desc): boolean
+/**
+ * What this build supports on the current platform, see `capabilities`.
+ *
+ * Properties:
+ * - `backend` (string): The hotkey backend in use: `"windows"`, `"macos"` or `"x11"`.
+ * - `sideSpecificModifiers` (boolean): Whether left and right modifiers can be told apart.
+ * - `mediaKeys` (boolean): Whether media keys (e.g. `MediaPlayPause`) can be registered.
+ * - `liveKeyState` (boolean): Whether the modifiers currently held can be read, e.g. for `exact` hotkeys.
+ * - `focusGating` (boolean): Whether hotkeys can be limited to when a given window is focused.
+ * - `layoutQueries` (boolean): Whether `currentLayout` and `setOnLayoutChange` report the keyboard layout.
+ * - `lowLevelHook` (boolean): Whether a low-level keyboard hook is available, e.g. for `setWatchUnmapped`.
+ * - `osRepeatControl` (boolean): Whether `setIgnoreOsRepeat` has any effect.
+*/
+export interface Capabilities {
+  backend: string
+  sideSpecificModifiers: boolean
+  mediaKeys: boolean
+  liveKeyState: boolean
+  focusGating: boolean
+  layoutQueries: boolean
+  lowLevelHook: boolean
+  osRepeatControl: boolean
 }
 /**
- * Manages global hotkeys by handling registration, unregistration and listening for hotkey events.
+ * Reports what this build supports on the current platform, so features can be detected up front
+ * instead of by calling them and checking for `false` or `null`.
+ *
+ * @example
+ * ```js
+ * import { capabilities } from '@hotk/core';
+ *
+ * if (!capabilities().layoutQueries) {
+ *   console.warn('Bindings will not follow keyboard layout changes');
+ * }
+ * ```
 */
-export declare class HotkManager {
-  /**
+export declare function capabilities(): Capabilities
+/**
+ * Options for `HotkManager.destroy`.
+ *
+ * Properties:
+ * - `flush` (boolean, optional): Deliver pending events before detaching the callback. Defaults to `false`.
+ *   If the callback throws, the remaining events are still delivered and `destroy` rethrows the first error.
+*/
+export interface DestroyOptions {
+  flush?: boolean
+}
+/**
+ * Options for `HotkManager.register`.
+ *
+ * Properties:
+ * - `exactModifiers` (boolean, optional): Drop the events of the hotkey unless exactly its modifiers are held,
+ *   checked against the live key state, so `Ctrl+A` doesn't fire while `Ctrl+Shift+A` is held.
+ *   Only Windows exposes the live key state; elsewhere the events are never dropped. Defaults to `false`.
+ * - `idempotent` (boolean, optional): Registering a combination that is already registered succeeds with its id
+ *   without counting another registration, so a single `unregister` releases it. Suited for re-applying a
+ *   configuration. Defaults to `false`.
+*/
+export interface RegisterOptions {
+  exactModifiers?: boolean
+  idempotent?: boolean
+}
+/**
+ * Settings for `HotkManager.configure`. Fields left unset keep their current value.
+ *
+ * Properties:
+ * - `bufferPreinit` (boolean, optional): See `setBufferPreinit`.
+ * - `emitLifecycle` (boolean, optional): See `setEmitLifecycle`.
+ * - `lateEventGraceMs` (number, optional): See `setLateEventGraceMs`.
+ * - `releaseWatchdogMs` (number, optional): See `setReleaseWatchdog`; `0` disables it.
+ * - `queueLimit` (number, optional): See `setQueueLimit`; `0` removes the bound.
+ * - `queuePolicy` (QueuePolicy, optional): Policy of the queue bound. Without a `queueLimit` it applies to the
+ *   current one. Defaults to the current policy, or `DropNewest` if there is none.
+ * - `ignoreOsRepeat` (boolean, optional): See `setIgnoreOsRepeat`.
+ * - `ignoredModifiers` (Mod[], optional): See `setIgnoredModifiers`.
+ * - `instanceName` (string, optional): See `setInstanceName`; an empty string removes the name.
+ * - `consoleTrace` (boolean, optional): See `enableConsoleTrace`.
+*/
+export interface HotkOptions {
+  bufferPreinit?: boolean
+  emitLifecycle?: boolean
+  lateEventGraceMs?: number
+  releaseWatchdogMs?: number
+  queueLimit?: number
+  queuePolicy?: QueuePolicy
+  ignoreOsRepeat?: boolean
+  ignoredModifiers?: Array<Mod>
+  instanceName?: string
+  consoleTrace?: boolean
+}
+/**
+ * Options for `HotkManager.deferReleased`.
+ *
+ * Properties:
+ * - `dropWithheld` (boolean, optional): Drop the withheld `Released` instead of delivering it when the deferral
+ *   is disabled. Defaults to `false`.
+*/
+export interface DeferReleasedOptions {
+  dropWithheld?: boolean
+}
+/**
+ * Options for `HotkManager.setBatchCallback`.
+ *
+ * Properties:
+ * - `intervalMs` (number, optional): Milliseconds between batches. Defaults to `16`.
+ * - `maxSize` (number, optional): Number of waiting events that triggers a batch right away. Defaults to `256`.
+*/
+export interface BatchOptions {
+  intervalMs?: number
+  maxSize?: number
+}
+/**
+ * Label of a registered hotkey, see `HotkManager.labelsById`.
+ *
+ * Properties:
+ * - `id` (number): The hotkey id, as found in events.
+ * - `label` (string): Its canonical accelerator, e.g. `Control+Shift+KeyK`.
+*/
+export interface HotkeyLabel {
+  id: number
+  label: string
+}
+/**
+ * Upstream view of a registered hotkey, see `HotkManager.hotkeyForId`.
+ *
+ * Properties:
+ * - `rawId` (number): The `global_hotkey` `HotKey` id.
+ * - `code` (KeyCode): The key code it was registered with.
+ * - `mods` (Mod[]): The modifiers it was registered with.
+ * - `modifiersBits` (number): The `global_hotkey` `Modifiers` bits, with `Meta` folded into `Super`.
+*/
+export interface HotKeyInfo {
+  rawId: number
+  code: KeyCode
+  mods: Array<Mod>
+  modifiersBits: number
+}
+/**
+ * Returns a singleton instance of `HotkManager`.
+ *
+ * All `HotkManager` instances share the same internal state, allowing global coordination of hotkeys across your application.
+ *
+ * Returns `null` if the hotkey backend could not be created, e.g. on Linux without an X server; `hotkError()`
+ * tells why. The backend is created once, so later calls return the same result.
+ *
+ * @example
+ * ```js
+ * import { hotk, hotkError } from '@hotk/core';
+ *
+ * const manager = hotk();
+ * if (!manager) throw new Error(hotkError());
+ * ```
+*/
+export declare function hotk(): HotkManager | null
+/**
+ * Returns why the manager could not be created, when `hotk()` returns `null`.
+ *
+ * @returns The reason, or `null` if the manager was created.
+ *
+ * @example
+ * ```js
+ * import { hotk, hotkError } from '@hotk/core';
+ *
+ * const manager = hotk();
+ * if (!manager) console.error(hotkError());
+ * ```
+*/
+export declare function hotkError(): string | null
+/**
+ * Represents a response from a hotkey operation.
+ *
+ * `operation` tells whether it comes from a registration or an unregistration.
+ * On failure, `error` is a message for logging and `errorKind` tells what went wrong.
+ * `warnings` lists every non-fatal problem found for the hotkey; `warning` is the first message, if any.
+*/
+export declare class HotkReponse {
+  operation: Operation
+  code: ResponseCode
+  id: number
+  error?: string
+  errorKind?: ErrorKind
+  warnings: Array<Warning>
+  warning?: string
+  /**
+  * Checks whether the response indicates success.
+  *
+  * @returns `true` if the operation was successful, otherwise `false`.
+  */
+  isOk(): boolean
+}
+/**
+ * Manages global hotkeys by handling registration, unregistration and listening for hotkey events.
+*/
+export declare class HotkManager {
+  /**
   * Initializes the hotkey event listener.
   *
   * This method sets up the callback that will be invoked for every global hotkey event.
-  * It can only be called once per program. Subsequent calls will do nothing and return `false`.
+  * Only one callback is active: calling it again replaces the installed callback, e.g. when switching
+  * contexts. Events not delivered yet go to the new one, which keeps the `ref`/`unref` state of the
+  * callback it replaces.
   *
   * @param on_event - A function that will be called with each hotkey event.
+  * @returns {InitResult} `Installed` on success, `Replaced` if it replaced a previous callback,
+  * `Failed` if `on_event` is not a function, the listener could not be created, or the global event
+  * handler could not be installed (e.g. another component of the process installed its own).
   *
   * @example
   * ```js
@@ -371,15 +1119,21 @@ export declare class HotkManager {
   * const result = manager.register([Mod.Control], KeyCode.KeyA);
   *
   * // Listen for hotkey events
-  * manager.init((event) => {
+  * const status = manager.init((event) => {
   *   console.log('Received event:', event);
   * });
   * ```
   */
-  init(on_event: (event: Event) => void): boolean
+  init(on_event: (event: Event) => void): InitResult
   /**
   * Registers a global hotkey.
   *
+  * Registering a combination that is already registered succeeds without registering it again with the OS;
+  * registrations are counted and the OS registration is released by the last `unregister`. With `idempotent`,
+  * the existing registration is returned as is instead.
+  *
+  * @param {RegisterOptions} [options] - Optional matching behaviour.
+  *
   * @example
   * ```js
   * import { hotk, Mod, KeyCode } from '@hotk/core';
@@ -402,10 +1156,88 @@ export declare class HotkManager {
   * });
   * ```
   */
-  register(mods: Array<Mod>, code: KeyCode): HotkReponse
+  register(mods: Array<Mod>, code: KeyCode, options?: RegisterOptions | undefined | null): HotkReponse
+  /**
+  * Registers several hotkeys at once, taking the lock and notifying the backend a single time.
+  *
+  * Responses are in the same order as `descs`. Hotkeys that register stay registered even if others fail.
+  *
+  * @example
+  * ```js
+  * import { hotk, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const descs = [
+  *   { mods: [Mod.Control], code: KeyCode.KeyS },
+  *   { mods: [Mod.Control], code: KeyCode.KeyO },
+  * ];
+  * manager.registerMany(descs).forEach((response, i) => {
+  *   if (!response.isOk()) console.error(descs[i], response.error);
+  * });
+  * ```
+  */
+  registerMany(descs: Array<Desc>): Array<HotkReponse>
+  /**
+  * Registers a hotkey unless it is already registered, checking and registering under a single lock hold.
+  *
+  * Unlike calling `isRegistered` then `register`, two callers racing on the same combination can't both
+  * register it: exactly one gets `Registered`. An already registered combination is left untouched, its
+  * registration count included.
+  *
+  * @example
+  * ```js
+  * import { hotk, KeyCode, Mod, RegistrationResult } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * if (manager.registerChecked([Mod.Control], KeyCode.KeyS) === RegistrationResult.Failed) {
+  *   console.error('Could not register Control+S');
+  * }
+  * ```
+  */
+  registerChecked(mods: Array<Mod>, code: KeyCode): RegistrationResult
+  /**
+  * Registers a global hotkey like `register`, returning a handle that unregisters exactly this registration.
+  *
+  * Check `registered` or `error` on the handle to know whether registering succeeded.
+  *
+  * @example
+  * ```js
+  * import { hotk, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const handle = manager.registerHandle([Mod.Control], KeyCode.KeyS);
+  * if (!handle.registered) console.error(handle.error);
+  *
+  * // Later, without remembering Control+S.
+  * handle.unregister();
+  * ```
+  */
+  registerHandle(mods: Array<Mod>, code: KeyCode): RegistrationHandle
+  /**
+  * Registers a hotkey from the name of a `global-hotkey` key code (e.g. `"MediaPlayPause"`), for bindings
+  * stored as plain strings.
+  *
+  * `KeyCode` mirrors every code of the `global-hotkey` version this build ships with, so this behaves like
+  * `register` with the matching `KeyCode`. Names are matched exactly, like `KeyCode` variants; a name the
+  * backend doesn't recognize fails with `InvalidArgument` and id `0`. Codes without a `KeyCode` counterpart,
+  * which only a newer backend could add, are not registered: events and `listRegistered` report a `Desc`, which
+  * could not describe them. They fail with `Unsupported` and the id they would register as.
+  *
+  * @example
+  * ```js
+  * import { hotk, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const response = manager.registerRaw([Mod.Control], 'MediaPlayPause');
+  * if (!response.isOk()) console.error(response.error);
+  * ```
+  */
+  registerRaw(mods: Array<Mod>, codeName: string): HotkReponse
   /**
   * Unregisters a global hotkey.
   *
+  * A combination registered several times stays registered until it is unregistered as many times.
+  *
   * @example
   * ```js
   * import { hotk, Mod, KeyCode } from '@hotk/core';
@@ -429,68 +1261,1118 @@ export declare class HotkManager {
   */
   unregister(mods: Array<Mod>, code: KeyCode): HotkReponse
   /**
-  * Allows the Node.js process to exit naturally if no other tasks are pending.
+  * Sets the modifiers stripped from registrations and from the live state comparison of `exactModifiers`,
+  * so states like NumLock or Fn held on laptops don't cause spurious mismatches. Defaults to the lock
+  * modifiers (`CapsLock`, `NumLock`, `ScrollLock`, `FnLock` and `SymbolLock`).
   *
-  * This detaches the internal hotkey event callback from the event loop,
-  * meaning the process won't be held open just to listen for hotkey events.
+  * A registration that intentionally uses an ignored modifier registers without it, so
+  * `[Mod.Control, Mod.NumLock]` + `KeyA` becomes `Control+KeyA` and shares its id. Only later registrations
+  * are affected; pass `[]` to keep every modifier.
   *
-  * @returns `true` if the operation succeeded, `false` otherwise.
+  * @example
+  * ```js
+  * import { hotk, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setIgnoredModifiers([Mod.CapsLock, Mod.NumLock, Mod.Fn]);
+  * ```
+  */
+  setIgnoredModifiers(mods: Array<Mod>): void
+  /**
+  * Unregisters every hotkey, e.g. to tear down the bindings before installing a new set.
+  *
+  * Combinations registered several times are removed at once. Hotkeys that fail to unregister stay
+  * registered, so calling this again retries them.
+  *
+  * @returns One response per hotkey, sorted by id.
   *
   * @example
   * ```js
+  * import { hotk } from '@hotk/core';
+  *
   * const manager = hotk();
-  * manager.init(console.log);
-  * manager.unref(); // Now the process can exit if nothing else is running.
+  * const failed = manager.unregisterAll().filter(response => !response.isOk());
   * ```
   */
-  unref(): boolean
+  unregisterAll(): Array<HotkReponse>
   /**
-  * Keeps the Node.js process alive to continue listening for hotkey events.
+  * Checks whether a combination is registered, without touching the OS.
   *
-  * This ensures that the process will not exit until `destroy()` is called
-  * or `unref()` is used to detach the event callback from the event loop.
+  * @example
+  * ```js
+  * import { hotk, KeyCode, Mod } from '@hotk/core';
   *
-  * @returns `true` if the operation succeeded, `false` otherwise.
+  * const manager = hotk();
+  * button.disabled = manager.isRegistered([Mod.Control], KeyCode.KeyS);
+  * ```
+  */
+  isRegistered(mods: Array<Mod>, code: KeyCode): boolean
+  /**
+  * Checks whether registering a combination would clash with a hotkey of this process, before asking the OS.
+  *
+  * This is process-local conflict detection: it compares the computed id with the hotkeys registered here,
+  * so it also catches equivalent spellings (`Meta` and `Super`, ignored lock modifiers). It can't see the
+  * hotkeys of other applications; registering can still fail with `AlreadyRegistered` because of them.
   *
   * @example
   * ```js
+  * import { hotk, KeyCode, Mod } from '@hotk/core';
+  *
   * const manager = hotk();
-  * manager.init(console.log);
-  * manager.refer(); // Keeps the process alive
+  * if (manager.wouldConflict([Mod.Control], KeyCode.KeyS)) {
+  *   showError('Control+S is already bound');
+  * }
   * ```
   */
-  refer(): boolean
+  wouldConflict(mods: Array<Mod>, code: KeyCode): boolean
   /**
-  * Stops listening for hotkey events.
+  * Enqueues the registration of a hotkey and returns its id right away, without waiting for the backend.
   *
-  * This is required to allow the Node.js process to exit when `init` was called without `unref: true`.
+  * Registrations are performed in order on a background thread, and each response is reported to the
+  * callback set with `setOnDeferredResult`. Suited for fire-and-forget bulk registration from the JS thread.
+  *
+  * @returns The id the hotkey will have, as computed by `getHotkeyId`.
   *
   * @example
   * ```js
   * import { hotk, Mod, KeyCode } from '@hotk/core';
   *
-  * // Get the singleton instance
   * const manager = hotk();
+  * manager.setOnDeferredResult(response => {
+  *   if (!response.isOk()) console.error(response.id, response.error);
+  * });
+  * const id = manager.registerDeferred([Mod.Control], KeyCode.KeyD);
+  * ```
+  */
+  registerDeferred(mods: Array<Mod>, code: KeyCode): number
+  /**
+  * Sets the callback receiving the response of every `registerDeferred` call. Pass `null` to remove it.
   *
-  * // Register Ctrl + A as a hotkey
-  * const result = manager.register([Mod.Control], KeyCode.KeyA);
+  * Responses of registrations completed while no callback is set are dropped.
+  * The callback does not keep the process alive.
+  */
+  setOnDeferredResult(onResult: ((response: HotkReponse) => void) | null): void
+  /**
+  * Sets a callback receiving the events of every registered hotkey on `code`, whatever its modifiers,
+  * e.g. to highlight a key in an overlay. Pass `null` to remove it.
   *
-  * if (result.isOk()) {
-  *   console.log('Hotkey successfully registered');
-  * } else {
-  *   console.error('Failed to register hotkey');
-  * }
+  * It is called in addition to the `init` callback and the per-hotkey ones.
+  * The callback does not keep the process alive.
   *
-  * // Start listening for hotkey events without unref
-  * manager.init((event) => {
-  *   console.log('Received event:', event);
+  * @example
+  * ```js
+  * import { hotk, KeyCode } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setOnKey(KeyCode.KeyA, event => overlay.flash(event.code));
+  * ```
+  */
+  setOnKey(code: KeyCode, onEvent: ((event: Event) => void) | null): void
+  /**
+  * Attaches a handler to a combination, registering it first if it is not registered yet.
+  *
+  * Events of the combination go to `onEvent` instead of the `init` callback, so there is no need to switch
+  * on `event.id`. Calling it again for the same combination replaces the handler. The handler stays until
+  * `off` is called, even if the hotkey is unregistered, and does not keep the process alive.
+  *
+  * @returns `false` if the hotkey could not be registered; no handler is attached then.
+  *
+  * @example
+  * ```js
+  * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.on([Mod.Control], KeyCode.KeyS, event => {
+  *   if (event.eventType === EventType.Pressed) save();
   * });
+  * ```
+  */
+  on(mods: Array<Mod>, code: KeyCode, onEvent: (event: Event) => void): boolean
+  /**
+  * Detaches the handler attached with `on`, so the events of the combination go to the `init` callback again.
   *
-  * // Stop listening after 3 seconds so the process can exit
-  * setTimeout(() => {
-  *   manager.destroy();
-  * }, 3000);
+  * The hotkey stays registered; use `unregister` to release it.
+  *
+  * @returns `false` if the combination had no handler.
+  */
+  off(mods: Array<Mod>, code: KeyCode): boolean
+  /**
+  * Creates an empty hotkey profile.
+  *
+  * Profiles hold whole sets of hotkeys (e.g. one per game) that are switched with `activateProfile`.
+  *
+  * @returns `false` if a profile with that name already exists.
+  */
+  createProfile(name: string): boolean
+  /**
+  * Adds a hotkey to a profile, with an optional callback for its events.
+  *
+  * The hotkey is only registered while the profile is active; if it already is, it is registered right away.
+  * The callback receives the same events as the `init` callback, but only for this hotkey and only while
+  * the profile is active. It does not keep the process alive.
+  *
+  * @returns The registration response if the profile is active, otherwise an `Ok` response with the hotkey id.
+  * An `Error` response if the profile does not exist.
+  *
+  * @example
+  * ```js
+  * import { hotk, Mod, KeyCode } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.createProfile('game');
+  * manager.registerIn('game', [Mod.Control], KeyCode.KeyG, event => console.log('Game hotkey', event));
+  * manager.activateProfile('game');
+  * ```
+  */
+  registerIn(profile: string, mods: Array<Mod>, code: KeyCode, onEvent?: ((event: Event) => void) | undefined | null): HotkReponse
+  /**
+  * Switches the active profile: unregisters the hotkeys of the previously active profile,
+  * then registers the ones of `name`. Only one profile is active at a time.
+  *
+  * @returns One registration response per hotkey of the profile, or `null` if it does not exist.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.activateProfile('editor');
+  * ```
+  */
+  activateProfile(name: string): Array<HotkReponse> | null
+  /**
+  * Returns the name of the active profile, or `null` if none was activated.
+  */
+  activeProfile(): string | null
+  /**
+  * Lists every registered combination, ordered by hotkey id, e.g. for a "current shortcuts" screen.
+  *
+  * @example
+  * ```js
+  * import { hotk, descToAccelerator } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const rows = manager.listRegistered().map(descToAccelerator);
+  * ```
+  */
+  listRegistered(): Array<Desc>
+  /**
+  * Lists every registered combination as an accelerator string, ordered by hotkey id.
+  *
+  * Each string is in the canonical form of `descToAccelerator`, so it round-trips through `parseHotkey`.
+  *
+  * @example
+  * ```js
+  * import { hotk, parseHotkey } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const saved = manager.exportAccelerators();
+  * // Later
+  * saved.map(parseHotkey).forEach(desc => manager.register(desc.mods, desc.code));
   * ```
   */
-  destroy(): void
+  exportAccelerators(): Array<string>
+  /**
+  * Returns a fingerprint of the registered combinations, to detect whether the keymap changed,
+  * e.g. before persisting it.
+  *
+  * It hashes the sorted canonical accelerators (see `exportAccelerators`), so it doesn't depend on
+  * registration order and is stable across runs and platforms.
+  *
+  * @returns {string} 16 hex digits.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const fingerprint = hotk().keymapFingerprint();
+  * if (fingerprint !== settings.keymapFingerprint) {
+  *   save(hotk().exportAccelerators(), fingerprint);
+  * }
+  * ```
+  */
+  keymapFingerprint(): string
+  /**
+  * Returns the accelerator label of every registered hotkey, so UIs keyed by event ids can render them
+  * without a call per id.
+  *
+  * Labels are the same strings as `exportAccelerators`, in the same order (sorted by id).
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const labels = new Map(manager.labelsById().map(({ id, label }) => [id, label]));
+  * manager.init(event => console.log(labels.get(event.id)));
+  * ```
+  */
+  labelsById(): Array<HotkeyLabel>
+  /**
+  * Returns the upstream `global_hotkey` view of hotkey `id`, to correlate with code using that crate directly.
+  *
+  * @returns The hotkey info, or `null` if `id` is not registered.
+  *
+  * @example
+  * ```js
+  * import { hotk, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyK);
+  * manager.hotkeyForId(id); // { rawId: id, code: 'KeyK', mods: ['Control', 'Shift'], modifiersBits: 520 }
+  * ```
+  */
+  hotkeyForId(id: number): HotKeyInfo | null
+  /**
+  * Generates a TypeScript module with one constant per registered hotkey, for build pipelines that want
+  * type-safe references to the bindings.
+  *
+  * Hotkeys carry no action name, so each constant is named after its accelerator
+  * (`Control+Shift+KeyK` becomes `Control_Shift_KeyK`). Constants are sorted by id.
+  *
+  * @returns {string} The module source.
+  *
+  * @example
+  * ```js
+  * import { writeFileSync } from 'node:fs';
+  * import { hotk } from '@hotk/core';
+  *
+  * writeFileSync('src/keymap.ts', hotk().exportKeymapTs());
+  * // export const Control_Shift_KeyK = { mods: [Mod.Control, Mod.Shift], code: KeyCode.KeyK } as const;
+  * ```
+  */
+  exportKeymapTs(): string
+  /**
+  * Returns how many logical registrations share the OS registration of hotkey `id`, `0` if it is not registered.
+  */
+  registrants(id: number): number
+  /**
+  * Resumes delivering the events of hotkey `id` after `disarm`.
+  *
+  * Hotkeys are armed when registered.
+  *
+  * @returns `true` if `id` is registered.
+  */
+  arm(id: number): boolean
+  /**
+  * Ignores the presses of hotkey `id` for `cooldownMs` after each one that fires, like a game ability.
+  *
+  * Presses during the cooldown are dropped along with their `Released`. Pass `0` to disable it.
+  * The cooldown is forgotten when the hotkey is unregistered.
+  *
+  * @returns `true` if `id` is registered.
+  *
+  * @example
+  * ```js
+  * import { hotk, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const { id } = manager.register([Mod.Alt], KeyCode.KeyQ);
+  * manager.setCooldown(id, 5000);
+  * ```
+  */
+  setCooldown(id: number, cooldownMs: number): boolean
+  /**
+  * Registers a hotkey that also fires a `MultiTap` event when it is pressed `taps` times in a row,
+  * each press within `windowMs` of the first, e.g. a double tap to toggle an overlay.
+  *
+  * The `MultiTap` event follows the `Pressed` of the last tap and carries the count in `taps`; every press
+  * is still delivered as usual. A press after the window starts counting again. Timing is tracked as the
+  * presses arrive from the OS, so event loop jitter doesn't affect it. Registering it again updates `taps`
+  * and `windowMs`; they are forgotten when the hotkey is unregistered.
+  *
+  * @returns The registration response. An `InvalidArgument` error if `taps` is less than `2`.
+  *
+  * @example
+  * ```js
+  * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.registerMultiTap([Mod.Control], KeyCode.Space, 2, 300);
+  * manager.init(event => {
+  *   if (event.eventType === EventType.MultiTap) overlay.toggle();
+  * });
+  * ```
+  */
+  registerMultiTap(mods: Array<Mod>, code: KeyCode, taps: number, windowMs: number): HotkReponse
+  /**
+  * Registers a sequence of hotkeys, e.g. `Ctrl+K` then `Ctrl+S`, that fires a `Sequence` event when its
+  * steps are pressed in order, all within `windowMs` of the first.
+  *
+  * Each step is registered as a regular hotkey, so its events are still delivered as usual. The `Sequence`
+  * event follows the `Pressed` of the last step; its `id` is the one returned here and its `code` and `mods`
+  * are those of the last step. Pressing another registered hotkey, or the next step after the window, starts
+  * the sequence over. Registering the same sequence again only updates `windowMs`.
+  *
+  * @returns The registration response, with the id of the sequence. If a step fails, the steps registered
+  * before it are unregistered and its response is returned. An `InvalidArgument` error for fewer than
+  * two steps.
+  *
+  * @example
+  * ```js
+  * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const { id } = manager.registerSequence(
+  *   [
+  *     { mods: [Mod.Control], code: KeyCode.KeyK },
+  *     { mods: [Mod.Control], code: KeyCode.KeyS },
+  *   ],
+  *   1000,
+  * );
+  * manager.init(event => {
+  *   if (event.id === id && event.eventType === EventType.Sequence) saveAll();
+  * });
+  * ```
+  */
+  registerSequence(steps: Array<Desc>, windowMs: number): HotkReponse
+  /**
+  * Removes a sequence registered with `registerSequence` and unregisters its steps.
+  *
+  * @returns `false` if `id` is not a registered sequence.
+  */
+  unregisterSequence(id: number): boolean
+  /**
+  * Withholds the `Released` events of hotkey `id` while `enabled`, for "hold to drag, we'll tell you when
+  * to finish" interactions: `Pressed` is still delivered right away.
+  *
+  * Disabling it delivers the most recent `Released` withheld meanwhile, if any, or drops it with
+  * `dropWithheld`. The deferral is forgotten when the hotkey is unregistered.
+  *
+  * @param {DeferReleasedOptions} [options] - What to do with the withheld event when disabling it.
+  * @returns `true` if `id` is registered.
+  *
+  * @example
+  * ```js
+  * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const { id } = manager.register([Mod.Alt], KeyCode.KeyD);
+  * manager.init(event => {
+  *   if (event.eventType === EventType.Pressed) {
+  *     manager.deferReleased(id, true);
+  *     drag.start().then(() => manager.deferReleased(id, false));
+  *   }
+  * });
+  * ```
+  */
+  deferReleased(id: number, enabled: boolean, options?: DeferReleasedOptions | undefined | null): boolean
+  /**
+  * Registers a hotkey that also fires a `LongPress` event when it is held for longer than `holdMs`,
+  * e.g. a "hold Alt+Space" gesture.
+  *
+  * The `LongPress` event comes right before the `Released` that ends the hold, and its `deltaMs` is
+  * how long the hotkey was held. Shorter presses only produce the usual `Pressed` and `Released`.
+  * The hold is timed as events arrive from the OS, so event loop latency doesn't affect it.
+  * Registering it again updates `holdMs`; it is forgotten when the hotkey is unregistered.
+  *
+  * @example
+  * ```js
+  * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.registerLongPress([Mod.Alt], KeyCode.Space, 500);
+  * manager.init(event => {
+  *   if (event.eventType === EventType.LongPress) launcher.open();
+  * });
+  * ```
+  */
+  registerLongPress(mods: Array<Mod>, code: KeyCode, holdMs: number): HotkReponse
+  /**
+  * Drops every event of hotkey `id`, keeping it registered, until `arm` is called.
+  *
+  * Useful for actions that must only fire once explicitly enabled, e.g. behind a safety toggle.
+  *
+  * @returns `true` if `id` is registered.
+  *
+  * @example
+  * ```js
+  * import { hotk, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyK);
+  * manager.disarm(id);
+  * // Later, once the user enables it
+  * manager.arm(id);
+  * ```
+  */
+  disarm(id: number): boolean
+  /**
+  * Registers every entry of a keymap in the background.
+  *
+  * Registration happens off the JS thread, so applying hundreds of bindings doesn't block the UI.
+  * `on_progress` is called with `{ done, total }` as entries are processed.
+  *
+  * @returns A promise resolving to one response per entry, in the same order as `descs`.
+  *
+  * @example
+  * ```js
+  * import { hotk, Mod, KeyCode } from '@hotk/core';
+  *
+  * const manager = hotk();
+  *
+  * const responses = await manager.applyKeymapAsync(
+  *   [
+  *     { mods: [Mod.Control], code: KeyCode.KeyA },
+  *     { mods: [Mod.Control], code: KeyCode.KeyB },
+  *   ],
+  *   ({ done, total }) => console.log(`${done}/${total}`),
+  * );
+  * ```
+  */
+  applyKeymapAsync(descs: Array<Desc>, on_progress?: (progress: KeymapProgress) => void): Promise<Array<HotkReponse>>
+  /**
+  * Frees the OS grabs of every registered hotkey, so other applications can register the same combinations,
+  * e.g. while another tool needs them.
+  *
+  * The hotkeys stay registered here (`registrants`, `exportAccelerators`, ...) but no longer fire until
+  * `reacquireOsGrabs` is called. Unregistering one of them meanwhile just forgets it.
+  *
+  * @returns One response per released hotkey, sorted by id.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.releaseOsGrabs();
+  * await otherToolFinished();
+  * manager.reacquireOsGrabs();
+  * ```
+  */
+  releaseOsGrabs(): Array<HotkReponse>
+  /**
+  * Registers again at the OS level the hotkeys freed by `releaseOsGrabs`.
+  *
+  * A combination grabbed by another application in the meantime fails and stays released,
+  * so calling this again retries it.
+  *
+  * @returns One response per released hotkey, sorted by id.
+  */
+  reacquireOsGrabs(): Array<HotkReponse>
+  /**
+  * Pauses every global hotkey, e.g. while a text field captures shortcuts, without losing the registrations.
+  *
+  * Works like `releaseOsGrabs`: the OS grabs are freed, but the hotkeys stay registered here, so
+  * `listRegistered` and `isRegistered` still reflect the intended state. Call `resume` to restore them.
+  *
+  * A hotkey the OS fails to unregister keeps its grab and keeps firing; calling `suspend` again retries it.
+  *
+  * @returns The responses of the hotkeys that failed to unregister, empty if all were paused.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * input.addEventListener('focus', () => {
+  *   manager.suspend().forEach(response => console.error('Still active', response.id, response.error));
+  * });
+  * input.addEventListener('blur', () => {
+  *   manager.resume().forEach(response => console.error('Lost', response.id, response.error));
+  * });
+  * ```
+  */
+  suspend(): Array<HotkReponse>
+  /**
+  * Registers again with the OS the hotkeys paused by `suspend`.
+  *
+  * A combination another application grabbed in the meantime can't be restored; it stays paused,
+  * so calling `resume` again retries it.
+  *
+  * @returns The responses of the hotkeys that failed to re-register, empty if all were restored.
+  */
+  resume(): Array<HotkReponse>
+  /**
+  * Replaces the backend with a fresh one and registers every hotkey again, e.g. once `isHealthy` reports
+  * that the Windows backend thread stopped.
+  *
+  * Hotkeys paused by `suspend` stay paused. One that can't be restored is paused as well, so `resume`
+  * retries it. The layout change and error callbacks and the repeat setting carry over; the backend priority
+  * and `setWatchUnmapped` must be set again. Only Windows runs the backend on its own thread; elsewhere
+  * this does nothing. The old backend is stopped first, as a process runs a single one; throws if the new
+  * one can't be created, leaving `isHealthy` false. A backend thread that doesn't stop within a second is
+  * left behind to exit on its own, and the new one starts anyway.
+  *
+  * @returns The responses of the hotkeys that failed to re-register, empty if all were restored.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * if (!manager.isHealthy()) {
+  *   manager.recreate().forEach(response => console.error('Lost', response.id, response.error));
+  * }
+  * ```
+  */
+  recreate(): Array<HotkReponse>
+  /**
+  * Allows the Node.js process to exit naturally if no other tasks are pending.
+  *
+  * This detaches the internal hotkey event callback from the event loop,
+  * meaning the process won't be held open just to listen for hotkey events.
+  *
+  * @returns `true` if the operation succeeded, `false` otherwise.
+  *
+  * @example
+  * ```js
+  * const manager = hotk();
+  * manager.init(console.log);
+  * manager.unref(); // Now the process can exit if nothing else is running.
+  * ```
+  */
+  unref(): boolean
+  /**
+  * Keeps the Node.js process alive to continue listening for hotkey events.
+  *
+  * This ensures that the process will not exit until `destroy()` is called
+  * or `unref()` is used to detach the event callback from the event loop.
+  *
+  * @returns `true` if the operation succeeded, `false` otherwise.
+  *
+  * @example
+  * ```js
+  * const manager = hotk();
+  * manager.init(console.log);
+  * manager.refer(); // Keeps the process alive
+  * ```
+  */
+  refer(): boolean
+  /**
+  * Returns when `init` started listening, in milliseconds since the Unix epoch, or `null` if it was not
+  * called or `destroy` was called since. Replacing the callback with another `init` keeps the original time.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const since = hotk().listeningSince();
+  * console.log(since === null ? 'Not listening' : `Listening since ${new Date(since).toISOString()}`);
+  * ```
+  */
+  listeningSince(): number | null
+  /**
+  * Returns for how many milliseconds the manager has been listening, see `listeningSince`.
+  *
+  * Measured with a monotonic clock, so changes of the system time don't affect it.
+  */
+  listeningUptimeMs(): number | null
+  /**
+  * Enables or disables buffering of events that fire before `init` is called.
+  *
+  * While enabled and no callback is installed yet, events for registered hotkeys are kept
+  * and delivered, in order, as soon as `init` installs the callback. At most 64 events are
+  * kept; once the buffer is full the oldest event is dropped to make room for the newest.
+  * Disabling buffering discards anything buffered so far.
+  *
+  * Buffering is disabled by default.
+  *
+  * @example
+  * ```js
+  * const manager = hotk();
+  * manager.setBufferPreinit(true);
+  * manager.register([Mod.Control], KeyCode.KeyA);
+  *
+  * // Presses of Ctrl + A made before this point are delivered here.
+  * manager.init(console.log);
+  * ```
+  */
+  setBufferPreinit(enabled: boolean): void
+  /**
+  * Delivers events in batches instead of one call per event, for apps that process input once per frame.
+  *
+  * Events accumulate and `onBatch` is called with all of them every `intervalMs`, or as soon as `maxSize`
+  * of them are waiting. While set, the `init` callback receives no events. Pass `null` to go back to
+  * per-event delivery; events still waiting are delivered to the previous `onBatch` first.
+  *
+  * The callback does not keep the process alive.
+  *
+  * @param {BatchOptions} [options] - Defaults to a batch every 16 ms, of at most 256 events.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setBatchCallback(events => events.forEach(handle), { intervalMs: 16 });
+  * ```
+  */
+  setBatchCallback(onBatch: ((events: Event[]) => void) | null, options?: BatchOptions): void
+  /**
+  * Returns how many events were dropped on their way to the `init` callback: those that could not be
+  * converted to JS objects, and those a full queue dropped (see `setQueueLimit`). A conversion failure
+  * only skips its own event; the following ones are still delivered.
+  */
+  droppedEvents(): number
+  /**
+  * Exempts the events of hotkey `id` from the queue limit set by `setQueueLimit`, e.g. for a critical
+  * "stop recording" key that must never be dropped. The other hotkeys are still dropped once the queue is full.
+  *
+  * The setting is forgotten when the hotkey is unregistered.
+  *
+  * @returns `true` if `id` is registered.
+  *
+  * @example
+  * ```js
+  * import { hotk, KeyCode, Mod } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const { id } = manager.register([Mod.Control, Mod.Alt], KeyCode.KeyR);
+  * manager.setQueueExempt(id, true);
+  * ```
+  */
+  setQueueExempt(id: number, enabled: boolean): boolean
+  /**
+  * Whether the events of hotkey `id` are exempt from the queue limit, see `setQueueExempt`.
+  */
+  isQueueExempt(id: number): boolean
+  /**
+  * Bounds how many events wait for the `init` callback, e.g. while the JS thread is busy under sustained
+  * input. Once `limit` events are waiting, `policy` decides which are dropped; each one counts in
+  * `droppedEvents`. Pass `0` to remove the bound, the default.
+  *
+  * Events delivered through `setBatchCallback` and those of hotkeys exempted with `setQueueExempt` are not
+  * affected.
+  *
+  * @example
+  * ```js
+  * import { hotk, QueuePolicy } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setQueueLimit(64, QueuePolicy.DropOldest);
+  * ```
+  */
+  setQueueLimit(limit: number, policy: QueuePolicy): void
+  /**
+  * Returns how often the locks of the manager were found held by another thread, and the longest wait for
+  * them, to tell whether heavy registration traffic delays event dispatch or the other way around.
+  *
+  * An uncontended lock costs a counter increment; waits are only timed when the lock is already held.
+  * Counters accumulate for the lifetime of the process.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const { contended, maxWaitMs } = manager.lockStats().dispatch;
+  * if (maxWaitMs > 16) console.warn(`Events waited up to ${maxWaitMs}ms, ${contended} times`);
+  * ```
+  */
+  lockStats(): LockStats
+  /**
+  * Feeds an event through the same path as events coming from the OS.
+  *
+  * Useful to test handlers without pressing keys. The event is handled like a real one, except that it has
+  * `synthetic` set to `true`, as does everything derived from it.
+  *
+  * @returns `true` if `id` belongs to a registered hotkey, or one still in the late event grace window.
+  * `false` for `Registered`, `Unregistered`, `MultiTap`, `LongPress` and `Sequence`, which the OS never sends.
+  *
+  * @example
+  * ```js
+  * const { id } = manager.register([Mod.Control], KeyCode.KeyA);
+  * manager.simulate(id, EventType.Pressed);
+  * ```
+  */
+  simulate(id: number, eventType: EventType): boolean
+  /**
+  * Also sends a synthetic `Registered` or `Unregistered` event through the event callbacks whenever a hotkey
+  * is registered with or unregistered from the OS, so a UI can follow the bindings from the event stream alone.
+  *
+  * Repeated registrations of a combination only produce an event for the first one, and unregistrations
+  * for the last one. Disabled by default.
+  *
+  * @example
+  * ```js
+  * import { EventType, hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setEmitLifecycle(true);
+  * manager.init(event => {
+  *   if (event.eventType === EventType.Registered) addBinding(event.id);
+  * });
+  * ```
+  */
+  setEmitLifecycle(enabled: boolean): void
+  /**
+  * Preallocates room for at least `additional` more registered hotkeys.
+  *
+  * Registering hundreds of hotkeys at once otherwise grows the internal map several times.
+  * This is only a performance hint; registration works the same without it.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.reserveCapacity(keymap.length);
+  * await manager.applyKeymapAsync(keymap);
+  * ```
+  */
+  reserveCapacity(additional: number): void
+  /**
+  * Keeps delivering events of an unregistered hotkey for `graceMs` after it was unregistered.
+  *
+  * An event can reach the manager just after its hotkey was unregistered, e.g. the key-up of a hotkey
+  * unregistered on key-down; by default it is dropped. Within the grace window it is delivered as usual.
+  * Pass `0` to disable it, which is the default.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setLateEventGraceMs(250);
+  * ```
+  */
+  setLateEventGraceMs(graceMs: number): void
+  /**
+  * Enables a watchdog that emits a synthetic `Released` for a hotkey still pressed after `timeoutMs`.
+  *
+  * A lost `Released` (e.g. focus changed mid keypress) otherwise leaves the hotkey held forever,
+  * breaking hold and tap logic. The real `Released`, if it arrives later, is not delivered again.
+  * Disabled by default; pass `null` or `0` to disable it.
+  *
+  * @param {number | null} timeoutMs - Milliseconds a hotkey may stay pressed.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setReleaseWatchdog(5000);
+  * ```
+  */
+  setReleaseWatchdog(timeoutMs?: number | undefined | null): void
+  /**
+  * Forgets which hotkeys are currently pressed, cancelling any pending watchdog `Released`.
+  *
+  * Use it after the app knows keys were released without being told, e.g. on focus loss.
+  */
+  resetKeyState(): void
+  /**
+  * Checks whether the key and every modifier of a combination are physically down right now.
+  *
+  * The combination doesn't need to be registered, so this can poll "is the user holding X".
+  * Other keys being down don't matter.
+  *
+  * Only Windows exposes the live key state; elsewhere this returns `false`.
+  *
+  * @example
+  * ```js
+  * import { hotk, KeyCode, Mod } from '@hotk/core';
+  *
+  * if (hotk().isComboHeld([Mod.Shift], KeyCode.Space)) {
+  *   boost();
+  * }
+  * ```
+  */
+  isComboHeld(mods: Array<Mod>, code: KeyCode): boolean
+  /**
+  * Whether the auto-repeat of a held hotkey is ignored, so each press yields a single `Pressed`.
+  *
+  * Windows sends the hotkey again on every key repeat while it is held; the other platforms only report
+  * the first press. This is enabled by default, so all platforms behave the same.
+  *
+  * Only Windows reports repeats; elsewhere this does nothing and returns `false`.
+  *
+  * @returns `true` if the setting was applied.
+  */
+  setIgnoreOsRepeat(enabled: boolean): boolean
+  /**
+  * Changes the scheduling priority of the thread running the hotkey backend.
+  *
+  * Lowering it reduces contention in apps where hotkeys are low priority, e.g. to save battery.
+  * The priority is left unchanged unless this is called.
+  *
+  * Only Windows runs the backend on its own thread; elsewhere this does nothing and returns `false`.
+  *
+  * @returns `true` if the priority was changed.
+  *
+  * @example
+  * ```js
+  * import { hotk, BackendPriority } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setBackendPriority(BackendPriority.BelowNormal);
+  * ```
+  */
+  setBackendPriority(priority: BackendPriority): boolean
+  /**
+  * Returns how many actions (registrations, unregistrations, ...) were sent to the backend thread
+  * and not processed yet.
+  *
+  * It drops back to `0` once each call completes; a number that keeps growing means the backend is stalled.
+  * Only Windows runs the backend on its own thread; elsewhere this is always `0`.
+  */
+  pendingActions(): number
+  /**
+  * Whether the backend can still process registrations.
+  *
+  * On Windows this is `false` once the thread running the backend message loop has exited, e.g. after a
+  * panic; nothing registers or fires after that, so the app should warn or restart. Elsewhere the backend
+  * has no thread of its own and this is always `true`.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * if (!manager.isHealthy()) console.error('The hotkey backend stopped');
+  * ```
+  */
+  isHealthy(): boolean
+  /**
+  * Returns the current scheduling priority of the backend thread, or `null` where there is none.
+  */
+  backendPriority(): BackendPriority | null
+  /**
+  * Returns the identifier of the active keyboard layout, or `null` where it cannot be read.
+  *
+  * On Windows this is the layout name (KLID) of the window in the foreground, e.g. `"00000409"` for US
+  * English, as last polled by the backend every 250 ms. Compare it over time to warn when
+  * layout-dependent bindings may have shifted. Other platforms return `null`.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * console.log(manager.currentLayout()); // "00000409"
+  * ```
+  */
+  currentLayout(): string | null
+  /**
+  * Sets a callback fired with the new `currentLayout()` whenever the keyboard layout changes,
+  * so layout-dependent bindings can be re-resolved. Pass `null` to remove it.
+  *
+  * The callback does not keep the process alive. Only Windows reports layout changes, noticed within
+  * 250 ms of the switch; elsewhere this does nothing and returns `false`.
+  *
+  * @returns `true` if the callback was installed.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setOnLayoutChange(layout => console.log('Layout changed to', layout));
+  * ```
+  */
+  setOnLayoutChange(onChange: ((layout: string | null) => void) | null): boolean
+  /**
+  * Sets a callback fired with the hotkey id and the reason when the backend fails on a registration after
+  * the call that requested it already returned, so the failure can't be reported by that call. Pass `null`
+  * to remove it.
+  *
+  * This happens when the backend thread handles a `register` its caller stopped waiting for, or can't restore
+  * a hotkey whose `unregister` was reported as failed. The callback does not keep the process alive. Only the
+  * Windows backend registers on its own thread; elsewhere this does nothing and returns `false`.
+  *
+  * @returns `true` if the callback was installed.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.onError((id, reason) => console.error(`Hotkey ${id} failed: ${reason}`));
+  * ```
+  */
+  onError(onError: ((id: number, reason: string) => void) | null): boolean
+  /**
+  * Reports the combinations the user attempts that aren't registered, e.g. for kiosk apps that log
+  * blocked shortcuts. `onAttempt` receives every key pressed while a modifier is held, once per press,
+  * unless the combination is registered.
+  *
+  * Only Windows supports it, through a low-level keyboard hook (`WH_KEYBOARD_LL`) that sees every
+  * keystroke of the session: security software may flag it, and each keystroke waits on the hook, so
+  * keep it off when not needed. Elsewhere this does nothing and returns `false`.
+  *
+  * @param {boolean} enabled - Whether to install the hook; `false` removes it.
+  * @param onAttempt - Receives the attempted combination. Required when `enabled`.
+  * @returns `true` if the hook was installed or removed.
+  *
+  * @example
+  * ```js
+  * import { hotk, descToAccelerator } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setWatchUnmapped(true, desc => console.warn('Blocked', descToAccelerator(desc)));
+  * ```
+  */
+  setWatchUnmapped(enabled: boolean, onAttempt?: ((desc: Desc) => void) | undefined | null): boolean
+  /**
+  * Starts appending every dispatched event to the file at `path`, one line per event
+  * (`<unix ms> <id> <event type> <mods+code>`), to debug hotkeys that fire unexpectedly.
+  *
+  * Lines are written from a background thread, so tracing never delays event delivery.
+  * Starting a new trace stops the previous one.
+  *
+  * @param {string} path - File to append to. It is created if it does not exist.
+  * @returns {TraceResult} `Ok`, or `Error` with the reason the file could not be opened.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const result = manager.startEventTrace('hotkeys.log');
+  * if (result.code !== 'Ok') console.error(result.error);
+  * ```
+  */
+  startEventTrace(path: string): TraceResult
+  /**
+  * Applies several settings at once. Every field set in `options` is applied while holding the manager lock,
+  * so no event or other call sees only part of them; unset fields keep their current value.
+  *
+  * @param {HotkOptions} options - Settings to apply.
+  *
+  * @example
+  * ```js
+  * import { hotk, QueuePolicy } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.configure({
+  *   emitLifecycle: true,
+  *   queueLimit: 64,
+  *   queuePolicy: QueuePolicy.DropOldest,
+  *   instanceName: 'settings-window',
+  * });
+  * ```
+  */
+  configure(options: HotkOptions): void
+  /**
+  * Returns the current value of every setting `configure` accepts, whether it was set through `configure` or
+  * the individual setters. Passing the result back to `configure` restores them.
+  *
+  * Disabled settings are reported with their disabling value: `0` for `lateEventGraceMs`, `releaseWatchdogMs`
+  * and `queueLimit`, and an empty string for `instanceName`. `ignoreOsRepeat` is `false` where unsupported.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * const saved = manager.currentConfig();
+  * manager.configure({ emitLifecycle: true });
+  * manager.configure(saved);
+  * ```
+  */
+  currentConfig(): HotkOptions
+  /**
+  * Names this instance in trace lines (`[name] <unix ms> <id> <event type> <mods+code>`), to tell apart the
+  * logs of several managers, e.g. across worker threads. Pass an empty string to remove the name.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.setInstanceName('settings-window');
+  * manager.enableConsoleTrace(true);
+  * ```
+  */
+  setInstanceName(name: string): void
+  /**
+  * Prints every dispatched event to stderr, in the same format as `startEventTrace`, to check whether a
+  * hotkey fires at all. Events are still delivered to the callbacks as usual. Off by default.
+  *
+  * Lines are printed from a background thread, so this never delays event delivery.
+  *
+  * @example
+  * ```js
+  * import { hotk } from '@hotk/core';
+  *
+  * const manager = hotk();
+  * manager.enableConsoleTrace(true);
+  * ```
+  */
+  enableConsoleTrace(enabled: boolean): void
+  /**
+  * Stops the event trace started with `startEventTrace`, once every pending line is written.
+  *
+  * @returns `true` if a trace was running.
+  */
+  stopEventTrace(): boolean
+  /**
+  * Stops listening for hotkey events.
+  *
+  * This is required to allow the Node.js process to exit when `init` was called without `unref: true`.
+  *
+  * Teardown happens in a fixed order: events stop being routed to JS, the `init` callback is aborted and
+  * released, then the per-hotkey (`registerIn`, `on`, `setOnKey`) and batch callbacks and every pending
+  * event are dropped. No callback fires afterwards, even for events that reach the manager late. Hotkeys
+  * stay registered, and `init` can be called again to listen anew, as many times as needed.
+  *
+  * The process-wide `global_hotkey` event handler is left installed: the crate keeps it in a `OnceCell`,
+  * so it can't be replaced or removed once set. It holds no JS callback, only the routing to the manager.
+  *
+  * Events fired but not yet delivered are dropped. With `flush`, they are first delivered to the `init`
+  * callback synchronously, in the order they were fired, so they are all observed before `destroy` returns.
+  * Events still waiting in the pre-init buffer (`init` never called) have no callback to go to and are
+  * dropped either way.
+  *
+  * @param {DestroyOptions} [options] - Optional teardown behaviour.
+  *
+  * @example
+  * ```js
+  * import { hotk, Mod, KeyCode } from '@hotk/core';
+  *
+  * // Get the singleton instance
+  * const manager = hotk();
+  *
+  * // Register Ctrl + A as a hotkey
+  * const result = manager.register([Mod.Control], KeyCode.KeyA);
+  *
+  * if (result.isOk()) {
+  *   console.log('Hotkey successfully registered');
+  * } else {
+  *   console.error('Failed to register hotkey');
+  * }
+  *
+  * // Start listening for hotkey events without unref
+  * manager.init((event) => {
+  *   console.log('Received event:', event);
+  * });
+  *
+  * // Stop listening after 3 seconds so the process can exit
+  * setTimeout(() => {
+  *   manager.destroy();
+  * }, 3000);
+  * ```
+  */
+  destroy(options?: DestroyOptions | undefined | null): void
+}
+/**
+ * A single registration made by `HotkManager.registerHandle`, to unregister it without keeping track of
+ * its combination.
+ *
+ * Dropping the handle doesn't unregister the hotkey, as it would then depend on when the garbage collector
+ * runs; it stays registered until `unregister` is called on the handle or on the manager. The handle itself
+ * holds no resource, so an unused one is simply collected.
+*/
+export declare class RegistrationHandle {
+  /**
+  * Id of the hotkey, as returned by `getHotkeyId`.
+  */
+  get id(): number
+  /**
+  * Whether the handle still holds its registration: `false` if registering failed or once `unregister`
+  * was called.
+  */
+  get registered(): boolean
+  /**
+  * Why registering failed, or `null` if it succeeded.
+  */
+  get error(): string | null
+  /**
+  * Removes the registration of this handle. Like `HotkManager.unregister`, a combination registered several
+  * times stays registered until every registration is removed.
+  *
+  * @returns The response of the unregistration; an error if the handle holds no registration.
+  */
+  unregister(): HotkReponse
 }
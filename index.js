@@ -310,16 +310,46 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { KeyCode, keyCodeKeys, modKeys, keyCodeToHuman, Mod, getHotkeyId, EventType, ResponseCode, HotkReponse, HotkManager, hotk } = nativeBinding
+const { KeyCode, keyCodeKeys, modKeys, keyCodeToHuman, isModifierKey, modToHuman, platformPrimaryMeta, modsToHuman, KeyCategory, keyCategory, Mod, descToAccelerator, formatAccelerator, toMenuAccelerator, parseHotkey, splitAccelerator, parseAccelerator, getHotkeyId, EventType, eventToDesc, eventToJson, QueuePolicy, BackendPriority, ErrorKind, Operation, ResponseCode, InitResult, RegistrationResult, WarningCode, HotkReponse, validateCombo, validateKeymap, hasInputPermission, requestInputPermission, validModifiersFor, combosConflict, capabilities, HotkManager, RegistrationHandle, hotk, hotkError } = nativeBinding
 
 module.exports.KeyCode = KeyCode
 module.exports.keyCodeKeys = keyCodeKeys
 module.exports.modKeys = modKeys
 module.exports.keyCodeToHuman = keyCodeToHuman
+module.exports.isModifierKey = isModifierKey
+module.exports.modToHuman = modToHuman
+module.exports.platformPrimaryMeta = platformPrimaryMeta
+module.exports.modsToHuman = modsToHuman
+module.exports.KeyCategory = KeyCategory
+module.exports.keyCategory = keyCategory
 module.exports.Mod = Mod
+module.exports.descToAccelerator = descToAccelerator
+module.exports.formatAccelerator = formatAccelerator
+module.exports.toMenuAccelerator = toMenuAccelerator
+module.exports.parseHotkey = parseHotkey
+module.exports.splitAccelerator = splitAccelerator
+module.exports.parseAccelerator = parseAccelerator
 module.exports.getHotkeyId = getHotkeyId
 module.exports.EventType = EventType
+module.exports.eventToDesc = eventToDesc
+module.exports.eventToJson = eventToJson
+module.exports.QueuePolicy = QueuePolicy
+module.exports.BackendPriority = BackendPriority
+module.exports.ErrorKind = ErrorKind
+module.exports.Operation = Operation
 module.exports.ResponseCode = ResponseCode
+module.exports.InitResult = InitResult
+module.exports.RegistrationResult = RegistrationResult
+module.exports.WarningCode = WarningCode
 module.exports.HotkReponse = HotkReponse
+module.exports.validateCombo = validateCombo
+module.exports.validateKeymap = validateKeymap
+module.exports.hasInputPermission = hasInputPermission
+module.exports.requestInputPermission = requestInputPermission
+module.exports.validModifiersFor = validModifiersFor
+module.exports.combosConflict = combosConflict
+module.exports.capabilities = capabilities
 module.exports.HotkManager = HotkManager
+module.exports.RegistrationHandle = RegistrationHandle
 module.exports.hotk = hotk
+module.exports.hotkError = hotkError
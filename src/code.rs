@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use global_hotkey::hotkey::{HotKey, Modifiers};
 use strum::IntoEnumIterator;
@@ -9,10 +10,14 @@ use strum_macros::{Display, EnumString};
 
 /**
  * Represents keyboard key codes used to define hotkeys.
- * Each variant corresponds to a specific physical key.
+ * Each variant corresponds to a specific physical key, named after its position on a US layout.
+ *
+ * Hotkeys are never resolved by character, nor re-resolved when the keyboard layout changes: a registered
+ * hotkey keeps firing after a layout switch. On Windows the backend registers the virtual key of the code,
+ * so on layouts that move letters (e.g. AZERTY) the key triggering `KeyA` is the one labeled A.
  */
 #[napi(string_enum)]
-#[derive(Debug, Display, EnumString, EnumIter)]
+#[derive(Debug, PartialEq, Eq, Hash, Display, EnumString, EnumIter)]
 pub enum KeyCode {
   Backquote,
   Backslash,
@@ -239,6 +244,24 @@ impl KeyCode {
   pub fn from_global_hotkeys(code: global_hotkey::hotkey::Code) -> Option<KeyCode> {
     code.to_string().parse().ok()
   }
+
+  pub fn is_modifier(&self) -> bool {
+    matches!(
+      self,
+      KeyCode::ControlLeft
+        | KeyCode::ControlRight
+        | KeyCode::ShiftLeft
+        | KeyCode::ShiftRight
+        | KeyCode::AltLeft
+        | KeyCode::AltRight
+        | KeyCode::MetaLeft
+        | KeyCode::MetaRight
+        | KeyCode::Fn
+        | KeyCode::CapsLock
+        | KeyCode::NumLock
+        | KeyCode::ScrollLock
+    )
+  }
 }
 
 /**
@@ -265,111 +288,524 @@ pub fn mod_keys() -> Vec<String> {
   Mod::iter().map(|e| e.to_string()).collect()
 }
 
+/**
+ * Labels of `key_code_to_human`, in `KeyCode` order. Keys without one (`Unidentified`) are left out.
+ */
+const HUMAN_KEYS: &[(KeyCode, &str)] = &[
+  (KeyCode::Backquote, "`"),
+  (KeyCode::Backslash, "\\"),
+  (KeyCode::BracketLeft, "["),
+  (KeyCode::BracketRight, "]"),
+  (KeyCode::Comma, ","),
+  (KeyCode::Digit0, "0"),
+  (KeyCode::Digit1, "1"),
+  (KeyCode::Digit2, "2"),
+  (KeyCode::Digit3, "3"),
+  (KeyCode::Digit4, "4"),
+  (KeyCode::Digit5, "5"),
+  (KeyCode::Digit6, "6"),
+  (KeyCode::Digit7, "7"),
+  (KeyCode::Digit8, "8"),
+  (KeyCode::Digit9, "9"),
+  (KeyCode::Equal, "="),
+  (KeyCode::IntlBackslash, "\\"),
+  (KeyCode::IntlRo, "ro"),
+  (KeyCode::IntlYen, "¥"),
+  (KeyCode::KeyA, "a"),
+  (KeyCode::KeyB, "b"),
+  (KeyCode::KeyC, "c"),
+  (KeyCode::KeyD, "d"),
+  (KeyCode::KeyE, "e"),
+  (KeyCode::KeyF, "f"),
+  (KeyCode::KeyG, "g"),
+  (KeyCode::KeyH, "h"),
+  (KeyCode::KeyI, "i"),
+  (KeyCode::KeyJ, "j"),
+  (KeyCode::KeyK, "k"),
+  (KeyCode::KeyL, "l"),
+  (KeyCode::KeyM, "m"),
+  (KeyCode::KeyN, "n"),
+  (KeyCode::KeyO, "o"),
+  (KeyCode::KeyP, "p"),
+  (KeyCode::KeyQ, "q"),
+  (KeyCode::KeyR, "r"),
+  (KeyCode::KeyS, "s"),
+  (KeyCode::KeyT, "t"),
+  (KeyCode::KeyU, "u"),
+  (KeyCode::KeyV, "v"),
+  (KeyCode::KeyW, "w"),
+  (KeyCode::KeyX, "x"),
+  (KeyCode::KeyY, "y"),
+  (KeyCode::KeyZ, "z"),
+  (KeyCode::Minus, "-"),
+  (KeyCode::Period, "."),
+  (KeyCode::Quote, "\""),
+  (KeyCode::Semicolon, ";"),
+  (KeyCode::Slash, "/"),
+  (KeyCode::AltLeft, "Left Alt"),
+  (KeyCode::AltRight, "Right Alt"),
+  (KeyCode::Backspace, "Backspace"),
+  (KeyCode::CapsLock, "Caps Lock"),
+  (KeyCode::ContextMenu, "Menu"),
+  (KeyCode::ControlLeft, "Left Ctrl"),
+  (KeyCode::ControlRight, "Right Ctrl"),
+  (KeyCode::Enter, "Enter"),
+  (KeyCode::MetaLeft, "Left Meta"),
+  (KeyCode::MetaRight, "Right Meta"),
+  (KeyCode::ShiftLeft, "Left Shift"),
+  (KeyCode::ShiftRight, "Right Shift"),
+  (KeyCode::Space, "Space"),
+  (KeyCode::Tab, "Tab"),
+  (KeyCode::Convert, "Convert"),
+  (KeyCode::KanaMode, "Kana"),
+  (KeyCode::Lang1, "Lang 1"),
+  (KeyCode::Lang2, "Lang 2"),
+  (KeyCode::Lang3, "Lang 3"),
+  (KeyCode::Lang4, "Lang 4"),
+  (KeyCode::Lang5, "Lang 5"),
+  (KeyCode::NonConvert, "Non Convert"),
+  (KeyCode::Delete, "Delete"),
+  (KeyCode::End, "End"),
+  (KeyCode::Help, "Help"),
+  (KeyCode::Home, "Home"),
+  (KeyCode::Insert, "Insert"),
+  (KeyCode::PageDown, "Page Down"),
+  (KeyCode::PageUp, "Page Up"),
+  (KeyCode::ArrowDown, "↓"),
+  (KeyCode::ArrowLeft, "←"),
+  (KeyCode::ArrowRight, "→"),
+  (KeyCode::ArrowUp, "↑"),
+  (KeyCode::NumLock, "Num Lock"),
+  (KeyCode::Numpad0, "Num 0"),
+  (KeyCode::Numpad1, "Num 1"),
+  (KeyCode::Numpad2, "Num 2"),
+  (KeyCode::Numpad3, "Num 3"),
+  (KeyCode::Numpad4, "Num 4"),
+  (KeyCode::Numpad5, "Num 5"),
+  (KeyCode::Numpad6, "Num 6"),
+  (KeyCode::Numpad7, "Num 7"),
+  (KeyCode::Numpad8, "Num 8"),
+  (KeyCode::Numpad9, "Num 9"),
+  (KeyCode::NumpadAdd, "Num +"),
+  (KeyCode::NumpadBackspace, "Num Backspace"),
+  (KeyCode::NumpadClear, "Num Clear"),
+  (KeyCode::NumpadClearEntry, "Num Clear Entry"),
+  (KeyCode::NumpadComma, "Num ,"),
+  (KeyCode::NumpadDecimal, "Num ."),
+  (KeyCode::NumpadDivide, "Num /"),
+  (KeyCode::NumpadEnter, "Num Enter"),
+  (KeyCode::NumpadEqual, "Num ="),
+  (KeyCode::NumpadHash, "Num #"),
+  (KeyCode::NumpadMemoryAdd, "Num M+"),
+  (KeyCode::NumpadMemoryClear, "Num MC"),
+  (KeyCode::NumpadMemoryRecall, "Num MR"),
+  (KeyCode::NumpadMemoryStore, "Num MS"),
+  (KeyCode::NumpadMemorySubtract, "Num M-"),
+  (KeyCode::NumpadMultiply, "Num *"),
+  (KeyCode::NumpadParenLeft, "Num ("),
+  (KeyCode::NumpadParenRight, "Num )"),
+  (KeyCode::NumpadStar, "Num *"),
+  (KeyCode::NumpadSubtract, "Num -"),
+  (KeyCode::Escape, "Esc"),
+  (KeyCode::Fn, "Fn"),
+  (KeyCode::FnLock, "Fn Lock"),
+  (KeyCode::PrintScreen, "Print Screen"),
+  (KeyCode::ScrollLock, "Scroll Lock"),
+  (KeyCode::Pause, "Pause"),
+  (KeyCode::BrowserBack, "Browser Back"),
+  (KeyCode::BrowserFavorites, "Browser Favorites"),
+  (KeyCode::BrowserForward, "Browser Forward"),
+  (KeyCode::BrowserHome, "Browser Home"),
+  (KeyCode::BrowserRefresh, "Browser Refresh"),
+  (KeyCode::BrowserSearch, "Browser Search"),
+  (KeyCode::BrowserStop, "Browser Stop"),
+  (KeyCode::Eject, "Eject"),
+  (KeyCode::LaunchApp1, "Launch App 1"),
+  (KeyCode::LaunchApp2, "Launch App 2"),
+  (KeyCode::LaunchMail, "Mail"),
+  (KeyCode::MediaPlayPause, "Play/Pause"),
+  (KeyCode::MediaSelect, "Media Select"),
+  (KeyCode::MediaStop, "Stop"),
+  (KeyCode::MediaTrackNext, "Next Track"),
+  (KeyCode::MediaTrackPrevious, "Previous Track"),
+  (KeyCode::Power, "Power"),
+  (KeyCode::Sleep, "Sleep"),
+  (KeyCode::AudioVolumeDown, "Volume Down"),
+  (KeyCode::AudioVolumeMute, "Mute"),
+  (KeyCode::AudioVolumeUp, "Volume Up"),
+  (KeyCode::WakeUp, "Wake Up"),
+  (KeyCode::Hyper, "Hyper"),
+  (KeyCode::Super, "Super"),
+  (KeyCode::Turbo, "Turbo"),
+  (KeyCode::Abort, "Abort"),
+  (KeyCode::Resume, "Resume"),
+  (KeyCode::Suspend, "Suspend"),
+  (KeyCode::Again, "Again"),
+  (KeyCode::Copy, "Copy"),
+  (KeyCode::Cut, "Cut"),
+  (KeyCode::Find, "Find"),
+  (KeyCode::Open, "Open"),
+  (KeyCode::Paste, "Paste"),
+  (KeyCode::Props, "Props"),
+  (KeyCode::Select, "Select"),
+  (KeyCode::Undo, "Undo"),
+  (KeyCode::Hiragana, "Hiragana"),
+  (KeyCode::Katakana, "Katakana"),
+  (KeyCode::F1, "f1"),
+  (KeyCode::F2, "f2"),
+  (KeyCode::F3, "f3"),
+  (KeyCode::F4, "f4"),
+  (KeyCode::F5, "f5"),
+  (KeyCode::F6, "f6"),
+  (KeyCode::F7, "f7"),
+  (KeyCode::F8, "f8"),
+  (KeyCode::F9, "f9"),
+  (KeyCode::F10, "f10"),
+  (KeyCode::F11, "f11"),
+  (KeyCode::F12, "f12"),
+  (KeyCode::F13, "f13"),
+  (KeyCode::F14, "f14"),
+  (KeyCode::F15, "f15"),
+  (KeyCode::F16, "f16"),
+  (KeyCode::F17, "f17"),
+  (KeyCode::F18, "f18"),
+  (KeyCode::F19, "f19"),
+  (KeyCode::F20, "f20"),
+  (KeyCode::F21, "f21"),
+  (KeyCode::F22, "f22"),
+  (KeyCode::F23, "f23"),
+  (KeyCode::F24, "f24"),
+  (KeyCode::F25, "f25"),
+  (KeyCode::F26, "f26"),
+  (KeyCode::F27, "f27"),
+  (KeyCode::F28, "f28"),
+  (KeyCode::F29, "f29"),
+  (KeyCode::F30, "f30"),
+  (KeyCode::F31, "f31"),
+  (KeyCode::F32, "f32"),
+  (KeyCode::F33, "f33"),
+  (KeyCode::F34, "f34"),
+  (KeyCode::F35, "f35"),
+  (KeyCode::BrightnessDown, "Brightness Down"),
+  (KeyCode::BrightnessUp, "Brightness Up"),
+  (KeyCode::DisplayToggleIntExt, "Display Toggle"),
+  (KeyCode::KeyboardLayoutSelect, "Layout Select"),
+  (KeyCode::LaunchAssistant, "Assistant"),
+  (KeyCode::LaunchControlPanel, "Control Panel"),
+  (KeyCode::LaunchScreenSaver, "Screen Saver"),
+  (KeyCode::MailForward, "Mail Forward"),
+  (KeyCode::MailReply, "Mail Reply"),
+  (KeyCode::MailSend, "Mail Send"),
+  (KeyCode::MediaFastForward, "Fast Forward"),
+  (KeyCode::MediaPause, "Pause Media"),
+  (KeyCode::MediaPlay, "Play"),
+  (KeyCode::MediaRecord, "Record"),
+  (KeyCode::MediaRewind, "Rewind"),
+  (KeyCode::MicrophoneMuteToggle, "Microphone Mute"),
+  (KeyCode::PrivacyScreenToggle, "Privacy Screen"),
+  (KeyCode::SelectTask, "Select Task"),
+  (KeyCode::ShowAllWindows, "Show All Windows"),
+  (KeyCode::ZoomToggle, "Zoom"),
+];
+
 /**
  * Converts a KeyCode enum variant to a human-readable string representation.
  *
- * This is useful for displaying key codes in a user-friendly format.
+ * This is useful for displaying key codes in a user-friendly format: characters for the typing area
+ * (`"a"`, `"/"`), arrows for arrow keys (`"↑"`) and short names for the rest (`"Page Up"`, `"Num 0"`, `"Esc"`).
  *
  * @param {KeyCode} key_code - The key code to convert.
- * @returns {string | null} A human-readable string representing the key code, or null if no mapping exists.
+ * @returns {string | null} A human-readable string representing the key code, or null for `Unidentified`.
+ *
+ * @example
+ * ```js
+ * import { keyCodeToHuman, KeyCode } from '@hotk/core';
+ *
+ * keyCodeToHuman(KeyCode.ArrowUp); // "↑"
+ * keyCodeToHuman(KeyCode.Numpad0); // "Num 0"
+ * ```
  */
 #[napi]
 pub fn key_code_to_human(key_code: KeyCode) -> Option<String> {
-  let c = match key_code {
-    KeyCode::Digit0 => "0",
-    KeyCode::Digit1 => "1",
-    KeyCode::Digit2 => "2",
-    KeyCode::Digit3 => "3",
-    KeyCode::Digit4 => "4",
-    KeyCode::Digit5 => "5",
-    KeyCode::Digit6 => "6",
-    KeyCode::Digit7 => "7",
-    KeyCode::Digit8 => "8",
-    KeyCode::Digit9 => "9",
-    KeyCode::KeyA => "a",
-    KeyCode::KeyB => "b",
-    KeyCode::KeyC => "c",
-    KeyCode::KeyD => "d",
-    KeyCode::KeyE => "e",
-    KeyCode::KeyF => "f",
-    KeyCode::KeyG => "g",
-    KeyCode::KeyH => "h",
-    KeyCode::KeyI => "i",
-    KeyCode::KeyJ => "j",
-    KeyCode::KeyK => "k",
-    KeyCode::KeyL => "l",
-    KeyCode::KeyM => "m",
-    KeyCode::KeyN => "n",
-    KeyCode::KeyO => "o",
-    KeyCode::KeyP => "p",
-    KeyCode::KeyQ => "q",
-    KeyCode::KeyR => "r",
-    KeyCode::KeyS => "s",
-    KeyCode::KeyT => "t",
-    KeyCode::KeyU => "u",
-    KeyCode::KeyV => "v",
-    KeyCode::KeyW => "w",
-    KeyCode::KeyX => "x",
-    KeyCode::KeyY => "y",
-    KeyCode::KeyZ => "z",
-    KeyCode::Backquote => "`",
-    KeyCode::Backslash => "\\",
-    KeyCode::BracketLeft => "[",
-    KeyCode::BracketRight => "]",
-    KeyCode::Comma => ",",
-    KeyCode::Equal => "=",
-    KeyCode::Minus => "-",
-    KeyCode::Period => ".",
-    KeyCode::Quote => "\"",
-    KeyCode::Semicolon => ";",
-    KeyCode::Slash => "/",
-    KeyCode::F1 => "f1",
-    KeyCode::F2 => "f2",
-    KeyCode::F3 => "f3",
-    KeyCode::F4 => "f4",
-    KeyCode::F5 => "f5",
-    KeyCode::F6 => "f6",
-    KeyCode::F7 => "f7",
-    KeyCode::F8 => "f8",
-    KeyCode::F9 => "f9",
-    KeyCode::F10 => "f10",
-    KeyCode::F11 => "f11",
-    KeyCode::F12 => "f12",
-    KeyCode::F13 => "f13",
-    KeyCode::F14 => "f14",
-    KeyCode::F15 => "f15",
-    KeyCode::F16 => "f16",
-    KeyCode::F17 => "f17",
-    KeyCode::F18 => "f18",
-    KeyCode::F19 => "f19",
-    KeyCode::F20 => "f20",
-    KeyCode::F21 => "f21",
-    KeyCode::F22 => "f22",
-    KeyCode::F23 => "f23",
-    KeyCode::F24 => "f24",
-    KeyCode::F25 => "f25",
-    KeyCode::F26 => "f26",
-    KeyCode::F27 => "f27",
-    KeyCode::F28 => "f28",
-    KeyCode::F29 => "f29",
-    KeyCode::F30 => "f30",
-    KeyCode::F31 => "f31",
-    KeyCode::F32 => "f32",
-    KeyCode::F33 => "f33",
-    KeyCode::F34 => "f34",
-    KeyCode::F35 => "f35",
-
-    _ => return None,
+  HUMAN_KEYS
+    .iter()
+    .find(|(code, _)| *code == key_code)
+    .map(|(_, label)| label.to_string())
+}
+
+/**
+ * Checks whether a key code is a modifier key (e.g. `ControlLeft`, `ShiftRight`, `Fn`, `CapsLock`).
+ *
+ * Modifier keys can't be the main key of a hotkey; use it to prevent selecting one in a UI.
+ *
+ * @param {KeyCode} code - The key code to check.
+ * @returns {boolean} `true` for modifier keys.
+ *
+ * @example
+ * ```js
+ * import { isModifierKey, KeyCode } from '@hotk/core';
+ *
+ * isModifierKey(KeyCode.ShiftLeft); // true
+ * isModifierKey(KeyCode.KeyA); // false
+ * ```
+ */
+#[napi]
+pub fn is_modifier_key(code: KeyCode) -> bool {
+  code.is_modifier()
+}
+
+/**
+ * Platform display name of a modifier: symbols on macOS (`⌘`), names elsewhere (`Ctrl`).
+ */
+pub fn mod_label(modifier: Mod) -> &'static str {
+  #[cfg(target_os = "macos")]
+  let name = match modifier {
+    Mod::Control => "⌃",
+    Mod::Alt | Mod::AltGraph => "⌥",
+    Mod::Shift => "⇧",
+    Mod::Meta | Mod::Super | Mod::CommandOrControl => "⌘",
+    Mod::CapsLock => "⇪",
+    Mod::Fn => "fn",
+    Mod::FnLock => "fn lock",
+    Mod::NumLock => "⇭",
+    Mod::ScrollLock => "scroll lock",
+    Mod::Symbol => "symbol",
+    Mod::SymbolLock => "symbol lock",
+    Mod::Hyper => "✦",
+  };
+
+  #[cfg(not(target_os = "macos"))]
+  let name = match modifier {
+    Mod::Control | Mod::CommandOrControl => "Ctrl",
+    Mod::Alt => "Alt",
+    Mod::AltGraph => "AltGr",
+    Mod::Shift => "Shift",
+    #[cfg(target_os = "windows")]
+    Mod::Meta | Mod::Super => "Win",
+    #[cfg(not(target_os = "windows"))]
+    Mod::Meta | Mod::Super => "Super",
+    Mod::CapsLock => "Caps Lock",
+    Mod::Fn => "Fn",
+    Mod::FnLock => "Fn Lock",
+    Mod::NumLock => "Num Lock",
+    Mod::ScrollLock => "Scroll Lock",
+    Mod::Symbol => "Symbol",
+    Mod::SymbolLock => "Symbol Lock",
+    Mod::Hyper => "Hyper",
   };
 
-  Some(c.to_string())
+  name
+}
+
+/**
+ * Converts a modifier to its display label using the platform convention.
+ *
+ * macOS uses the symbols of its menus (`⌘`, `⌥`, `⇧`, `⌃`); Windows and Linux use names (`Ctrl`, `Alt`),
+ * with the OS key read as `Win` on Windows and `Super` on Linux. `CommandOrControl` is labelled as the
+ * modifier it resolves to.
+ *
+ * @param {Mod} modifier - The modifier to convert.
+ * @returns {string} The label, e.g. `"⌘"` for `Meta` on macOS and `"Win"` on Windows.
+ *
+ * @example
+ * ```js
+ * import { modToHuman, Mod } from '@hotk/core';
+ *
+ * const chips = [Mod.Control, Mod.Shift].map(modToHuman); // ['Ctrl', 'Shift'] (['⌃', '⇧'] on macOS)
+ * ```
+ */
+#[napi]
+pub fn mod_to_human(modifier: Mod) -> String {
+  mod_label(modifier).to_string()
+}
+
+/**
+ * Position of a modifier in a displayed combination, following the platform order (Ctrl, Alt, Shift, Cmd/Win).
+ */
+pub fn mod_order(modifier: Mod) -> u8 {
+  match modifier {
+    Mod::Control => 0,
+    Mod::CommandOrControl => 1,
+    Mod::Alt => 2,
+    Mod::AltGraph => 3,
+    Mod::Shift => 4,
+    Mod::Meta => 5,
+    Mod::Super => 6,
+    Mod::Hyper => 7,
+    Mod::Fn => 8,
+    Mod::FnLock => 9,
+    Mod::CapsLock => 10,
+    Mod::NumLock => 11,
+    Mod::ScrollLock => 12,
+    Mod::Symbol => 13,
+    Mod::SymbolLock => 14,
+  }
+}
+
+/**
+ * Returns the conventional name of the OS key on this platform: `Meta` (Command) on macOS, `Super`
+ * (the Windows key) elsewhere.
+ *
+ * Both register the same hotkey, as `Meta` is folded into `Super`; this is the one to show and store so
+ * bindings read naturally. `parseHotkey` and `parseAccelerator` resolve `Win`, `Cmd` and `Super` to it.
+ *
+ * @example
+ * ```js
+ * import { hotk, platformPrimaryMeta, KeyCode } from '@hotk/core';
+ *
+ * hotk().register([platformPrimaryMeta()], KeyCode.Space); // Command+Space on macOS, Win+Space elsewhere
+ * ```
+ */
+#[napi]
+pub fn platform_primary_meta() -> Mod {
+  if cfg!(target_os = "macos") {
+    Mod::Meta
+  } else {
+    Mod::Super
+  }
+}
+
+/**
+ * Converts a list of modifiers to a display string using the platform convention.
+ *
+ * Modifiers are put in canonical order and duplicates are dropped. On macOS the symbols are joined
+ * without a separator (`⌃⇧⌘`); elsewhere the names are joined with `+` (`Ctrl+Shift+Win`).
+ *
+ * @param {Mod[]} mods - The modifiers to convert.
+ * @returns {string} The display string, empty if `mods` is empty.
+ *
+ * @example
+ * ```js
+ * import { modsToHuman, Mod } from '@hotk/core';
+ *
+ * modsToHuman([Mod.Shift, Mod.Control]); // "Ctrl+Shift" ("⌃⇧" on macOS)
+ * ```
+ */
+#[napi]
+pub fn mods_to_human(mods: Vec<Mod>) -> String {
+  let mut mods: Vec<Mod> = mods.iter().map(|m| m.resolve()).collect();
+  mods.sort_by_key(|m| mod_order(*m));
+
+  let mut names: Vec<&str> = mods.into_iter().map(mod_label).collect();
+  names.dedup();
+
+  let separator = if cfg!(target_os = "macos") { "" } else { "+" };
+  names.join(separator)
+}
+
+/**
+ * Broad group a key code belongs to.
+ *
+ * - `Letter`, `Digit`, `Punctuation`: Keys of the main typing area.
+ * - `Modifier`: Keys that act as modifiers when held (e.g. `ShiftLeft`, `CapsLock`).
+ * - `Editing`: Whitespace and editing keys (e.g. `Enter`, `Delete`, `Copy`).
+ * - `Navigation`: Arrows, `Home`, `End` and paging keys.
+ * - `Function`: `F1` to `F35`.
+ * - `Numpad`: Keys of the numeric keypad.
+ * - `Media`: Playback and volume keys.
+ * - `Application`: Browser, mail and application launch keys.
+ * - `System`: Power, display and other system keys.
+ * - `Ime`: Input method and language keys (e.g. `Convert`, `Lang1`, `Hiragana`), rarely registerable.
+ * - `Unknown`: Keys that could not be identified.
+ */
+#[napi(string_enum)]
+#[derive(Debug, PartialEq)]
+pub enum KeyCategory {
+  Letter,
+  Digit,
+  Punctuation,
+  Modifier,
+  Editing,
+  Navigation,
+  Function,
+  Numpad,
+  Media,
+  Application,
+  System,
+  Ime,
+  Unknown,
+}
+
+impl KeyCode {
+  pub fn category(&self) -> KeyCategory {
+    use KeyCode::*;
+
+    match self {
+      KeyA | KeyB | KeyC | KeyD | KeyE | KeyF | KeyG | KeyH | KeyI | KeyJ | KeyK | KeyL | KeyM
+      | KeyN | KeyO | KeyP | KeyQ | KeyR | KeyS | KeyT | KeyU | KeyV | KeyW | KeyX | KeyY
+      | KeyZ => KeyCategory::Letter,
+      Digit0 | Digit1 | Digit2 | Digit3 | Digit4 | Digit5 | Digit6 | Digit7 | Digit8 | Digit9 => {
+        KeyCategory::Digit
+      }
+      Backquote | Backslash | BracketLeft | BracketRight | Comma | Equal | IntlBackslash
+      | IntlRo | IntlYen | Minus | Period | Quote | Semicolon | Slash => KeyCategory::Punctuation,
+      AltLeft | AltRight | ControlLeft | ControlRight | MetaLeft | MetaRight | ShiftLeft
+      | ShiftRight | Fn | CapsLock | NumLock | ScrollLock => KeyCategory::Modifier,
+      Backspace | Enter | Space | Tab | Delete | Insert | ContextMenu | Help | Again | Copy
+      | Cut | Find | Open | Paste | Props | Select | Undo => KeyCategory::Editing,
+      End | Home | PageDown | PageUp | ArrowDown | ArrowLeft | ArrowRight | ArrowUp => {
+        KeyCategory::Navigation
+      }
+      F1 | F2 | F3 | F4 | F5 | F6 | F7 | F8 | F9 | F10 | F11 | F12 | F13 | F14 | F15 | F16
+      | F17 | F18 | F19 | F20 | F21 | F22 | F23 | F24 | F25 | F26 | F27 | F28 | F29 | F30 | F31
+      | F32 | F33 | F34 | F35 => KeyCategory::Function,
+      Numpad0 | Numpad1 | Numpad2 | Numpad3 | Numpad4 | Numpad5 | Numpad6 | Numpad7 | Numpad8
+      | Numpad9 | NumpadAdd | NumpadBackspace | NumpadClear | NumpadClearEntry | NumpadComma
+      | NumpadDecimal | NumpadDivide | NumpadEnter | NumpadEqual | NumpadHash | NumpadMemoryAdd
+      | NumpadMemoryClear | NumpadMemoryRecall | NumpadMemoryStore | NumpadMemorySubtract
+      | NumpadMultiply | NumpadParenLeft | NumpadParenRight | NumpadStar | NumpadSubtract => {
+        KeyCategory::Numpad
+      }
+      MediaPlayPause | MediaSelect | MediaStop | MediaTrackNext | MediaTrackPrevious
+      | MediaFastForward | MediaPause | MediaPlay | MediaRecord | MediaRewind | AudioVolumeDown
+      | AudioVolumeMute | AudioVolumeUp | MicrophoneMuteToggle | Eject => KeyCategory::Media,
+      BrowserBack | BrowserFavorites | BrowserForward | BrowserHome | BrowserRefresh
+      | BrowserSearch | BrowserStop | LaunchApp1 | LaunchApp2 | LaunchMail | LaunchAssistant
+      | LaunchControlPanel | LaunchScreenSaver | MailForward | MailReply | MailSend => {
+        KeyCategory::Application
+      }
+      Escape | FnLock | PrintScreen | Pause | Power | Sleep | WakeUp | Hyper | Super | Turbo
+      | Abort | Resume | Suspend | BrightnessDown | BrightnessUp | DisplayToggleIntExt
+      | KeyboardLayoutSelect | PrivacyScreenToggle | SelectTask | ShowAllWindows | ZoomToggle => {
+        KeyCategory::System
+      }
+      Convert | NonConvert | KanaMode | Lang1 | Lang2 | Lang3 | Lang4 | Lang5 | Hiragana
+      | Katakana => KeyCategory::Ime,
+      Unidentified => KeyCategory::Unknown,
+    }
+  }
+}
+
+/**
+ * Returns the category a key code belongs to.
+ *
+ * @param {KeyCode} code - The key code to classify.
+ * @returns {KeyCategory} The category of the key.
+ *
+ * @example
+ * ```js
+ * import { keyCategory, KeyCategory, KeyCode } from '@hotk/core';
+ *
+ * keyCategory(KeyCode.Lang1) === KeyCategory.Ime; // true
+ * ```
+ */
+#[napi]
+pub fn key_category(code: KeyCode) -> KeyCategory {
+  code.category()
 }
 
 /**
  * Modifier keys used in hotkey combinations.
+ *
+ * `CommandOrControl` is a portable pseudo-modifier: it stays as is in descriptors and accelerators,
+ * and only resolves to `Meta` on macOS and `Control` elsewhere when the hotkey is registered.
+ *
+ * `Meta` and `Super` are distinct upstream flags, but every backend folds `Meta` into `Super`: both are
+ * the OS key (Command on macOS, Windows elsewhere) and register the same hotkey. See `platformPrimaryMeta`
+ * for the one to use.
  */
 #[napi(string_enum)]
-#[derive(Debug, Display, EnumString, EnumIter)]
+#[derive(Debug, PartialEq, Display, EnumString, EnumIter)]
 pub enum Mod {
   Control,
   Alt,
@@ -385,6 +821,7 @@ pub enum Mod {
   Hyper,
   Shift,
   Super,
+  CommandOrControl,
 }
 
 impl Mod {
@@ -404,6 +841,18 @@ impl Mod {
       Mod::Symbol => Modifiers::SYMBOL,
       Mod::SymbolLock => Modifiers::SYMBOL_LOCK,
       Mod::Hyper => Modifiers::HYPER,
+      Mod::CommandOrControl => self.resolve().global_hotkeys(),
+    }
+  }
+
+  /**
+   * The concrete modifier on this platform: `CommandOrControl` becomes `Meta` on macOS and `Control` elsewhere.
+   */
+  pub fn resolve(&self) -> Mod {
+    match self {
+      Mod::CommandOrControl if cfg!(target_os = "macos") => Mod::Meta,
+      Mod::CommandOrControl => Mod::Control,
+      other => *other,
     }
   }
 
@@ -446,20 +895,376 @@ impl Desc {
   }
 }
 
+impl Desc {
+  /**
+   * Canonical accelerator of the combination: modifiers in fixed order, deduplicated, then the key code,
+   * joined with `+` (e.g. `Control+Shift+KeyK`).
+   */
+  pub fn to_accelerator(&self) -> String {
+    let mut mods = self.mods.clone();
+    mods.sort_by_key(|m| mod_order(*m));
+
+    let mut tokens: Vec<String> = mods.iter().map(|m| m.to_string()).collect();
+    tokens.dedup();
+    tokens.push(self.code.to_string());
+    tokens.join("+")
+  }
+
+  /**
+   * Parses an accelerator in the form produced by `to_accelerator`, reading `Win`, `Cmd` and `Super` as
+   * the OS key of this platform.
+   */
+  pub fn from_accelerator(accelerator: &str) -> Option<Self> {
+    let mut tokens: Vec<&str> = accelerator.split('+').map(str::trim).collect();
+    let code = KeyCode::from_str(tokens.pop()?).ok()?;
+    let mods = tokens
+      .into_iter()
+      .map(|token| match token {
+        "Win" | "Cmd" | "Super" => Some(platform_primary_meta()),
+        _ => Mod::from_str(token).ok(),
+      })
+      .collect::<Option<Vec<_>>>()?;
+
+    Some(Self::new(code, mods))
+  }
+
+  /**
+   * The upstream `global_hotkey` hotkey the combination registers as.
+   */
+  pub fn to_hotkey(&self) -> HotKey {
+    let mods = self
+      .mods
+      .iter()
+      .map(|m| m.global_hotkeys())
+      .fold(Modifiers::empty(), |acc, m| acc | m);
+    HotKey::new(Some(mods), self.code.global_hotkeys())
+  }
+
+  /**
+   * TypeScript object literal of the combination, referencing the `Mod` and `KeyCode` enums
+   * (e.g. `{ mods: [Mod.Control, Mod.Shift], code: KeyCode.KeyK }`).
+   */
+  pub fn to_ts_literal(&self) -> String {
+    let mut mods = self.mods.clone();
+    mods.sort_by_key(|m| mod_order(*m));
+
+    let mut tokens: Vec<String> = mods.iter().map(|m| format!("Mod.{}", m)).collect();
+    tokens.dedup();
+    format!(
+      "{{ mods: [{}], code: KeyCode.{} }}",
+      tokens.join(", "),
+      self.code
+    )
+  }
+}
+
+/**
+ * Turns `name` into a valid TypeScript identifier, replacing every other character with `_`.
+ */
+pub fn ts_identifier(name: &str) -> String {
+  let mut identifier: String = name
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect();
+  if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+    identifier.insert(0, '_');
+  }
+  identifier
+}
+
+/**
+ * Converts a hotkey descriptor to its canonical accelerator string, e.g. `Control+Shift+KeyK`.
+ *
+ * Modifiers are always emitted in the same order and without duplicates, so equal combinations
+ * give equal strings. The result can be parsed back with `parseHotkey`.
+ *
+ * @param {Desc} desc - The combination to convert.
+ * @returns {string} The accelerator string.
+ *
+ * @example
+ * ```js
+ * import { descToAccelerator, KeyCode, Mod } from '@hotk/core';
+ *
+ * descToAccelerator({ mods: [Mod.Shift, Mod.Control], code: KeyCode.KeyK }); // "Control+Shift+KeyK"
+ * ```
+ */
+#[napi]
+pub fn desc_to_accelerator(desc: Desc) -> String {
+  desc.to_accelerator()
+}
+
+/**
+ * Formats a hotkey descriptor as a stable accelerator string, e.g. to key a config map by binding.
+ *
+ * Same output as `descToAccelerator`: modifiers in a fixed order (Control, Alt, Shift, Meta, …) whatever
+ * the input order, so equal bindings always give equal strings. The result parses back with
+ * `parseAccelerator`.
+ *
+ * @param {Desc} desc - The combination to format.
+ * @returns {string} The accelerator string.
+ *
+ * @example
+ * ```js
+ * import { formatAccelerator, KeyCode, Mod } from '@hotk/core';
+ *
+ * formatAccelerator({ mods: [Mod.Shift, Mod.Control], code: KeyCode.KeyK }); // "Control+Shift+KeyK"
+ * ```
+ */
+#[napi]
+pub fn format_accelerator(desc: Desc) -> String {
+  desc_to_accelerator(desc)
+}
+
+/**
+ * Key names of the Electron menu accelerator dialect, for the keys other than letters and digits that have one.
+ */
+const MENU_KEYS: &[(KeyCode, &str)] = &[
+  (KeyCode::Backquote, "`"),
+  (KeyCode::Backslash, "\\"),
+  (KeyCode::BracketLeft, "["),
+  (KeyCode::BracketRight, "]"),
+  (KeyCode::Comma, ","),
+  (KeyCode::Equal, "="),
+  (KeyCode::Minus, "-"),
+  (KeyCode::Period, "."),
+  (KeyCode::Quote, "'"),
+  (KeyCode::Semicolon, ";"),
+  (KeyCode::Slash, "/"),
+  (KeyCode::Backspace, "Backspace"),
+  (KeyCode::CapsLock, "Capslock"),
+  (KeyCode::Enter, "Enter"),
+  (KeyCode::Space, "Space"),
+  (KeyCode::Tab, "Tab"),
+  (KeyCode::Delete, "Delete"),
+  (KeyCode::End, "End"),
+  (KeyCode::Home, "Home"),
+  (KeyCode::Insert, "Insert"),
+  (KeyCode::PageDown, "PageDown"),
+  (KeyCode::PageUp, "PageUp"),
+  (KeyCode::ArrowDown, "Down"),
+  (KeyCode::ArrowLeft, "Left"),
+  (KeyCode::ArrowRight, "Right"),
+  (KeyCode::ArrowUp, "Up"),
+  (KeyCode::NumLock, "Numlock"),
+  (KeyCode::Numpad0, "num0"),
+  (KeyCode::Numpad1, "num1"),
+  (KeyCode::Numpad2, "num2"),
+  (KeyCode::Numpad3, "num3"),
+  (KeyCode::Numpad4, "num4"),
+  (KeyCode::Numpad5, "num5"),
+  (KeyCode::Numpad6, "num6"),
+  (KeyCode::Numpad7, "num7"),
+  (KeyCode::Numpad8, "num8"),
+  (KeyCode::Numpad9, "num9"),
+  (KeyCode::NumpadAdd, "numadd"),
+  (KeyCode::NumpadDecimal, "numdec"),
+  (KeyCode::NumpadDivide, "numdiv"),
+  (KeyCode::NumpadMultiply, "nummult"),
+  (KeyCode::NumpadSubtract, "numsub"),
+  (KeyCode::Escape, "Escape"),
+  (KeyCode::F1, "F1"),
+  (KeyCode::F2, "F2"),
+  (KeyCode::F3, "F3"),
+  (KeyCode::F4, "F4"),
+  (KeyCode::F5, "F5"),
+  (KeyCode::F6, "F6"),
+  (KeyCode::F7, "F7"),
+  (KeyCode::F8, "F8"),
+  (KeyCode::F9, "F9"),
+  (KeyCode::F10, "F10"),
+  (KeyCode::F11, "F11"),
+  (KeyCode::F12, "F12"),
+  (KeyCode::F13, "F13"),
+  (KeyCode::F14, "F14"),
+  (KeyCode::F15, "F15"),
+  (KeyCode::F16, "F16"),
+  (KeyCode::F17, "F17"),
+  (KeyCode::F18, "F18"),
+  (KeyCode::F19, "F19"),
+  (KeyCode::F20, "F20"),
+  (KeyCode::F21, "F21"),
+  (KeyCode::F22, "F22"),
+  (KeyCode::F23, "F23"),
+  (KeyCode::F24, "F24"),
+  (KeyCode::PrintScreen, "PrintScreen"),
+  (KeyCode::ScrollLock, "Scrolllock"),
+  (KeyCode::MediaPlayPause, "MediaPlayPause"),
+  (KeyCode::MediaStop, "MediaStop"),
+  (KeyCode::MediaTrackNext, "MediaNextTrack"),
+  (KeyCode::MediaTrackPrevious, "MediaPreviousTrack"),
+  (KeyCode::AudioVolumeDown, "VolumeDown"),
+  (KeyCode::AudioVolumeMute, "VolumeMute"),
+  (KeyCode::AudioVolumeUp, "VolumeUp"),
+];
+
+// Order of the modifiers in a menu accelerator.
+const MENU_MOD_ORDER: &[&str] = &["CmdOrCtrl", "Ctrl", "Alt", "AltGr", "Shift", "Cmd", "Super"];
+
+/**
+ * Name of a modifier in the Electron menu accelerator dialect. The modifier `CommandOrControl` resolves to
+ * here is written `CmdOrCtrl`, so the menu shows the combination that is registered.
+ */
+fn menu_mod(modifier: Mod) -> Option<&'static str> {
+  if modifier.resolve() == Mod::CommandOrControl.resolve() {
+    return Some("CmdOrCtrl");
+  }
+
+  match modifier {
+    Mod::Control => Some("Ctrl"),
+    Mod::Alt => Some("Alt"),
+    Mod::AltGraph => Some("AltGr"),
+    Mod::Shift => Some("Shift"),
+    Mod::Meta | Mod::Super if cfg!(target_os = "macos") => Some("Cmd"),
+    Mod::Meta | Mod::Super => Some("Super"),
+    _ => None,
+  }
+}
+
+/**
+ * Converts a hotkey descriptor to an accelerator for the menus of Electron and Tauri, e.g. `CmdOrCtrl+Shift+A`.
+ *
+ * Unlike `descToAccelerator`, keys use the menu names (`A`, `1`, `Up`, `num0`, `VolumeUp`) and the modifier
+ * `CommandOrControl` resolves to on this platform is written `CmdOrCtrl`: `Control` outside macOS and `Meta`
+ * or `Super` on macOS. The OS key is `Super` elsewhere.
+ *
+ * @param {Desc} desc - The combination to convert.
+ * @returns {string | null} The menu accelerator, or `null` if a modifier (e.g. `Hyper`) or the key
+ *   (e.g. `F25`) has no name in the menu dialect.
+ *
+ * @example
+ * ```js
+ * import { toMenuAccelerator, KeyCode, Mod } from '@hotk/core';
+ *
+ * toMenuAccelerator({ mods: [Mod.CommandOrControl, Mod.Shift], code: KeyCode.KeyA }); // "CmdOrCtrl+Shift+A"
+ * ```
+ */
+#[napi]
+pub fn to_menu_accelerator(desc: Desc) -> Option<String> {
+  let name = desc.code.to_string();
+  let key = match (name.strip_prefix("Key"), name.strip_prefix("Digit")) {
+    (Some(letter), _) => letter.to_string(),
+    (_, Some(digit)) => digit.to_string(),
+    _ => MENU_KEYS
+      .iter()
+      .find(|(code, _)| *code == desc.code)
+      .map(|(_, key)| key.to_string())?,
+  };
+
+  let mut tokens = desc
+    .mods
+    .into_iter()
+    .map(menu_mod)
+    .collect::<Option<Vec<&str>>>()?;
+  tokens.sort_by_key(|token| MENU_MOD_ORDER.iter().position(|m| m == token));
+  tokens.dedup();
+  tokens.push(&key);
+  Some(tokens.join("+"))
+}
+
+/**
+ * Parses an accelerator string produced by `descToAccelerator` back into a hotkey descriptor.
+ *
+ * `Win`, `Cmd` and `Super` are read as the OS key of this platform, see `platformPrimaryMeta`.
+ *
+ * @param {string} accelerator - `Mod` names followed by a `KeyCode` name, joined with `+`.
+ * @returns {Desc | null} The descriptor, or `null` if a token is not a known modifier or key code.
+ *
+ * @example
+ * ```js
+ * import { parseHotkey } from '@hotk/core';
+ *
+ * parseHotkey('Control+Shift+KeyK'); // { mods: ['Control', 'Shift'], code: 'KeyK' }
+ * ```
+ */
+#[napi]
+pub fn parse_hotkey(accelerator: String) -> Option<Desc> {
+  Desc::from_accelerator(&accelerator)
+}
+
+/**
+ * Splits an accelerator into its modifiers and key code, for UIs rendering them separately
+ * (e.g. modifier chips and a key badge).
+ *
+ * Accepts the same strings as `parseHotkey`. Modifiers come back in display order and without duplicates.
+ *
+ * @param {string} accelerator - `Mod` names followed by a `KeyCode` name, joined with `+`.
+ * @returns {Desc | null} The parts, or `null` if the accelerator can't be parsed.
+ *
+ * @example
+ * ```js
+ * import { splitAccelerator } from '@hotk/core';
+ *
+ * const { mods, code } = splitAccelerator('Shift+Control+KeyK'); // ['Control', 'Shift'], 'KeyK'
+ * ```
+ */
+#[napi]
+pub fn split_accelerator(accelerator: String) -> Option<Desc> {
+  let mut desc = Desc::from_accelerator(&accelerator)?;
+  desc.mods.sort_by_key(|m| mod_order(*m));
+  desc.mods.dedup();
+  Some(desc)
+}
+
+/**
+ * Parses a hand-written accelerator, e.g. from a settings file, into a hotkey descriptor.
+ *
+ * Tokens are joined with `+` and matched case-insensitively: every token but the last is a `Mod`
+ * (also accepting `Ctrl` for `Control`, and `Cmd`, `Win` or `Super` for `platformPrimaryMeta`), the last one
+ * a `KeyCode`.
+ *
+ * @param {string} input - The accelerator, e.g. `ctrl+shift+KeyK`.
+ * @returns {Desc | null} The descriptor, or `null` if a token is unknown or the key code is missing.
+ *
+ * @example
+ * ```js
+ * import { parseAccelerator } from '@hotk/core';
+ *
+ * parseAccelerator('Ctrl+Shift+keyk'); // { mods: ['Control', 'Shift'], code: 'KeyK' }
+ * ```
+ */
+#[napi]
+pub fn parse_accelerator(input: String) -> Option<Desc> {
+  let mut tokens: Vec<&str> = input.split('+').map(str::trim).collect();
+  let code = tokens.pop()?;
+  let code = KeyCode::iter().find(|c| c.to_string().eq_ignore_ascii_case(code))?;
+  let mods = tokens
+    .into_iter()
+    .map(parse_mod_token)
+    .collect::<Option<Vec<_>>>()?;
+
+  Some(Desc::new(code, mods))
+}
+
+fn parse_mod_token(token: &str) -> Option<Mod> {
+  match token.to_ascii_lowercase().as_str() {
+    "ctrl" => Some(Mod::Control),
+    "cmd" | "win" | "super" => Some(platform_primary_meta()),
+    _ => Mod::iter().find(|m| m.to_string().eq_ignore_ascii_case(token)),
+  }
+}
+
 /**
  * Computes a unique identifier for the given key combination.
  *
  * This function takes a key code and a list of modifier keys,
  * and returns a stable numeric ID that uniquely identifies
  * that specific hotkey combination.
+ *
+ * The id is not a lossy hash: the modifiers fill the upper 16 bits and the key the lower 16,
+ * so two combinations only share an id when they register the same hotkey (e.g. `Meta` and `Super`).
+ * Ids never have the top bit set; that range is left for ids not tied to a single combination, like those
+ * of `HotkManager.registerSequence`, so they can't collide with computed ones. Registration still checks
+ * that an id isn't taken by a different combination and fails with `IdCollision` instead of overwriting it.
  */
 #[napi]
 pub fn get_hotkey_id(code: KeyCode, mods: Vec<Mod>) -> u32 {
-  let mods = mods
-    .iter()
-    .map(|m| m.global_hotkeys())
-    .fold(Modifiers::empty(), |acc, m| acc | m);
-  HotKey::new(Some(mods), code.global_hotkeys()).id
+  Desc::new(code, mods).to_hotkey().id
 }
 
 /**
@@ -468,11 +1273,35 @@ pub fn get_hotkey_id(code: KeyCode, mods: Vec<Mod>) -> u32 {
  * Possible values:
  * - `Pressed`: The hotkey was pressed.
  * - `Released`: The hotkey was released.
+ * - `Registered`: The hotkey was registered, see `HotkManager.setEmitLifecycle`.
+ * - `Unregistered`: The hotkey was unregistered, see `HotkManager.setEmitLifecycle`.
+ * - `MultiTap`: The hotkey was pressed the configured number of times in a row, see `HotkManager.registerMultiTap`.
+ * - `LongPress`: The hotkey was held for the configured time before being released, see `HotkManager.registerLongPress`.
+ * - `Sequence`: The steps of a sequence were pressed in order, see `HotkManager.registerSequence`.
  */
 #[napi]
 pub enum EventType {
   Pressed,
   Released,
+  Registered,
+  Unregistered,
+  MultiTap,
+  LongPress,
+  Sequence,
+}
+
+impl EventType {
+  pub fn name(&self) -> &'static str {
+    match self {
+      EventType::Pressed => "Pressed",
+      EventType::Released => "Released",
+      EventType::Registered => "Registered",
+      EventType::Unregistered => "Unregistered",
+      EventType::MultiTap => "MultiTap",
+      EventType::LongPress => "LongPress",
+      EventType::Sequence => "Sequence",
+    }
+  }
 }
 
 /**
@@ -483,11 +1312,149 @@ pub enum EventType {
  * - `code` (KeyCode): The key code associated with the hotkey.
  * - `mods` (Mod[]): An array of modifier keys (e.g., Control, Shift).
  * - `event_type` (EventType): The type of the event (pressed or released).
+ * - `delta_ms` (number | undefined): Milliseconds since the previous event of the same hotkey, `0` for its first.
+ * - `synthetic` (boolean | undefined): `true` if the crate manufactured the event (e.g. a watchdog `Released`
+ *   or one fed through `simulate`), `false` if it was delivered by the OS.
+ * - `taps` (number | undefined): For `MultiTap` events, how many presses triggered it.
+ * - `timestamp` (number | undefined): When the event reached the crate, in milliseconds since the Unix epoch
+ *   with sub-millisecond precision. Unlike `Date.now()` in the callback, it doesn't include the delivery delay.
  */
 #[napi(object)]
+#[derive(Clone)]
 pub struct Event {
   pub id: u32,
   pub code: KeyCode,
   pub mods: Vec<Mod>,
   pub event_type: EventType,
+  pub delta_ms: Option<f64>,
+  pub synthetic: Option<bool>,
+  pub taps: Option<u32>,
+  pub timestamp: Option<f64>,
+}
+
+impl Event {
+  /**
+   * The current time as an event `timestamp`.
+   */
+  pub fn now() -> Option<f64> {
+    SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .ok()
+      .map(|d| d.as_secs_f64() * 1000.0)
+  }
+
+  /**
+   * Formats the event as a single log line: `<unix ms> <id> <event type> <mods+code>`.
+   */
+  pub fn to_log_line(&self) -> String {
+    let timestamp = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|d| d.as_millis())
+      .unwrap_or(0);
+    let event_type = self.event_type.name();
+    let combo = self
+      .mods
+      .iter()
+      .map(|m| m.to_string())
+      .chain(std::iter::once(self.code.to_string()))
+      .collect::<Vec<_>>()
+      .join("+");
+
+    format!("{} {} {} {}", timestamp, self.id, event_type, combo)
+  }
+}
+
+/**
+ * Extracts the combination that fired an event, e.g. to persist it.
+ *
+ * @param {Event} event - An event received by the `init` callback.
+ * @returns {Desc} The key code and modifiers of the event.
+ *
+ * @example
+ * ```js
+ * import { hotk, eventToDesc } from '@hotk/core';
+ *
+ * hotk().init(event => save(eventToDesc(event)));
+ * ```
+ */
+#[napi]
+pub fn event_to_desc(event: Event) -> Desc {
+  Desc::new(event.code, event.mods)
+}
+
+/**
+ * Serializes an event to a single-line JSON object, for logging or sending it to another process.
+ *
+ * The shape is stable: every key is always present, in this order, with `null` for absent values.
+ * - `id` (number): The hotkey id.
+ * - `code` (string): The key code, e.g. `"KeyK"`.
+ * - `mods` (string[]): The modifiers, e.g. `["Control", "Shift"]`.
+ * - `accelerator` (string): The canonical accelerator, e.g. `"Control+Shift+KeyK"`.
+ * - `eventType` (string): The name of the event type, e.g. `"Pressed"`, which unlike the numeric
+ *   `EventType` value doesn't depend on the version of the package.
+ * - `deltaMs` (number | null): Milliseconds since the previous event of the same hotkey.
+ * - `synthetic` (boolean | null): Whether the crate manufactured the event.
+ * - `taps` (number | null): For `MultiTap` events, how many presses triggered it.
+ * - `timestamp` (number | null): When the event reached the crate, in milliseconds since the Unix epoch.
+ *
+ * @example
+ * ```js
+ * import { hotk, eventToJson } from '@hotk/core';
+ *
+ * hotk().init(event => process.send(eventToJson(event)));
+ * // {"id":1234,"code":"KeyK","mods":["Control","Shift"],"accelerator":"Control+Shift+KeyK","eventType":"Pressed",...}
+ * ```
+ */
+#[napi]
+pub fn event_to_json(event: Event) -> String {
+  // Key codes and modifiers are plain identifiers, so no string needs escaping.
+  let mods = event
+    .mods
+    .iter()
+    .map(|m| format!("\"{}\"", m))
+    .collect::<Vec<_>>()
+    .join(",");
+  let or_null = |value: Option<String>| value.unwrap_or_else(|| "null".to_string());
+
+  format!(
+    "{{\"id\":{},\"code\":\"{}\",\"mods\":[{}],\"accelerator\":\"{}\",\"eventType\":\"{}\",\"deltaMs\":{},\"synthetic\":{},\"taps\":{},\"timestamp\":{}}}",
+    event.id,
+    event.code,
+    mods,
+    Desc::new(event.code, event.mods.clone()).to_accelerator(),
+    event.event_type.name(),
+    or_null(event.delta_ms.map(|delta| delta.to_string())),
+    or_null(event.synthetic.map(|synthetic| synthetic.to_string())),
+    or_null(event.taps.map(|taps| taps.to_string())),
+    or_null(event.timestamp.map(|timestamp| timestamp.to_string())),
+  )
+}
+
+/**
+ * Which events a full delivery queue drops, see `HotkManager.setQueueLimit`.
+ *
+ * - `DropNewest`: Incoming events are dropped, keeping the ones already waiting.
+ * - `DropOldest`: The oldest waiting event is dropped to make room for the incoming one.
+ */
+#[napi(string_enum)]
+#[derive(Debug, PartialEq)]
+pub enum QueuePolicy {
+  DropNewest,
+  DropOldest,
+}
+
+/**
+ * Scheduling priority of the thread running the hotkey backend.
+ *
+ * Only the Windows backend runs its own thread, so this has no effect on other platforms.
+ */
+#[napi(string_enum)]
+#[derive(Debug, PartialEq)]
+pub enum BackendPriority {
+  Idle,
+  Lowest,
+  BelowNormal,
+  Normal,
+  AboveNormal,
+  Highest,
 }
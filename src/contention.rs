@@ -0,0 +1,56 @@
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::TryLockError;
+use std::time::Instant;
+
+use crate::events::LockCounters;
+
+/**
+ * Counts how often a lock is taken and how long takers wait for it, see `HotkManager.lockStats`.
+ *
+ * An uncontended lock costs a counter increment; waits are only timed when the lock is already held.
+ */
+#[derive(Default)]
+pub struct Contention {
+  acquisitions: AtomicU32,
+  contended: AtomicU32,
+  max_wait_us: AtomicU64,
+}
+
+impl Contention {
+  pub const fn new() -> Self {
+    Self {
+      acquisitions: AtomicU32::new(0),
+      contended: AtomicU32::new(0),
+      max_wait_us: AtomicU64::new(0),
+    }
+  }
+
+  pub fn lock<'a, T>(&self, mutex: &'a Mutex<T>) -> MutexGuard<'a, T> {
+    self.acquisitions.fetch_add(1, Ordering::Relaxed);
+    match mutex.try_lock() {
+      Ok(guard) => guard,
+      Err(TryLockError::WouldBlock) => {
+        let start = Instant::now();
+        let guard = mutex.lock().unwrap();
+        self.contended.fetch_add(1, Ordering::Relaxed);
+        self
+          .max_wait_us
+          .fetch_max(start.elapsed().as_micros() as u64, Ordering::Relaxed);
+        guard
+      }
+      Err(TryLockError::Poisoned(_)) => mutex.lock().unwrap(),
+    }
+  }
+
+  pub fn counters(&self) -> LockCounters {
+    LockCounters {
+      acquisitions: self.acquisitions.load(Ordering::Relaxed),
+      contended: self.contended.load(Ordering::Relaxed),
+      max_wait_ms: self.max_wait_us.load(Ordering::Relaxed) as f64 / 1000.0,
+    }
+  }
+}
@@ -0,0 +1,40 @@
+use std::sync::mpsc::channel;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use napi::threadsafe_function::ErrorStrategy;
+use napi::threadsafe_function::ThreadsafeFunction;
+use napi::threadsafe_function::ThreadsafeFunctionCallMode;
+
+use crate::code::Desc;
+use crate::events::HotkReponse;
+use crate::InnerHotk;
+
+pub type DeferredCallback = ThreadsafeFunction<HotkReponse, ErrorStrategy::Fatal>;
+
+/**
+ * Starts the thread that performs deferred registrations in the order they were enqueued,
+ * reporting each response to the deferred result callback, if any.
+ */
+pub fn spawn_worker(hotk: Arc<Mutex<InnerHotk>>) -> Sender<Desc> {
+  let (sender, receiver) = channel::<Desc>();
+
+  std::thread::spawn(move || {
+    for desc in receiver {
+      let (response, callback) = {
+        let mut lock = crate::lock_hotk(&hotk);
+        (
+          lock.register(desc.mods, desc.code),
+          lock.on_deferred_result.clone(),
+        )
+      };
+
+      if let Some(callback) = callback {
+        callback.call(response, ThreadsafeFunctionCallMode::NonBlocking);
+      }
+    }
+  });
+
+  sender
+}
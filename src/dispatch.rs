@@ -0,0 +1,1181 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use global_hotkey::hotkey::Modifiers;
+use global_hotkey::GlobalHotKeyEvent;
+use global_hotkey::HotKeyState;
+use napi::threadsafe_function::ErrorStrategy;
+use napi::threadsafe_function::ThreadsafeFunction;
+use napi::threadsafe_function::ThreadsafeFunctionCallMode;
+use napi::CallContext;
+use napi::Env;
+use napi::JsFunction;
+use napi::JsObject;
+use napi::JsUndefined;
+use napi::Ref;
+
+use crate::code::Desc;
+use crate::code::Event;
+use crate::code::EventType;
+use crate::code::KeyCode;
+use crate::code::Mod;
+use crate::code::QueuePolicy;
+use crate::contention::Contention;
+use crate::events::LockCounters;
+use crate::trace::EventTrace;
+use crate::HotkOptions;
+
+/**
+ * Wakes the JS thread so it drains the delivery queue. It carries no payload:
+ * events stay in the queue until delivered, so they can be flushed synchronously.
+ */
+pub type EventCallback = ThreadsafeFunction<(), ErrorStrategy::Fatal>;
+
+/**
+ * Receives the events of a single hotkey.
+ */
+pub type HotkeyCallback = ThreadsafeFunction<Event, ErrorStrategy::Fatal>;
+
+/**
+ * Receives the events accumulated since the previous batch.
+ */
+pub type BatchCallback = ThreadsafeFunction<Vec<Event>, ErrorStrategy::Fatal>;
+
+/**
+ * Maximum number of events kept while waiting for `init`. Once full, the oldest event is dropped.
+ */
+pub const PREINIT_CAPACITY: usize = 64;
+
+struct Batch {
+  callback: BatchCallback,
+  events: Vec<Event>,
+  max_size: usize,
+  // Identifies the flushing thread of this batch, so the thread of a replaced batch stops.
+  generation: u64,
+}
+
+impl Batch {
+  fn flush(&mut self) {
+    if !self.events.is_empty() {
+      let events = std::mem::take(&mut self.events);
+      self
+        .callback
+        .call(events, ThreadsafeFunctionCallMode::NonBlocking);
+    }
+  }
+}
+
+#[derive(Default)]
+struct DispatchState {
+  tsfn: Option<EventCallback>,
+  callback: Option<Ref<()>>,
+  // Whether `tsfn` was unref'd, so a replacing callback is too.
+  unreferenced: bool,
+  // When a callback was first installed, kept when it is replaced.
+  listening_since: Option<(SystemTime, Instant)>,
+  buffer_preinit: bool,
+  preinit: VecDeque<Event>,
+  queue: VecDeque<Event>,
+  // Most events the queue holds, with what to drop when it is full. Unbounded if `None`.
+  queue_limit: Option<(usize, QueuePolicy)>,
+  // Events dropped because the queue was full.
+  overflowed: u32,
+  trace: Option<EventTrace>,
+  console_trace: Option<EventTrace>,
+  // Prefixes trace lines, to tell instances apart.
+  instance_name: Option<String>,
+  hotkey_callbacks: HashMap<u32, HotkeyCallback>,
+  // Callbacks for every hotkey on a key, whatever its modifiers.
+  key_callbacks: HashMap<KeyCode, HotkeyCallback>,
+  // Handlers receiving the events of an id in place of the `init` callback.
+  handlers: HashMap<u32, HotkeyCallback>,
+  release_timeout: Option<Duration>,
+  // Held ids, with when and how they were last pressed.
+  pressed: HashMap<u32, (Instant, Event)>,
+  // Whether the watchdog thread is running.
+  watching: bool,
+  // Ids the watchdog released whose real Released has not arrived yet.
+  expired: HashSet<u32>,
+  late_grace: Duration,
+  // Recently unregistered hotkeys, with when they were removed.
+  tombstones: HashMap<u32, (Desc, Instant)>,
+  // Ids whose events are dropped until they are armed again.
+  disarmed: HashSet<u32>,
+  // When the last event of each id was emitted.
+  last_emitted: HashMap<u32, Instant>,
+  // When set, replaces the per-event delivery to the `init` callback.
+  batch: Option<Batch>,
+  batch_generation: u64,
+  // Ids only delivered while exactly their modifiers are held.
+  exact: HashSet<u32>,
+  // Ids whose press was dropped (exact or cooldown), so their Released is dropped too.
+  rejected: HashSet<u32>,
+  cooldowns: HashMap<u32, Duration>,
+  // When each id in cooldown may fire again.
+  next_allowed: HashMap<u32, Instant>,
+  // Presses needed for a `MultiTap` and the window they must fit in.
+  multi_taps: HashMap<u32, (u32, Duration)>,
+  // Presses counted so far, with when the first of them happened.
+  tapping: HashMap<u32, (u32, Instant)>,
+  // How long a press must be held to fire a `LongPress`.
+  long_presses: HashMap<u32, Duration>,
+  // When each held id with a long press was pressed.
+  held_since: HashMap<u32, Instant>,
+  // Ids never dropped by the queue limit.
+  queue_exempt: HashSet<u32>,
+  // Ids whose Released is withheld, with the last one withheld.
+  deferred_released: HashMap<u32, Option<Event>>,
+  // Ids of the steps of each sequence, and the window they must fit in.
+  sequences: HashMap<u32, (Vec<u32>, Duration)>,
+  // Steps of each sequence pressed so far, with when the first of them happened.
+  sequence_progress: HashMap<u32, (usize, Instant)>,
+  emit_lifecycle: bool,
+  // Modifiers left out of the live state comparison of exact ids.
+  ignored_modifiers: Modifiers,
+}
+
+/**
+ * Whether exactly the modifiers of `mods` are held right now. `true` when the platform
+ * can't tell, so events are never dropped on a guess.
+ */
+fn exact_modifiers_held(mods: &[Mod], ignored: Modifiers) -> bool {
+  let Some(held) = crate::held_modifiers() else {
+    return true;
+  };
+
+  let mask = (Modifiers::CONTROL | Modifiers::ALT | Modifiers::SHIFT | Modifiers::SUPER) - ignored;
+  held & mask == fold_meta(mods_bits(mods)) & mask
+}
+
+fn mods_bits(mods: &[Mod]) -> Modifiers {
+  mods
+    .iter()
+    .map(|m| m.global_hotkeys())
+    .fold(Modifiers::empty(), |acc, m| acc | m)
+}
+
+/**
+ * Same folding `HotKey::new` applies.
+ */
+fn fold_meta(mut mods: Modifiers) -> Modifiers {
+  if mods.contains(Modifiers::META) {
+    mods.remove(Modifiers::META);
+    mods.insert(Modifiers::SUPER);
+  }
+  mods
+}
+
+/**
+ * Routes raw hotkey events to the JS callback.
+ *
+ * It is installed as the global event handler once, when the manager is created,
+ * so events can be observed before `init` provides a callback.
+ */
+pub struct Dispatcher {
+  hotkeys: Arc<Mutex<HashMap<u32, Desc>>>,
+  state: Mutex<DispatchState>,
+  // Events that could not be converted to JS and were skipped.
+  dropped: AtomicU32,
+  // Number of upcoming conversions to fail, to exercise the dropped path.
+  #[cfg(feature = "test-hooks")]
+  failing_conversions: AtomicU32,
+  state_contention: Contention,
+}
+
+impl Dispatcher {
+  pub fn new(hotkeys: Arc<Mutex<HashMap<u32, Desc>>>) -> Self {
+    Self {
+      hotkeys,
+      state: Default::default(),
+      dropped: Default::default(),
+      #[cfg(feature = "test-hooks")]
+      failing_conversions: Default::default(),
+      state_contention: Default::default(),
+    }
+  }
+
+  /**
+   * Returns `false` if the event was ignored because its id is unknown. `synthetic` is set for events
+   * the crate feeds itself rather than receiving them from the OS.
+   */
+  pub fn dispatch(self: &Arc<Self>, event: GlobalHotKeyEvent, synthetic: bool) -> bool {
+    let timestamp = Event::now();
+    let desc = self.hotkeys.lock().unwrap().get(&event.id).cloned();
+
+    let state = &mut *self.lock_state();
+
+    let desc = match desc {
+      Some(desc) => desc,
+      None => match state.tombstones.get(&event.id) {
+        Some((desc, removed)) if removed.elapsed() <= state.late_grace => desc.clone(),
+        _ => return false,
+      },
+    };
+
+    if state.disarmed.contains(&event.id) {
+      return true;
+    }
+
+    let ev = Event {
+      id: event.id,
+      code: desc.code,
+      mods: desc.mods,
+      event_type: match event.state {
+        HotKeyState::Pressed => EventType::Pressed,
+        HotKeyState::Released => EventType::Released,
+      },
+      delta_ms: None,
+      synthetic: Some(synthetic),
+      taps: None,
+      timestamp,
+    };
+
+    if state.exact.contains(&ev.id) {
+      match event.state {
+        HotKeyState::Pressed if !exact_modifiers_held(&ev.mods, state.ignored_modifiers) => {
+          state.rejected.insert(ev.id);
+          return true;
+        }
+        HotKeyState::Pressed => {
+          state.rejected.remove(&ev.id);
+        }
+        HotKeyState::Released => {
+          if state.rejected.remove(&ev.id) {
+            return true;
+          }
+        }
+      }
+    }
+
+    if let Some(cooldown) = state.cooldowns.get(&ev.id).copied() {
+      match event.state {
+        HotKeyState::Pressed => {
+          let now = Instant::now();
+          if state
+            .next_allowed
+            .get(&ev.id)
+            .is_some_and(|next| now < *next)
+          {
+            state.rejected.insert(ev.id);
+            return true;
+          }
+          state.rejected.remove(&ev.id);
+          state.next_allowed.insert(ev.id, now + cooldown);
+        }
+        HotKeyState::Released => {
+          if state.rejected.remove(&ev.id) {
+            return true;
+          }
+        }
+      }
+    }
+
+    match event.state {
+      HotKeyState::Pressed => {
+        state.pressed.insert(ev.id, (Instant::now(), ev.clone()));
+        state.expired.remove(&ev.id);
+        if state.release_timeout.is_some() && !state.watching {
+          state.watching = true;
+          self.watch();
+        }
+      }
+      HotKeyState::Released => {
+        state.pressed.remove(&ev.id);
+        // Already released by the watchdog.
+        if state.expired.remove(&ev.id) {
+          return true;
+        }
+      }
+    }
+
+    let multi_tap = match (event.state, state.multi_taps.get(&ev.id).copied()) {
+      (HotKeyState::Pressed, Some((taps, window))) => {
+        let now = Instant::now();
+        let (count, first) = match state.tapping.get(&ev.id) {
+          Some((count, first)) if now.duration_since(*first) <= window => (count + 1, *first),
+          _ => (1, now),
+        };
+        if count >= taps {
+          state.tapping.remove(&ev.id);
+          Some(count)
+        } else {
+          state.tapping.insert(ev.id, (count, first));
+          None
+        }
+      }
+      _ => None,
+    };
+
+    let sequences: Vec<Event> = match event.state {
+      HotKeyState::Pressed => Self::advance_sequences(state, ev.id)
+        .into_iter()
+        .map(|id| Event {
+          id,
+          event_type: EventType::Sequence,
+          synthetic: Some(true),
+          ..ev.clone()
+        })
+        .collect(),
+      HotKeyState::Released => vec![],
+    };
+
+    match event.state {
+      HotKeyState::Pressed if state.long_presses.contains_key(&ev.id) => {
+        // OS repeats of a held key keep the first press.
+        state.held_since.entry(ev.id).or_insert_with(Instant::now);
+      }
+      HotKeyState::Released => Self::end_hold(state, &ev),
+      _ => {}
+    }
+
+    // After the long press bookkeeping, so a withheld release still ends the hold.
+    if let (HotKeyState::Released, Some(withheld)) =
+      (event.state, state.deferred_released.get_mut(&ev.id))
+    {
+      *withheld = Some(ev);
+      return true;
+    }
+
+    match multi_tap {
+      Some(taps) => {
+        let tap = Event {
+          event_type: EventType::MultiTap,
+          synthetic: Some(true),
+          taps: Some(taps),
+          ..ev.clone()
+        };
+        Self::emit(state, ev);
+        Self::emit(state, tap);
+      }
+      None => Self::emit(state, ev),
+    }
+    for sequence in sequences {
+      Self::emit(state, sequence);
+    }
+    true
+  }
+
+  /**
+   * Ends the hold of a released id, emitting a `LongPress` first if it was held long enough.
+   */
+  fn end_hold(state: &mut DispatchState, release: &Event) {
+    let Some(hold) = state.long_presses.get(&release.id).copied() else {
+      return;
+    };
+    if state
+      .held_since
+      .remove(&release.id)
+      .is_some_and(|since| since.elapsed() > hold)
+    {
+      let long = Event {
+        event_type: EventType::LongPress,
+        synthetic: Some(true),
+        ..release.clone()
+      };
+      Self::emit(state, long);
+    }
+  }
+
+  /**
+   * Takes the state lock, counting how often it is found held, see `HotkManager.lockStats`.
+   */
+  fn lock_state(&self) -> MutexGuard<'_, DispatchState> {
+    self.state_contention.lock(&self.state)
+  }
+
+  pub fn state_contention(&self) -> LockCounters {
+    self.state_contention.counters()
+  }
+
+  /**
+   * Moves every sequence forward with a press of `id`, returning the ids of those it completes. A press
+   * that isn't the next step, or comes after the window, starts the sequence over.
+   */
+  fn advance_sequences(state: &mut DispatchState, id: u32) -> Vec<u32> {
+    let now = Instant::now();
+    let mut completed = vec![];
+
+    for (sequence, (steps, window)) in &state.sequences {
+      let (done, first) = match state.sequence_progress.get(sequence) {
+        Some((done, first)) if now.duration_since(*first) <= *window => (*done, *first),
+        _ => (0, now),
+      };
+      let (done, first) = if steps[done] == id {
+        (done + 1, first)
+      } else if steps[0] == id {
+        (1, now)
+      } else {
+        (0, now)
+      };
+
+      if done == steps.len() {
+        completed.push(*sequence);
+        state.sequence_progress.remove(sequence);
+      } else if done > 0 {
+        state.sequence_progress.insert(*sequence, (done, first));
+      } else {
+        state.sequence_progress.remove(sequence);
+      }
+    }
+    completed
+  }
+
+  /**
+   * Remembers an unregistered hotkey for the late event grace window, so its late events are still delivered.
+   */
+  pub fn tombstone(&self, id: u32, desc: Desc) {
+    let mut state = self.lock_state();
+
+    if state.late_grace.is_zero() {
+      return;
+    }
+    let grace = state.late_grace;
+    state
+      .tombstones
+      .retain(|_, (_, removed)| removed.elapsed() <= grace);
+    state.tombstones.insert(id, (desc, Instant::now()));
+  }
+
+  /**
+   * Emits a synthetic `Registered` or `Unregistered` event for `id`, if lifecycle events are enabled.
+   */
+  pub fn lifecycle(&self, id: u32, desc: Desc, event_type: EventType) {
+    let state = &mut *self.lock_state();
+
+    if !state.emit_lifecycle {
+      return;
+    }
+    Self::emit(
+      state,
+      Event {
+        id,
+        code: desc.code,
+        mods: desc.mods,
+        event_type,
+        delta_ms: None,
+        synthetic: Some(true),
+        taps: None,
+        timestamp: Event::now(),
+      },
+    );
+  }
+
+  pub fn set_emit_lifecycle(&self, enabled: bool) {
+    self.lock_state().emit_lifecycle = enabled;
+  }
+
+  /**
+   * Only delivers the events of `id` while exactly its modifiers are held.
+   */
+  pub fn set_exact(&self, id: u32) {
+    self.lock_state().exact.insert(id);
+  }
+
+  /**
+   * Leaves `mods` out when comparing the live modifier state for exact ids.
+   */
+  pub fn set_ignored_modifiers(&self, mods: &[Mod]) {
+    self.lock_state().ignored_modifiers = fold_meta(mods_bits(mods));
+  }
+
+  /**
+   * Ignores the presses of `id` for `cooldown` after each delivered one. A zero `cooldown` disables it.
+   */
+  pub fn set_cooldown(&self, id: u32, cooldown: Duration) {
+    let mut state = self.lock_state();
+
+    state.next_allowed.remove(&id);
+    if cooldown.is_zero() {
+      state.cooldowns.remove(&id);
+    } else {
+      state.cooldowns.insert(id, cooldown);
+    }
+  }
+
+  /**
+   * Emits a `MultiTap` event once `id` is pressed `taps` times within `window` of the first press.
+   */
+  pub fn set_multi_tap(&self, id: u32, taps: u32, window: Duration) {
+    let mut state = self.lock_state();
+
+    state.tapping.remove(&id);
+    state.multi_taps.insert(id, (taps, window));
+  }
+
+  /**
+   * Emits a `Sequence` event with `id` once the hotkeys of `steps` are pressed in order within `window`.
+   */
+  pub fn set_sequence(&self, id: u32, steps: Vec<u32>, window: Duration) {
+    let mut state = self.lock_state();
+
+    state.sequence_progress.remove(&id);
+    state.sequences.insert(id, (steps, window));
+  }
+
+  pub fn remove_sequence(&self, id: u32) {
+    let mut state = self.lock_state();
+
+    state.sequence_progress.remove(&id);
+    state.sequences.remove(&id);
+  }
+
+  /**
+   * Emits a `LongPress` event before the `Released` of `id` when it was held for longer than `hold`.
+   */
+  pub fn set_long_press(&self, id: u32, hold: Duration) {
+    let mut state = self.lock_state();
+
+    state.held_since.remove(&id);
+    state.long_presses.insert(id, hold);
+  }
+
+  /**
+   * Exempts the events of `id` from the queue limit, or restores the default.
+   */
+  pub fn set_queue_exempt(&self, id: u32, enabled: bool) {
+    let mut state = self.lock_state();
+
+    if enabled {
+      state.queue_exempt.insert(id);
+    } else {
+      state.queue_exempt.remove(&id);
+    }
+  }
+
+  pub fn is_queue_exempt(&self, id: u32) -> bool {
+    self.lock_state().queue_exempt.contains(&id)
+  }
+
+  /**
+   * Withholds the `Released` events of `id` while `enabled`. Disabling it delivers the last one withheld,
+   * unless `drop_withheld`.
+   */
+  pub fn defer_released(&self, id: u32, enabled: bool, drop_withheld: bool) {
+    let state = &mut *self.lock_state();
+
+    if enabled {
+      state.deferred_released.entry(id).or_insert(None);
+      return;
+    }
+    if let Some(Some(ev)) = state.deferred_released.remove(&id) {
+      if !drop_withheld {
+        Self::emit(state, ev);
+      }
+    }
+  }
+
+  /**
+   * Drops the per-registration options of `id`, once it is no longer registered.
+   */
+  pub fn forget(&self, id: u32) {
+    let mut state = self.lock_state();
+
+    state.exact.remove(&id);
+    state.rejected.remove(&id);
+    state.cooldowns.remove(&id);
+    state.next_allowed.remove(&id);
+    state.multi_taps.remove(&id);
+    state.tapping.remove(&id);
+    state.long_presses.remove(&id);
+    state.held_since.remove(&id);
+    state.queue_exempt.remove(&id);
+    state.deferred_released.remove(&id);
+    state.pressed.remove(&id);
+    state.expired.remove(&id);
+  }
+
+  /**
+   * Delivers the events of `id` again. Returns `false` if it was not disarmed.
+   */
+  pub fn arm(&self, id: u32) -> bool {
+    self.lock_state().disarmed.remove(&id)
+  }
+
+  /**
+   * Drops the events of `id` until it is armed again. Returns `false` if it was already disarmed.
+   */
+  pub fn disarm(&self, id: u32) -> bool {
+    self.lock_state().disarmed.insert(id)
+  }
+
+  pub fn set_late_grace(&self, grace: Duration) {
+    let mut state = self.lock_state();
+
+    state.late_grace = grace;
+    if grace.is_zero() {
+      state.tombstones.clear();
+    }
+  }
+
+  fn emit(state: &mut DispatchState, mut ev: Event) {
+    let now = Instant::now();
+    let previous = state.last_emitted.insert(ev.id, now);
+    ev.delta_ms = Some(previous.map_or(0.0, |previous| {
+      now.duration_since(previous).as_secs_f64() * 1000.0
+    }));
+
+    if state.trace.is_some() || state.console_trace.is_some() {
+      let line = match &state.instance_name {
+        Some(name) => format!("[{}] {}", name, ev.to_log_line()),
+        None => ev.to_log_line(),
+      };
+      if let Some(trace) = &state.trace {
+        trace.write(line.clone());
+      }
+      if let Some(trace) = &state.console_trace {
+        trace.write(line);
+      }
+    }
+
+    if let Some(callback) = state.hotkey_callbacks.get(&ev.id) {
+      callback.call(ev.clone(), ThreadsafeFunctionCallMode::NonBlocking);
+    }
+    if let Some(callback) = state.key_callbacks.get(&ev.code) {
+      callback.call(ev.clone(), ThreadsafeFunctionCallMode::NonBlocking);
+    }
+
+    if let Some(handler) = state.handlers.get(&ev.id) {
+      handler.call(ev, ThreadsafeFunctionCallMode::NonBlocking);
+    } else if let Some(batch) = &mut state.batch {
+      batch.events.push(ev);
+      if batch.events.len() >= batch.max_size {
+        batch.flush();
+      }
+    } else if let Some(tsfn) = &state.tsfn {
+      match state.queue_limit {
+        _ if state.queue_exempt.contains(&ev.id) => {}
+        Some((limit, QueuePolicy::DropNewest)) if state.queue.len() >= limit => {
+          state.overflowed += 1;
+          return;
+        }
+        Some((limit, QueuePolicy::DropOldest)) if state.queue.len() >= limit => {
+          state.queue.pop_front();
+          state.overflowed += 1;
+        }
+        _ => {}
+      }
+      state.queue.push_back(ev);
+      tsfn.call((), ThreadsafeFunctionCallMode::NonBlocking);
+    } else if state.buffer_preinit {
+      if state.preinit.len() == PREINIT_CAPACITY {
+        state.preinit.pop_front();
+      }
+      state.preinit.push_back(ev);
+    }
+  }
+
+  /**
+   * Starts the watchdog thread. A single thread serves every held hotkey: it sleeps until the next one is
+   * due and stops once none is held or the watchdog is disabled.
+   */
+  fn watch(self: &Arc<Self>) {
+    let dispatcher = self.clone();
+
+    std::thread::spawn(move || {
+      while let Some(wait) = dispatcher.expire_held() {
+        std::thread::sleep(wait);
+      }
+    });
+  }
+
+  /**
+   * Emits a synthetic Released for every hotkey held past the release timeout. Returns how long until the
+   * next one is due, or `None` once there is nothing left to watch.
+   */
+  fn expire_held(&self) -> Option<Duration> {
+    let state = &mut *self.lock_state();
+    let now = Instant::now();
+
+    let Some(timeout) = state.release_timeout.filter(|_| !state.pressed.is_empty()) else {
+      state.watching = false;
+      return None;
+    };
+
+    let expired: Vec<u32> = state
+      .pressed
+      .iter()
+      .filter(|(_, (since, _))| now.duration_since(*since) >= timeout)
+      .map(|(id, _)| *id)
+      .collect();
+    for id in expired {
+      let Some((_, press)) = state.pressed.remove(&id) else {
+        continue;
+      };
+      state.expired.insert(id);
+      if state.disarmed.contains(&id) {
+        state.held_since.remove(&id);
+        continue;
+      }
+
+      let release = Event {
+        event_type: EventType::Released,
+        delta_ms: None,
+        synthetic: Some(true),
+        timestamp: Event::now(),
+        ..press
+      };
+      // The real Released is dropped once it arrives, so the hold ends here.
+      Self::end_hold(state, &release);
+      // Withheld like a real Released.
+      if let Some(withheld) = state.deferred_released.get_mut(&id) {
+        *withheld = Some(release);
+        continue;
+      }
+      Self::emit(state, release);
+    }
+
+    let next = state
+      .pressed
+      .values()
+      .map(|(since, _)| *since + timeout)
+      .min();
+    if next.is_none() {
+      state.watching = false;
+    }
+    next.map(|next| next.saturating_duration_since(now))
+  }
+
+  /**
+   * Sets how long a hotkey may stay pressed before a synthetic Released is emitted. `None` disables it.
+   */
+  pub fn set_release_timeout(&self, timeout: Option<Duration>) {
+    self.lock_state().release_timeout = timeout;
+  }
+
+  /**
+   * Forgets every held hotkey, so no synthetic Released is emitted for them.
+   */
+  pub fn reset_key_state(&self) {
+    let mut state = self.lock_state();
+
+    state.pressed.clear();
+    state.expired.clear();
+  }
+
+  /**
+   * Delivers events to `callback` in batches, every `interval` or as soon as `max_size` events accumulate,
+   * instead of one by one to the `init` callback. `None` restores the per-event delivery.
+   */
+  pub fn set_batch(
+    self: &Arc<Self>,
+    callback: Option<BatchCallback>,
+    interval: Duration,
+    max_size: usize,
+  ) {
+    let mut state = self.lock_state();
+
+    // Events of the previous batch are not lost.
+    if let Some(mut previous) = state.batch.take() {
+      previous.flush();
+    }
+    let Some(callback) = callback else {
+      return;
+    };
+
+    state.batch_generation += 1;
+    let generation = state.batch_generation;
+    state.batch = Some(Batch {
+      callback,
+      events: vec![],
+      max_size: max_size.max(1),
+      generation,
+    });
+
+    let dispatcher = self.clone();
+    std::thread::spawn(move || loop {
+      std::thread::sleep(interval);
+
+      let mut state = dispatcher.lock_state();
+      match &mut state.batch {
+        Some(batch) if batch.generation == generation => batch.flush(),
+        _ => return,
+      }
+    });
+  }
+
+  /**
+   * Replaces the per-hotkey callbacks.
+   */
+  pub fn set_hotkey_callbacks(&self, callbacks: HashMap<u32, HotkeyCallback>) {
+    self.lock_state().hotkey_callbacks = callbacks;
+  }
+
+  /**
+   * Sets the callback for the hotkeys on `code`, or removes it if `callback` is `None`.
+   */
+  pub fn set_key_callback(&self, code: KeyCode, callback: Option<HotkeyCallback>) {
+    let previous = {
+      let mut state = self.lock_state();
+      match callback {
+        Some(callback) => state.key_callbacks.insert(code, callback),
+        None => state.key_callbacks.remove(&code),
+      }
+    };
+    drop(previous);
+  }
+
+  /**
+   * Sets the handler of `id`, or removes it if `handler` is `None`. Returns whether `id` had one.
+   */
+  pub fn set_handler(&self, id: u32, handler: Option<HotkeyCallback>) -> bool {
+    let previous = {
+      let mut state = self.lock_state();
+      match handler {
+        Some(handler) => state.handlers.insert(id, handler),
+        None => state.handlers.remove(&id),
+      }
+    };
+    previous.is_some()
+  }
+
+  /**
+   * Installs the JS callback, first queueing any event buffered before it existed.
+   *
+   * A previous callback is replaced: events still queued go to the new one, which is unref'd if the
+   * previous one was. Returns whether a callback was replaced.
+   */
+  pub fn set_callback(
+    &self,
+    env: Env,
+    mut tsfn: EventCallback,
+    callback: Ref<()>,
+  ) -> napi::Result<bool> {
+    let (previous_tsfn, previous_callback) = {
+      let mut state = self.lock_state();
+
+      if state.tsfn.is_some() && state.unreferenced {
+        tsfn.unref(&env)?;
+      }
+
+      if state.tsfn.is_none() {
+        state.listening_since = Some((SystemTime::now(), Instant::now()));
+      }
+
+      let preinit: Vec<Event> = state.preinit.drain(..).collect();
+      state.queue.extend(preinit);
+      if !state.queue.is_empty() {
+        tsfn.call((), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+
+      (state.tsfn.replace(tsfn), state.callback.replace(callback))
+    };
+
+    let replaced = previous_tsfn.is_some();
+    if let Some(previous_tsfn) = previous_tsfn {
+      previous_tsfn.abort()?;
+    }
+    if let Some(mut previous_callback) = previous_callback {
+      previous_callback.unref(env)?;
+    }
+    Ok(replaced)
+  }
+
+  /**
+   * When the current callback started listening, unless there is none.
+   */
+  pub fn listening_since(&self) -> Option<(SystemTime, Instant)> {
+    self.lock_state().listening_since
+  }
+
+  /**
+   * Refs or unrefs the JS callback, remembering it for a callback that replaces it.
+   */
+  pub fn set_referenced(&self, env: Env, referenced: bool) -> bool {
+    let mut state = self.lock_state();
+    let Some(tsfn) = state.tsfn.as_mut() else {
+      return false;
+    };
+
+    let done = if referenced {
+      tsfn.refer(&env).is_ok()
+    } else {
+      tsfn.unref(&env).is_ok()
+    };
+    if done {
+      state.unreferenced = !referenced;
+    }
+    done
+  }
+
+  /**
+   * Delivers every queued event to the JS callback, in the order they were fired.
+   *
+   * Must run on the JS thread. The state lock is released before calling into JS,
+   * so the callback can use the manager.
+   *
+   * A throwing callback doesn't cost the events queued after it: every event is still
+   * delivered and the first error is returned once the queue is empty.
+   */
+  pub fn deliver(&self, env: Env) -> napi::Result<()> {
+    let mut thrown = None;
+    loop {
+      let (events, callback) = {
+        let mut state = self.lock_state();
+        let Some(callback) = &state.callback else {
+          break;
+        };
+        if state.queue.is_empty() {
+          break;
+        }
+        let callback: JsFunction = env.get_reference_value(callback)?;
+        (state.queue.drain(..).collect::<Vec<_>>(), callback)
+      };
+
+      // A failed conversion only costs its own event.
+      for ev in events {
+        match self.convert(env, ev) {
+          Ok(obj) => {
+            if let Err(error) = callback.call(None, &[obj]) {
+              thrown.get_or_insert(error);
+            }
+          }
+          Err(_) => {
+            self.dropped.fetch_add(1, Ordering::SeqCst);
+          }
+        }
+      }
+    }
+    thrown.map_or(Ok(()), Err)
+  }
+
+  fn convert(&self, env: Env, ev: Event) -> napi::Result<JsObject> {
+    #[cfg(feature = "test-hooks")]
+    if self
+      .failing_conversions
+      .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+      .is_ok()
+    {
+      return Err(napi::Error::from_reason("Simulated conversion failure"));
+    }
+    event_object(env, ev)
+  }
+
+  /**
+   * Number of events skipped because they could not be converted to JS objects or the queue was full.
+   */
+  pub fn dropped(&self) -> u32 {
+    self.dropped.load(Ordering::SeqCst) + self.lock_state().overflowed
+  }
+
+  /**
+   * Bounds the delivery queue to `limit` events, dropping by `policy` once full. A zero `limit` removes the bound.
+   */
+  pub fn set_queue_limit(&self, limit: u32, policy: QueuePolicy) {
+    self.lock_state().queue_limit = (limit > 0).then_some((limit as usize, policy));
+  }
+
+  /**
+   * Makes the next `count` conversions to JS objects fail.
+   */
+  #[cfg(feature = "test-hooks")]
+  pub fn fail_conversions(&self, count: u32) {
+    self.failing_conversions.store(count, Ordering::SeqCst);
+  }
+
+  /**
+   * Detaches every JS callback, so nothing is delivered until a new `set_callback`.
+   *
+   * The global event handler can't be uninstalled, so routing is stopped by taking the callback out
+   * of the state first; only then is the listener aborted and the remaining state cleared.
+   */
+  pub fn detach(&self, env: Env) -> napi::Result<()> {
+    let (tsfn, callback, hotkey_callbacks, key_callbacks, handlers, batch) = {
+      let mut state = self.lock_state();
+      let tsfn = state.tsfn.take();
+      let callback = state.callback.take();
+      state.unreferenced = false;
+      state.listening_since = None;
+      let hotkey_callbacks = std::mem::take(&mut state.hotkey_callbacks);
+      let key_callbacks = std::mem::take(&mut state.key_callbacks);
+      let handlers = std::mem::take(&mut state.handlers);
+      let batch = state.batch.take();
+      state.queue.clear();
+      state.preinit.clear();
+      state.pressed.clear();
+      state.expired.clear();
+      state.tombstones.clear();
+      (
+        tsfn,
+        callback,
+        hotkey_callbacks,
+        key_callbacks,
+        handlers,
+        batch,
+      )
+    };
+
+    if let Some(tsfn) = tsfn {
+      tsfn.abort()?;
+    }
+    if let Some(mut callback) = callback {
+      callback.unref(env)?;
+    }
+    drop(hotkey_callbacks);
+    drop(key_callbacks);
+    drop(handlers);
+    drop(batch);
+    Ok(())
+  }
+
+  /**
+   * Starts writing every dispatched event to `trace`, replacing any previous trace.
+   */
+  pub fn start_trace(&self, trace: EventTrace) {
+    let previous = self.lock_state().trace.replace(trace);
+    if let Some(previous) = previous {
+      previous.stop();
+    }
+  }
+
+  /**
+   * Stops the current trace, if any, once its pending lines are written.
+   */
+  pub fn stop_trace(&self) -> bool {
+    let trace = self.lock_state().trace.take();
+    trace.map(|trace| trace.stop()).is_some()
+  }
+
+  pub fn set_instance_name(&self, name: Option<String>) {
+    self.lock_state().instance_name = name;
+  }
+
+  /**
+   * Starts or stops printing every dispatched event to stderr.
+   */
+  pub fn set_console_trace(&self, enabled: bool) {
+    let mut state = self.lock_state();
+
+    if !enabled {
+      let previous = state.console_trace.take();
+      drop(state);
+      if let Some(previous) = previous {
+        previous.stop();
+      }
+    } else if state.console_trace.is_none() {
+      state.console_trace = Some(EventTrace::console());
+    }
+  }
+
+  /**
+   * Applies every set field of `options` under a single lock, so events never see them half applied.
+   */
+  pub fn configure(&self, options: &HotkOptions) {
+    let mut state = self.lock_state();
+
+    if let Some(enabled) = options.buffer_preinit {
+      state.buffer_preinit = enabled;
+      if !enabled {
+        state.preinit.clear();
+      }
+    }
+    if let Some(enabled) = options.emit_lifecycle {
+      state.emit_lifecycle = enabled;
+    }
+    if let Some(grace_ms) = options.late_event_grace_ms {
+      state.late_grace = Duration::from_millis(grace_ms as u64);
+      if grace_ms == 0 {
+        state.tombstones.clear();
+      }
+    }
+    if let Some(timeout_ms) = options.release_watchdog_ms {
+      state.release_timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms as u64));
+    }
+    if options.queue_limit.is_some() || options.queue_policy.is_some() {
+      let (limit, policy) = state.queue_limit.unwrap_or((0, QueuePolicy::DropNewest));
+      let limit = options.queue_limit.map_or(limit, |limit| limit as usize);
+      let policy = options.queue_policy.unwrap_or(policy);
+      state.queue_limit = (limit > 0).then_some((limit, policy));
+    }
+    if let Some(mods) = &options.ignored_modifiers {
+      state.ignored_modifiers = fold_meta(mods_bits(mods));
+    }
+    if let Some(name) = &options.instance_name {
+      state.instance_name = Some(name.clone()).filter(|name| !name.is_empty());
+    }
+
+    let previous_trace = match options.console_trace {
+      Some(false) => state.console_trace.take(),
+      Some(true) => {
+        if state.console_trace.is_none() {
+          state.console_trace = Some(EventTrace::console());
+        }
+        None
+      }
+      None => None,
+    };
+    drop(state);
+    if let Some(previous) = previous_trace {
+      previous.stop();
+    }
+  }
+
+  /**
+   * Current values of the settings `configure` applies. The ones kept outside the dispatcher, `ignoreOsRepeat`
+   * and `ignoredModifiers`, are left unset.
+   */
+  pub fn options(&self) -> HotkOptions {
+    let state = self.lock_state();
+    let (queue_limit, queue_policy) = state.queue_limit.unwrap_or((0, QueuePolicy::DropNewest));
+
+    HotkOptions {
+      buffer_preinit: Some(state.buffer_preinit),
+      emit_lifecycle: Some(state.emit_lifecycle),
+      late_event_grace_ms: Some(state.late_grace.as_millis() as u32),
+      release_watchdog_ms: Some(
+        state
+          .release_timeout
+          .map_or(0, |timeout| timeout.as_millis() as u32),
+      ),
+      queue_limit: Some(queue_limit as u32),
+      queue_policy: Some(queue_policy),
+      ignore_os_repeat: None,
+      ignored_modifiers: None,
+      instance_name: Some(state.instance_name.clone().unwrap_or_default()),
+      console_trace: Some(state.console_trace.is_some()),
+    }
+  }
+
+  pub fn set_buffer_preinit(&self, enabled: bool) {
+    let mut state = self.lock_state();
+
+    state.buffer_preinit = enabled;
+    if !enabled {
+      state.preinit.clear();
+    }
+  }
+}
+
+pub fn event_object(env: Env, event: Event) -> napi::Result<JsObject> {
+  let mut obj = env.create_object()?;
+  obj.set("id", event.id)?;
+  obj.set("code", event.code)?;
+  obj.set("mods", event.mods)?;
+  obj.set("eventType", event.event_type)?;
+  obj.set("deltaMs", event.delta_ms)?;
+  obj.set("synthetic", event.synthetic)?;
+  obj.set("taps", event.taps)?;
+  obj.set("timestamp", event.timestamp)?;
+  Ok(obj)
+}
+
+/**
+ * Target of the `EventCallback`, runs on the JS thread each time it is woken.
+ */
+#[js_function(1)]
+pub fn deliver_events(ctx: CallContext) -> napi::Result<JsUndefined> {
+  if let Ok(hotk) = crate::HOTK.as_ref() {
+    let dispatcher = crate::lock_hotk(hotk).dispatcher.clone();
+    dispatcher.deliver(*ctx.env)?;
+  }
+
+  ctx.env.get_undefined()
+}
@@ -19,29 +19,116 @@ impl Response {
   pub fn to_napi(&self) -> HotkReponse {
     match self {
       Response::OkRegister { id } => HotkReponse {
+        operation: Operation::Register,
         code: ResponseCode::Ok,
         id: *id,
         error: None,
+        error_kind: None,
+        warnings: vec![],
+        warning: None,
       },
       Response::ErrorRegister { id, error } => HotkReponse {
+        operation: Operation::Register,
         code: ResponseCode::Error,
         id: *id,
         error: Some(error.to_string()),
+        error_kind: Some(ErrorKind::of(error)),
+        warnings: vec![],
+        warning: None,
       },
       Response::OkUnregister { id } => HotkReponse {
+        operation: Operation::Unregister,
         code: ResponseCode::Ok,
         id: *id,
         error: None,
+        error_kind: None,
+        warnings: vec![],
+        warning: None,
       },
       Response::ErrorUnregister { id, error } => HotkReponse {
+        operation: Operation::Unregister,
         code: ResponseCode::Error,
         id: *id,
         error: Some(error.to_string()),
+        error_kind: Some(ErrorKind::of(error)),
+        warnings: vec![],
+        warning: None,
       },
     }
   }
 }
 
+/**
+ * Identifies why a hotkey operation failed, so callers can branch on it rather than on the message.
+ *
+ * - `AlreadyRegistered`: The combination is already registered, by this or another application.
+ * - `NotRegistered`: The combination is not registered, so it can't be unregistered.
+ * - `OsFailure`: The OS denied the operation.
+ * - `Unsupported`: The key or combination can't be registered on this platform.
+ * - `InvalidArgument`: An argument is invalid, e.g. an unknown profile.
+ * - `IdCollision`: The id of the combination is already taken by a different combination.
+ */
+#[napi(string_enum)]
+pub enum ErrorKind {
+  AlreadyRegistered,
+  NotRegistered,
+  OsFailure,
+  Unsupported,
+  InvalidArgument,
+  IdCollision,
+}
+
+impl ErrorKind {
+  fn of(error: &global_hotkey::Error) -> Self {
+    match error {
+      global_hotkey::Error::AlreadyRegistered(_) => ErrorKind::AlreadyRegistered,
+      global_hotkey::Error::FailedToUnRegister(_) => ErrorKind::NotRegistered,
+      global_hotkey::Error::HotKeyParseError(_)
+      | global_hotkey::Error::UnrecognizedHotKeyCode(_)
+      | global_hotkey::Error::EmptyHotKeyToken(_)
+      | global_hotkey::Error::UnexpectedHotKeyFormat(_) => ErrorKind::Unsupported,
+      _ => ErrorKind::OsFailure,
+    }
+  }
+}
+
+/**
+ * Why the manager could not be created.
+ */
+pub enum CreateError {
+  // `global_hotkey` failed to set up the backend.
+  Backend(global_hotkey::Error),
+  // The backend thread exited before reporting it was ready.
+  BackendThread,
+  // The backend thread reported an id that isn't its own, so actions could never reach it.
+  InvalidThreadId(u32),
+  // Another backend thread of the process is still running.
+  BackendRunning,
+}
+
+impl std::fmt::Display for CreateError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      CreateError::Backend(error) => write!(f, "Failed to create the hotkey backend: {}", error),
+      CreateError::BackendThread => write!(f, "The hotkey backend thread exited during startup"),
+      CreateError::InvalidThreadId(id) => write!(f, "Invalid hotkey backend thread id {}", id),
+      CreateError::BackendRunning => write!(f, "The hotkey backend is already running"),
+    }
+  }
+}
+
+/**
+ * The hotkey operation a response describes.
+ *
+ * - `Register`: The response comes from registering a hotkey.
+ * - `Unregister`: The response comes from unregistering a hotkey.
+ */
+#[napi(string_enum)]
+pub enum Operation {
+  Register,
+  Unregister,
+}
+
 /**
  * Represents the possible response codes for hotkey operations.
  *
@@ -54,14 +141,177 @@ pub enum ResponseCode {
   Error,
 }
 
+/**
+ * Contention of a single lock, see `LockStats`.
+ *
+ * Properties:
+ * - `acquisitions` (number): Times the lock was taken.
+ * - `contended` (number): Times a taker had to wait because the lock was held elsewhere.
+ * - `maxWaitMs` (number): Longest of those waits, in milliseconds.
+ */
+#[napi(object)]
+pub struct LockCounters {
+  pub acquisitions: u32,
+  pub contended: u32,
+  pub max_wait_ms: f64,
+}
+
+/**
+ * Contention of the locks of the manager, see `HotkManager.lockStats`.
+ *
+ * Properties:
+ * - `manager` (LockCounters): The manager lock, taken by every method and by background registrations
+ *   (`applyKeymapAsync`, deferred registrations).
+ * - `dispatch` (LockCounters): The dispatch state, shared by the thread receiving OS events, the JS thread
+ *   delivering them and the methods changing how they are dispatched.
+ */
+#[napi(object)]
+pub struct LockStats {
+  pub manager: LockCounters,
+  pub dispatch: LockCounters,
+}
+
+/**
+ * Outcome of `HotkManager.startEventTrace`.
+ *
+ * Properties:
+ * - `code` (ResponseCode): `Ok` if the trace started, `Error` otherwise.
+ * - `error` (string, optional): Why the trace file could not be opened.
+ */
+#[napi(object)]
+pub struct TraceResult {
+  pub code: ResponseCode,
+  pub error: Option<String>,
+}
+
+/**
+ * Outcome of `HotkManager.init`.
+ *
+ * - `Installed`: The callback was installed and will receive events.
+ * - `Replaced`: The callback was installed in place of a previous one.
+ * - `Failed`: The callback could not be installed.
+ */
+#[napi(string_enum)]
+pub enum InitResult {
+  Installed,
+  Replaced,
+  Failed,
+}
+
+/**
+ * Outcome of `HotkManager.registerChecked`.
+ *
+ * - `Registered`: The hotkey was not registered and now is.
+ * - `AlreadyRegistered`: The hotkey was already registered; nothing was done.
+ * - `Failed`: The hotkey could not be registered.
+ */
+#[napi(string_enum)]
+pub enum RegistrationResult {
+  Registered,
+  AlreadyRegistered,
+  Failed,
+}
+
+/**
+ * Identifies the kind of a registration warning.
+ *
+ * - `NoModifier`: The hotkey has no modifiers, so the key is captured system-wide.
+ * - `RiskyKey`: The key is one other applications rely on (e.g. `Escape`, `Enter`).
+ * - `UnsupportedModifier`: A modifier is ignored by the OS on this platform.
+ * - `ReservedCombo`: The combination is reserved by the operating system.
+ * - `ImeKey`: The key is an input method or language key, usually consumed by the IME before it can fire.
+ */
+#[napi(string_enum)]
+pub enum WarningCode {
+  NoModifier,
+  RiskyKey,
+  UnsupportedModifier,
+  ReservedCombo,
+  ImeKey,
+}
+
+/**
+ * A non-fatal problem detected while registering a hotkey.
+ *
+ * Properties:
+ * - `code` (WarningCode): What the warning is about.
+ * - `severity` (number): How serious it is: `1` informative, `2` likely unwanted, `3` likely not to work.
+ * - `message` (string): Human-readable description.
+ */
+#[napi(object)]
+#[derive(Clone)]
+pub struct Warning {
+  pub code: WarningCode,
+  pub severity: u32,
+  pub message: String,
+}
+
+impl Warning {
+  pub fn new(code: WarningCode, message: String) -> Self {
+    let severity = match code {
+      WarningCode::NoModifier => 2,
+      WarningCode::RiskyKey => 2,
+      WarningCode::UnsupportedModifier => 2,
+      WarningCode::ReservedCombo => 3,
+      WarningCode::ImeKey => 3,
+    };
+
+    Self {
+      code,
+      severity,
+      message,
+    }
+  }
+}
+
 /**
  * Represents a response from a hotkey operation.
+ *
+ * `operation` tells whether it comes from a registration or an unregistration.
+ * On failure, `error` is a message for logging and `errorKind` tells what went wrong.
+ * `warnings` lists every non-fatal problem found for the hotkey; `warning` is the first message, if any.
  */
 #[napi]
 pub struct HotkReponse {
+  pub operation: Operation,
   pub code: ResponseCode,
   pub id: u32,
   pub error: Option<String>,
+  pub error_kind: Option<ErrorKind>,
+  pub warnings: Vec<Warning>,
+  pub warning: Option<String>,
+}
+
+impl HotkReponse {
+  pub fn ok(operation: Operation, id: u32) -> Self {
+    Self {
+      operation,
+      code: ResponseCode::Ok,
+      id,
+      error: None,
+      error_kind: None,
+      warnings: vec![],
+      warning: None,
+    }
+  }
+
+  pub fn error(operation: Operation, id: u32, kind: ErrorKind, error: String) -> Self {
+    Self {
+      operation,
+      code: ResponseCode::Error,
+      id,
+      error: Some(error),
+      error_kind: Some(kind),
+      warnings: vec![],
+      warning: None,
+    }
+  }
+
+  pub fn with_warnings(mut self, warnings: Vec<Warning>) -> Self {
+    self.warning = warnings.first().map(|warning| warning.message.clone());
+    self.warnings = warnings;
+    self
+  }
 }
 
 #[napi]
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use napi::threadsafe_function::ErrorStrategy;
+use napi::threadsafe_function::ThreadsafeFunction;
+use napi::threadsafe_function::ThreadsafeFunctionCallMode;
+use napi::Env;
+use napi::Task;
+
+use crate::code::get_hotkey_id;
+use crate::code::Desc;
+use crate::code::KeyCode;
+use crate::code::Mod;
+use crate::events::HotkReponse;
+use crate::support;
+use crate::InnerHotk;
+
+/**
+ * Progress of a keymap being applied in the background.
+ *
+ * Properties:
+ * - `done` (number): How many entries have been processed so far.
+ * - `total` (number): How many entries the keymap contains.
+ */
+#[napi(object)]
+pub struct KeymapProgress {
+  pub done: u32,
+  pub total: u32,
+}
+
+/**
+ * Registers every entry of a keymap off the JS thread, reporting progress as it goes.
+ */
+pub struct ApplyKeymap {
+  pub(crate) hotk: Arc<Mutex<InnerHotk>>,
+  pub(crate) descs: Vec<Desc>,
+  pub(crate) on_progress: Option<ThreadsafeFunction<KeymapProgress, ErrorStrategy::Fatal>>,
+}
+
+#[napi]
+impl Task for ApplyKeymap {
+  type Output = Vec<HotkReponse>;
+  type JsValue = Vec<HotkReponse>;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let total = self.descs.len() as u32;
+    let mut responses = Vec::with_capacity(self.descs.len());
+
+    for (index, desc) in self.descs.drain(..).enumerate() {
+      // The lock is taken per entry so other calls can interleave with a long apply.
+      responses.push(crate::lock_hotk(&self.hotk).register(desc.mods, desc.code));
+
+      if let Some(on_progress) = &self.on_progress {
+        on_progress.call(
+          KeymapProgress {
+            done: index as u32 + 1,
+            total,
+          },
+          ThreadsafeFunctionCallMode::NonBlocking,
+        );
+      }
+    }
+
+    Ok(responses)
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+/**
+ * Validation result for a single keymap entry.
+ *
+ * Properties:
+ * - `index` (number): Position of the entry in the validated keymap.
+ * - `valid` (boolean): Whether the entry can be registered as is.
+ * - `reason` (string | undefined): Why the entry is invalid.
+ */
+#[napi(object)]
+pub struct ValidationEntry {
+  pub index: u32,
+  pub valid: bool,
+  pub reason: Option<String>,
+}
+
+/**
+ * Result of validating a whole keymap.
+ *
+ * Properties:
+ * - `valid` (boolean): `true` when every entry is valid.
+ * - `entries` (ValidationEntry[]): One entry per validated descriptor, in order.
+ */
+#[napi(object)]
+pub struct ValidationReport {
+  pub valid: bool,
+  pub entries: Vec<ValidationEntry>,
+}
+
+/**
+ * Checks whether a combination can be registered at all, returning why it can't.
+ *
+ * A combination is rejected when its key is `Unidentified` or a modifier key (e.g. `ShiftLeft`), since no
+ * `HotKey` can be built from it. `register` runs the same check before reaching the OS.
+ * Platform support is left to the OS backend, see `validateKeymap` to check it upfront.
+ *
+ * @returns {string | null} The reason, or `null` if the combination is valid.
+ *
+ * @example
+ * ```js
+ * import { validateCombo, Mod, KeyCode } from '@hotk/core';
+ *
+ * validateCombo([Mod.Control], KeyCode.KeyA); // null
+ * validateCombo([Mod.Control], KeyCode.ShiftLeft); // "ShiftLeft is a modifier key"
+ * ```
+ */
+#[napi]
+pub fn validate_combo(_mods: Vec<Mod>, code: KeyCode) -> Option<String> {
+  // Every modifier set is valid, unsupported modifiers are only warned about.
+  if matches!(code, KeyCode::Unidentified) {
+    Some(String::from(
+      "Unidentified is not supported, it maps to no key",
+    ))
+  } else if code.is_modifier() {
+    Some(format!("{} is a modifier key", code))
+  } else {
+    None
+  }
+}
+
+/**
+ * Checks a whole keymap for problems without registering anything.
+ *
+ * Every entry is checked for:
+ * - A modifier key (e.g. `ShiftLeft`) used as the main key.
+ * - Keys or modifiers the current platform can't register.
+ * - Combinations reserved by the operating system.
+ * - Duplicates of an earlier entry.
+ *
+ * @example
+ * ```js
+ * import { validateKeymap, Mod, KeyCode } from '@hotk/core';
+ *
+ * const report = validateKeymap([
+ *   { mods: [Mod.Control], code: KeyCode.KeyA },
+ *   { mods: [Mod.Control], code: KeyCode.KeyA },
+ * ]);
+ *
+ * for (const entry of report.entries.filter((e) => !e.valid)) {
+ *   console.log(`#${entry.index}: ${entry.reason}`);
+ * }
+ * ```
+ */
+#[napi]
+#[cfg_attr(test, allow(dead_code))]
+pub fn validate_keymap(descs: Vec<Desc>) -> ValidationReport {
+  let mut seen: HashMap<u32, usize> = HashMap::new();
+
+  let entries: Vec<ValidationEntry> = descs
+    .iter()
+    .enumerate()
+    .map(|(index, desc)| {
+      let id = get_hotkey_id(desc.code, desc.mods.clone());
+
+      let reason = if let Some(reason) = validate_combo(desc.mods.clone(), desc.code) {
+        Some(reason)
+      } else if !support::key_supported(desc.code) {
+        Some(format!("{} is not supported on this platform", desc.code))
+      } else if let Some(m) = desc.mods.iter().find(|m| !support::mod_supported(**m)) {
+        Some(format!("{} is not supported on this platform", m))
+      } else if let Some(owner) = support::reserved_combo(&desc.mods, desc.code) {
+        Some(format!("Reserved by the operating system ({})", owner))
+      } else {
+        seen
+          .get(&id)
+          .map(|first| format!("Duplicate of entry {}", first))
+      };
+
+      seen.entry(id).or_insert(index);
+
+      ValidationEntry {
+        index: index as u32,
+        valid: reason.is_none(),
+        reason,
+      }
+    })
+    .collect();
+
+  ValidationReport {
+    valid: entries.iter().all(|entry| entry.valid),
+    entries,
+  }
+}
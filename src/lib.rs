@@ -1,56 +1,605 @@
 // #![deny(clippy::all)]
 
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::hotkey::Modifiers;
 use global_hotkey::GlobalHotKeyEvent;
+use global_hotkey::HotKeyState;
+use napi::bindgen_prelude::AsyncTask;
 use napi::threadsafe_function::ErrorStrategy;
+use napi::threadsafe_function::ThreadSafeCallContext;
 use napi::threadsafe_function::ThreadsafeFunction;
 use napi::threadsafe_function::ThreadsafeFunctionCallMode;
 use napi::Env;
 use napi::JsFunction;
+use napi::JsUndefined;
+use napi::JsUnknown;
+use napi::ValueType;
 use once_cell::sync::Lazy;
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::mpsc::Sender;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
 
+use crate::code::get_hotkey_id;
+use crate::code::ts_identifier;
+use crate::code::BackendPriority;
 use crate::code::Desc;
-use crate::code::Event;
+use crate::code::EventType;
 use crate::code::KeyCode;
 use crate::code::Mod;
+use crate::code::QueuePolicy;
+use crate::contention::Contention;
+use crate::deferred::DeferredCallback;
+use crate::dispatch::BatchCallback;
+use crate::dispatch::Dispatcher;
+use crate::dispatch::EventCallback;
+use crate::dispatch::HotkeyCallback;
+use crate::events::CreateError;
+use crate::events::ErrorKind;
 use crate::events::HotkReponse;
+use crate::events::InitResult;
+use crate::events::LockStats;
+use crate::events::Operation;
+use crate::events::RegistrationResult;
 use crate::events::Response;
+use crate::events::ResponseCode;
+use crate::events::TraceResult;
+use crate::events::Warning;
+use crate::keymap::ApplyKeymap;
+use crate::keymap::KeymapProgress;
+use crate::trace::EventTrace;
 
 mod code;
+mod contention;
+mod deferred;
+mod dispatch;
 mod events;
+mod keymap;
+mod permission;
+mod support;
+mod trace;
 
 #[cfg(target_os = "windows")]
 mod windows;
 #[cfg(target_os = "windows")]
+pub use windows::held_modifiers;
+#[cfg(target_os = "windows")]
+pub use windows::FailureHook;
+#[cfg(target_os = "windows")]
+pub use windows::LayoutHook;
+#[cfg(target_os = "windows")]
 pub use windows::Manager;
+#[cfg(target_os = "windows")]
+pub use windows::UnmappedHook;
 
 #[cfg(not(target_os = "windows"))]
 mod plain;
 #[cfg(not(target_os = "windows"))]
+pub use plain::held_modifiers;
+#[cfg(not(target_os = "windows"))]
+pub use plain::FailureHook;
+#[cfg(not(target_os = "windows"))]
+pub use plain::LayoutHook;
+#[cfg(not(target_os = "windows"))]
 pub use plain::Manager;
+#[cfg(not(target_os = "windows"))]
+pub use plain::UnmappedHook;
 
 #[macro_use]
 extern crate napi_derive;
 
-static HOTK: Lazy<Option<Arc<Mutex<InnerHotk>>>> =
+static HOTK: Lazy<Result<Arc<Mutex<InnerHotk>>, CreateError>> =
   Lazy::new(|| InnerHotk::create().map(|hotk| Arc::new(Mutex::new(hotk))));
 
+static HOTK_CONTENTION: Contention = Contention::new();
+
+/**
+ * Takes the manager lock, counting how often it is found held, see `HotkManager.lockStats`.
+ */
+fn lock_hotk(hotk: &Mutex<InnerHotk>) -> MutexGuard<'_, InnerHotk> {
+  HOTK_CONTENTION.lock(hotk)
+}
+
 struct InnerHotk {
   pub manager: Manager,
   pub hotkeys: Arc<std::sync::Mutex<HashMap<u32, Desc>>>,
-  pub tsfn: Option<ThreadsafeFunction<Event, ErrorStrategy::Fatal>>,
+  pub dispatcher: Arc<Dispatcher>,
+  pub profiles: HashMap<String, Vec<Desc>>,
+  pub profile_callbacks: HashMap<String, HashMap<u32, HotkeyCallback>>,
+  pub active_profile: Option<String>,
+  pub deferred: Option<Sender<Desc>>,
+  pub on_deferred_result: Option<DeferredCallback>,
+  // Number of logical registrations sharing each OS registration.
+  pub registrants: HashMap<u32, u32>,
+  // Whether our global event handler is the one `global_hotkey` calls.
+  pub handler_installed: bool,
+  // Registered hotkeys whose OS grab was released by `release_os_grabs`.
+  pub released: HashSet<u32>,
+  // Modifiers stripped from registrations and live state comparisons.
+  pub ignored_modifiers: Vec<Mod>,
+  // Steps registered by each sequence, by sequence id.
+  pub sequences: HashMap<u32, Vec<Desc>>,
+}
+
+/**
+ * Id of the sequence of hotkeys `steps`: an FNV-1a hash of their ids, with the top bit set so it never
+ * collides with the id of a single combination.
+ */
+fn sequence_id(steps: &[u32]) -> u32 {
+  let hash = steps
+    .iter()
+    .flat_map(|id| id.to_le_bytes())
+    .fold(0x811c_9dc5u32, |hash, byte| {
+      (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    });
+  hash | 0x8000_0000
+}
+
+/**
+ * Lock modifiers, which laptops often report as held and would otherwise cause spurious mismatches.
+ */
+const LOCK_MODIFIERS: [Mod; 5] = [
+  Mod::CapsLock,
+  Mod::NumLock,
+  Mod::ScrollLock,
+  Mod::FnLock,
+  Mod::SymbolLock,
+];
+
+/**
+ * Installs the global event handler, returning whether it took effect.
+ *
+ * `global_hotkey` keeps the handler in a `OnceCell` and silently ignores later attempts, e.g. when another
+ * component of the process set it first. A rejected handler is dropped right away, which is detected through
+ * the reference count of a marker it captures. With the `test-hooks` feature, setting `HOTK_FAIL_EVENT_HANDLER`
+ * skips the installation, to exercise this path.
+ */
+fn install_event_handler(dispatcher: Arc<Dispatcher>) -> bool {
+  #[cfg(feature = "test-hooks")]
+  if std::env::var_os("HOTK_FAIL_EVENT_HANDLER").is_some() {
+    return false;
+  }
+
+  let marker = Arc::new(());
+  let handler_marker = marker.clone();
+  GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
+    let _ = &handler_marker;
+    dispatcher.dispatch(event, false);
+  }));
+
+  Arc::strong_count(&marker) > 1
 }
 
 impl InnerHotk {
-  pub fn create() -> Option<Self> {
-    Manager::new().map(|manager| Self {
-      manager,
-      hotkeys: Default::default(),
-      tsfn: None,
+  /**
+   * Creates the backend. With the `test-hooks` feature, setting `HOTK_FAIL_CREATE` makes it fail, to exercise
+   * this path.
+   */
+  pub fn create() -> Result<Self, CreateError> {
+    #[cfg(feature = "test-hooks")]
+    if std::env::var_os("HOTK_FAIL_CREATE").is_some() {
+      let error = std::io::Error::other("simulated by HOTK_FAIL_CREATE");
+      return Err(CreateError::Backend(error.into()));
+    }
+
+    Manager::new().map(|manager| {
+      let hotkeys: Arc<Mutex<HashMap<u32, Desc>>> = Default::default();
+      let dispatcher = Arc::new(Dispatcher::new(hotkeys.clone()));
+
+      let handler_installed = install_event_handler(dispatcher.clone());
+      dispatcher.set_ignored_modifiers(&LOCK_MODIFIERS);
+
+      Self {
+        manager,
+        hotkeys,
+        dispatcher,
+        profiles: Default::default(),
+        profile_callbacks: Default::default(),
+        active_profile: None,
+        deferred: None,
+        on_deferred_result: None,
+        registrants: Default::default(),
+        handler_installed,
+        released: Default::default(),
+        ignored_modifiers: LOCK_MODIFIERS.to_vec(),
+        sequences: Default::default(),
+      }
     })
   }
+
+  /**
+   * Drops the ignored modifiers from `mods`.
+   */
+  pub fn strip_ignored(&self, mods: Vec<Mod>) -> Vec<Mod> {
+    mods
+      .into_iter()
+      .filter(|m| !self.ignored_modifiers.contains(m))
+      .collect()
+  }
+
+  pub fn register(&mut self, mods: Vec<Mod>, code: KeyCode) -> HotkReponse {
+    self.register_many(vec![Desc::new(code, mods)]).remove(0)
+  }
+
+  /**
+   * Registers every combination, handing them to the backend in one go. Responses are index-aligned with
+   * `descs`, and a failure doesn't undo the others.
+   */
+  pub fn register_many(&mut self, descs: Vec<Desc>) -> Vec<HotkReponse> {
+    let mut responses: Vec<Option<HotkReponse>> = Vec::with_capacity(descs.len());
+    let mut valid: Vec<(usize, u32, Desc, Vec<Warning>)> = vec![];
+
+    for (index, desc) in descs.into_iter().enumerate() {
+      let warnings = support::warnings(&desc.mods, desc.code);
+      let mods = self.strip_ignored(desc.mods);
+      let id = get_hotkey_id(desc.code, mods.clone());
+
+      match keymap::validate_combo(mods.clone(), desc.code) {
+        Some(reason) => responses.push(Some(HotkReponse::error(
+          Operation::Register,
+          id,
+          ErrorKind::Unsupported,
+          reason,
+        ))),
+        None => {
+          responses.push(None);
+          valid.push((index, id, Desc::new(desc.code, mods), warnings));
+        }
+      }
+    }
+
+    // Index of the entry registering each id at the OS level.
+    let mut first: HashMap<u32, usize> = HashMap::new();
+    let mut pending: Vec<(usize, Desc, Vec<Warning>)> = vec![];
+    // Entries repeating a combination registered earlier in `descs`.
+    let mut repeated: Vec<(usize, u32, Vec<Warning>)> = vec![];
+
+    for (index, id, desc, warnings) in valid {
+      // Never overwrite the entry of a different combination.
+      let taken = self.hotkeys.lock().unwrap().get(&id).map(Desc::to_hotkey);
+      if let Some(taken) = taken.filter(|taken| *taken != desc.to_hotkey()) {
+        responses[index] = Some(HotkReponse::error(
+          Operation::Register,
+          id,
+          ErrorKind::IdCollision,
+          format!("Id {} is already taken by {}", id, taken),
+        ));
+      } else if let Some(count) = self.registrants.get_mut(&id) {
+        *count += 1;
+        responses[index] = Some(HotkReponse::ok(Operation::Register, id).with_warnings(warnings));
+      } else if let Entry::Vacant(entry) = first.entry(id) {
+        entry.insert(index);
+        pending.push((index, desc, warnings));
+      } else {
+        repeated.push((index, id, warnings));
+      }
+    }
+
+    let registered = self.manager.register_many(
+      pending
+        .iter()
+        .map(|(_, desc, _)| {
+          (
+            desc.mods.iter().map(|m| m.global_hotkeys()).collect(),
+            desc.code.global_hotkeys(),
+          )
+        })
+        .collect(),
+    );
+
+    for ((index, desc, warnings), (hotkey, response)) in pending.into_iter().zip(registered) {
+      if let Response::OkRegister { .. } = response {
+        self.registrants.insert(hotkey.id, 1);
+        // New registrations start armed.
+        self.dispatcher.arm(hotkey.id);
+        self.hotkeys.lock().unwrap().insert(hotkey.id, desc.clone());
+        self
+          .dispatcher
+          .lifecycle(hotkey.id, desc, EventType::Registered);
+      }
+      responses[index] = Some(response.to_napi().with_warnings(warnings));
+    }
+
+    for (index, id, warnings) in repeated {
+      let response = match self.registrants.get_mut(&id) {
+        Some(count) => {
+          *count += 1;
+          HotkReponse::ok(Operation::Register, id)
+        }
+        None => {
+          // Repeats fail the same way as the first entry.
+          let first = responses[first[&id]].as_ref().unwrap();
+          let kind = first.error_kind.unwrap_or(ErrorKind::OsFailure);
+          let error = first.error.clone().unwrap_or_default();
+          HotkReponse::error(Operation::Register, id, kind, error)
+        }
+      };
+      responses[index] = Some(response.with_warnings(warnings));
+    }
+
+    responses.into_iter().flatten().collect()
+  }
+
+  /**
+   * Registers `mods` + `code` unless it is already registered, in one go so no other registration
+   * can happen in between.
+   */
+  pub fn register_checked(&mut self, mods: Vec<Mod>, code: KeyCode) -> RegistrationResult {
+    let mods = self.strip_ignored(mods);
+    let id = get_hotkey_id(code, mods.clone());
+
+    if self.hotkeys.lock().unwrap().contains_key(&id) {
+      RegistrationResult::AlreadyRegistered
+    } else if self.register(mods, code).is_ok() {
+      RegistrationResult::Registered
+    } else {
+      RegistrationResult::Failed
+    }
+  }
+
+  pub fn unregister(&mut self, mods: Vec<Mod>, code: KeyCode) -> HotkReponse {
+    let mods = self.strip_ignored(mods);
+    let id = get_hotkey_id(code, mods.clone());
+
+    // Other registrants still rely on the OS registration.
+    if let Some(count) = self.registrants.get_mut(&id) {
+      if *count > 1 {
+        *count -= 1;
+        return HotkReponse::ok(Operation::Unregister, id);
+      }
+    }
+
+    self.remove(id, &mods, code)
+  }
+
+  /**
+   * Unregisters every hotkey regardless of its registrants, sorted by id. Those that fail stay registered,
+   * so a later call can retry them.
+   */
+  pub fn unregister_all(&mut self) -> Vec<HotkReponse> {
+    let mut descs: Vec<(u32, Desc)> = self
+      .hotkeys
+      .lock()
+      .unwrap()
+      .iter()
+      .map(|(id, desc)| (*id, desc.clone()))
+      .collect();
+    descs.sort_by_key(|(id, _)| *id);
+
+    descs
+      .into_iter()
+      .map(|(id, desc)| self.remove(id, &desc.mods, desc.code))
+      .collect()
+  }
+
+  /**
+   * Drops the OS registration of `id` along with every registrant.
+   */
+  fn remove(&mut self, id: u32, mods: &[Mod], code: KeyCode) -> HotkReponse {
+    // The OS grab is already gone.
+    if self.released.remove(&id) {
+      self.registrants.remove(&id);
+      self.dispatcher.forget(id);
+      if let Some(desc) = self.hotkeys.lock().unwrap().remove(&id) {
+        self.dispatcher.lifecycle(id, desc, EventType::Unregistered);
+      }
+      return HotkReponse::ok(Operation::Unregister, id);
+    }
+
+    let (hotkey, response) = self.manager.unregister(
+      mods.iter().map(|m| m.global_hotkeys()).collect(),
+      code.global_hotkeys(),
+    );
+
+    if let Response::OkUnregister { .. } = response {
+      self.registrants.remove(&hotkey.id);
+      self.dispatcher.forget(hotkey.id);
+      let removed = self.hotkeys.lock().unwrap().remove(&hotkey.id);
+      if let Some(desc) = removed {
+        self
+          .dispatcher
+          .lifecycle(hotkey.id, desc.clone(), EventType::Unregistered);
+        self.dispatcher.tombstone(hotkey.id, desc);
+      }
+    }
+
+    response.to_napi()
+  }
+
+  /**
+   * Unregisters every hotkey at the OS level, keeping it registered here so `reacquire_os_grabs` can
+   * restore it.
+   */
+  pub fn release_os_grabs(&mut self) -> Vec<HotkReponse> {
+    let mut descs: Vec<(u32, Desc)> = self
+      .hotkeys
+      .lock()
+      .unwrap()
+      .iter()
+      .filter(|(id, _)| !self.released.contains(id))
+      .map(|(id, desc)| (*id, desc.clone()))
+      .collect();
+    descs.sort_by_key(|(id, _)| *id);
+
+    descs
+      .into_iter()
+      .map(|(id, desc)| {
+        let (_, response) = self.manager.unregister(
+          desc.mods.iter().map(|m| m.global_hotkeys()).collect(),
+          desc.code.global_hotkeys(),
+        );
+        if let Response::OkUnregister { .. } = response {
+          self.released.insert(id);
+        }
+        response.to_napi()
+      })
+      .collect()
+  }
+
+  /**
+   * Registers again at the OS level the hotkeys released by `release_os_grabs`.
+   * Those that fail stay released, so a later call can retry them.
+   */
+  pub fn reacquire_os_grabs(&mut self) -> Vec<HotkReponse> {
+    let mut ids: Vec<u32> = self.released.iter().copied().collect();
+    ids.sort();
+
+    let hotkeys = self.hotkeys.lock().unwrap().clone();
+    ids
+      .into_iter()
+      .filter_map(|id| hotkeys.get(&id).map(|desc| (id, desc)))
+      .map(|(id, desc)| {
+        let (_, response) = self.manager.register(
+          desc.mods.iter().map(|m| m.global_hotkeys()).collect(),
+          desc.code.global_hotkeys(),
+        );
+        if let Response::OkRegister { .. } = response {
+          self.released.remove(&id);
+        }
+        response.to_napi()
+      })
+      .collect()
+  }
+
+  /**
+   * Restarts the backend, then registers again the hotkeys that weren't released by `release_os_grabs`.
+   * Those that fail are marked released, so `reacquire_os_grabs` retries them. Returns the failures.
+   */
+  pub fn recreate(&mut self) -> Result<Vec<HotkReponse>, CreateError> {
+    if !self.manager.recreate()? {
+      return Ok(vec![]);
+    }
+
+    let hotkeys = self.hotkeys.lock().unwrap().clone();
+    let mut ids: Vec<u32> = hotkeys
+      .keys()
+      .filter(|id| !self.released.contains(id))
+      .copied()
+      .collect();
+    ids.sort();
+
+    Ok(
+      ids
+        .into_iter()
+        .filter_map(|id| {
+          let desc = &hotkeys[&id];
+          let (_, response) = self.manager.register(
+            desc.mods.iter().map(|m| m.global_hotkeys()).collect(),
+            desc.code.global_hotkeys(),
+          );
+          if let Response::OkRegister { .. } = response {
+            return None;
+          }
+          self.released.insert(id);
+          Some(response.to_napi())
+        })
+        .collect(),
+    )
+  }
+
+  /**
+   * Registers every step of a sequence, then has the dispatcher watch for them being pressed in order.
+   * If a step fails, the steps registered before it are unregistered and its response is returned.
+   */
+  pub fn register_sequence(&mut self, steps: Vec<Desc>, window: Duration) -> HotkReponse {
+    let descs: Vec<Desc> = steps
+      .into_iter()
+      .map(|desc| Desc::new(desc.code, self.strip_ignored(desc.mods)))
+      .collect();
+    let ids: Vec<u32> = descs
+      .iter()
+      .map(|desc| get_hotkey_id(desc.code, desc.mods.clone()))
+      .collect();
+    let id = sequence_id(&ids);
+
+    if descs.len() < 2 {
+      return HotkReponse::error(
+        Operation::Register,
+        id,
+        ErrorKind::InvalidArgument,
+        format!("A sequence needs at least 2 steps, got {}", descs.len()),
+      );
+    }
+    if let Some(registered) = self.sequences.get(&id) {
+      let same_steps = registered
+        .iter()
+        .map(Desc::to_hotkey)
+        .eq(descs.iter().map(Desc::to_hotkey));
+      if !same_steps {
+        return HotkReponse::error(
+          Operation::Register,
+          id,
+          ErrorKind::IdCollision,
+          format!("Sequence id {} is taken by another sequence", id),
+        );
+      }
+      self.dispatcher.set_sequence(id, ids, window);
+      return HotkReponse::ok(Operation::Register, id);
+    }
+
+    for (index, desc) in descs.iter().enumerate() {
+      let response = self.register(desc.mods.clone(), desc.code);
+      if !response.is_ok() {
+        for registered in &descs[..index] {
+          self.unregister(registered.mods.clone(), registered.code);
+        }
+        return response;
+      }
+    }
+
+    self.dispatcher.set_sequence(id, ids, window);
+    self.sequences.insert(id, descs);
+    HotkReponse::ok(Operation::Register, id)
+  }
+
+  /**
+   * Stops watching sequence `id` and unregisters its steps. Returns `false` if it is not registered.
+   */
+  pub fn unregister_sequence(&mut self, id: u32) -> bool {
+    let Some(descs) = self.sequences.remove(&id) else {
+      return false;
+    };
+
+    self.dispatcher.remove_sequence(id);
+    for desc in descs {
+      self.unregister(desc.mods, desc.code);
+    }
+    true
+  }
+
+  /**
+   * Unregisters the active profile's hotkeys, then registers the ones of `name`.
+   */
+  pub fn activate_profile(&mut self, name: &str) -> Option<Vec<HotkReponse>> {
+    let descs = self.profiles.get(name)?.clone();
+
+    if let Some(active) = self.active_profile.take() {
+      for desc in self.profiles.get(&active).cloned().unwrap_or_default() {
+        self.unregister(desc.mods, desc.code);
+      }
+    }
+
+    let callbacks = self
+      .profile_callbacks
+      .get(name)
+      .cloned()
+      .unwrap_or_default();
+    self.dispatcher.set_hotkey_callbacks(callbacks);
+    self.active_profile = Some(name.to_string());
+
+    Some(
+      descs
+        .into_iter()
+        .map(|desc| self.register(desc.mods, desc.code))
+        .collect(),
+    )
+  }
 }
 
 /**
@@ -67,9 +616,14 @@ impl HotkManager {
    * Initializes the hotkey event listener.
    *
    * This method sets up the callback that will be invoked for every global hotkey event.
-   * It can only be called once per program. Subsequent calls will do nothing and return `false`.
+   * Only one callback is active: calling it again replaces the installed callback, e.g. when switching
+   * contexts. Events not delivered yet go to the new one, which keeps the `ref`/`unref` state of the
+   * callback it replaces.
    *
    * @param on_event - A function that will be called with each hotkey event.
+   * @returns {InitResult} `Installed` on success, `Replaced` if it replaced a previous callback,
+   * `Failed` if `on_event` is not a function, the listener could not be created, or the global event
+   * handler could not be installed (e.g. another component of the process installed its own).
    *
    * @example
    * ```js
@@ -82,60 +636,49 @@ impl HotkManager {
    * const result = manager.register([Mod.Control], KeyCode.KeyA);
    *
    * // Listen for hotkey events
-   * manager.init((event) => {
+   * const status = manager.init((event) => {
    *   console.log('Received event:', event);
    * });
    * ```
    */
   #[napi(ts_args_type = "on_event: (event: Event) => void")]
-  pub fn init(&mut self, on_event: JsFunction) -> napi::Result<bool> {
-    let mut lock = self.hotk.lock().unwrap();
+  pub fn init(&mut self, env: Env, on_event: JsUnknown) -> InitResult {
+    let lock = lock_hotk(&self.hotk);
 
-    if lock.tsfn.is_some() {
-      return Ok(false);
+    // Events would never reach the callback.
+    if !lock.handler_installed {
+      return InitResult::Failed;
     }
 
-    let hotkeys = lock.hotkeys.clone();
+    let installed = (|| -> napi::Result<bool> {
+      if on_event.get_type()? != ValueType::Function {
+        return Err(napi::Error::from_reason("on_event is not a function"));
+      }
 
-    if let Some(tsfn) = lock.tsfn.take() {
-      tsfn.abort()?;
-    }
+      let callback = env.create_reference(on_event)?;
+      let tsfn: EventCallback = env
+        .create_function("deliverEvents", dispatch::deliver_events)?
+        .create_threadsafe_function(0, |_| Ok(Vec::<JsUndefined>::new()))?;
 
-    let tsfn: ThreadsafeFunction<Event, ErrorStrategy::Fatal> = on_event
-      .create_threadsafe_function(0, |ctx| {
-        let event: Event = ctx.value;
-        ctx.env.create_object().and_then(|mut obj| {
-          obj.set("id", event.id)?;
-          obj.set("code", event.code)?;
-          obj.set("mods", event.mods)?;
-          obj.set("eventType", event.event_type)?;
-          Ok(vec![obj])
-        })
-      })?;
-
-    lock.tsfn = Some(tsfn.clone());
-
-    GlobalHotKeyEvent::set_event_handler(Some(move |event: GlobalHotKeyEvent| {
-      if let Some(desc) = hotkeys.lock().unwrap().get(&event.id).cloned() {
-        let ev = Event {
-          id: event.id,
-          code: desc.code,
-          mods: desc.mods,
-          event_type: match event.state {
-            global_hotkey::HotKeyState::Pressed => code::EventType::Pressed,
-            global_hotkey::HotKeyState::Released => code::EventType::Released,
-          },
-        };
-        tsfn.call(ev, ThreadsafeFunctionCallMode::NonBlocking);
-      }
-    }));
+      lock.dispatcher.set_callback(env, tsfn, callback)
+    })();
 
-    Ok(true)
+    match installed {
+      Ok(false) => InitResult::Installed,
+      Ok(true) => InitResult::Replaced,
+      Err(_) => InitResult::Failed,
+    }
   }
 
   /**
    * Registers a global hotkey.
    *
+   * Registering a combination that is already registered succeeds without registering it again with the OS;
+   * registrations are counted and the OS registration is released by the last `unregister`. With `idempotent`,
+   * the existing registration is returned as is instead.
+   *
+   * @param {RegisterOptions} [options] - Optional matching behaviour.
+   *
    * @example
    * ```js
    * import { hotk, Mod, KeyCode } from '@hotk/core';
@@ -159,121 +702,162 @@ impl HotkManager {
    * ```
    */
   #[napi]
-  pub fn register(&self, mods: Vec<Mod>, code: KeyCode) -> HotkReponse {
-    let lock = self.hotk.lock().unwrap();
-
-    let (hotkey, response) = lock.manager.register(
-      mods.iter().map(|m| m.global_hotkeys()).collect(),
-      code.global_hotkeys(),
-    );
+  pub fn register(
+    &self,
+    mods: Vec<Mod>,
+    code: KeyCode,
+    options: Option<RegisterOptions>,
+  ) -> HotkReponse {
+    let exact = options
+      .as_ref()
+      .and_then(|o| o.exact_modifiers)
+      .unwrap_or(false);
+    let idempotent = options.and_then(|o| o.idempotent).unwrap_or(false);
 
-    if let Response::OkRegister { .. } = response {
-      lock
-        .hotkeys
-        .lock()
-        .unwrap()
-        .insert(hotkey.id, Desc::new(code, mods));
+    let mut lock = lock_hotk(&self.hotk);
+    let id = get_hotkey_id(code, lock.strip_ignored(mods.clone()));
+    let response = if idempotent && lock.registrants.contains_key(&id) {
+      HotkReponse::ok(Operation::Register, id).with_warnings(support::warnings(&mods, code))
+    } else {
+      lock.register(mods, code)
+    };
+    if exact && response.is_ok() {
+      lock.dispatcher.set_exact(response.id);
     }
-
-    response.to_napi()
+    response
   }
 
   /**
-   * Unregisters a global hotkey.
+   * Registers several hotkeys at once, taking the lock and notifying the backend a single time.
+   *
+   * Responses are in the same order as `descs`. Hotkeys that register stay registered even if others fail.
    *
    * @example
    * ```js
-   * import { hotk, Mod, KeyCode } from '@hotk/core';
+   * import { hotk, KeyCode, Mod } from '@hotk/core';
    *
-   * // Get the singleton instance
    * const manager = hotk();
+   * const descs = [
+   *   { mods: [Mod.Control], code: KeyCode.KeyS },
+   *   { mods: [Mod.Control], code: KeyCode.KeyO },
+   * ];
+   * manager.registerMany(descs).forEach((response, i) => {
+   *   if (!response.isOk()) console.error(descs[i], response.error);
+   * });
+   * ```
+   */
+  #[napi]
+  pub fn register_many(&self, descs: Vec<Desc>) -> Vec<HotkReponse> {
+    lock_hotk(&self.hotk).register_many(descs)
+  }
+
+  /**
+   * Registers a hotkey unless it is already registered, checking and registering under a single lock hold.
    *
-   * // Register Ctrl + A as a hotkey
-   * const result = manager.register([Mod.Control], KeyCode.KeyA);
+   * Unlike calling `isRegistered` then `register`, two callers racing on the same combination can't both
+   * register it: exactly one gets `Registered`. An already registered combination is left untouched, its
+   * registration count included.
    *
-   * // Listen for hotkey events
-   * manager.init((event) => {
-   *   console.log('Received event:', event);
-   * });
+   * @example
+   * ```js
+   * import { hotk, KeyCode, Mod, RegistrationResult } from '@hotk/core';
    *
-   * // Unregister the hotkey after 3 seconds
-   * setTimeout(() => {
-   *   manager.unregister([Mod.Control], KeyCode.KeyA);
-   * }, 3000);
+   * const manager = hotk();
+   * if (manager.registerChecked([Mod.Control], KeyCode.KeyS) === RegistrationResult.Failed) {
+   *   console.error('Could not register Control+S');
+   * }
    * ```
    */
   #[napi]
-  pub fn unregister(&self, mods: Vec<Mod>, code: KeyCode) -> HotkReponse {
-    let lock = self.hotk.lock().unwrap();
-
-    let (hotkey, response) = lock.manager.unregister(
-      mods.iter().map(|m| m.global_hotkeys()).collect(),
-      code.global_hotkeys(),
-    );
-
-    if let Response::OkUnregister { .. } = response {
-      lock.hotkeys.lock().unwrap().remove(&hotkey.id);
-    }
-
-    response.to_napi()
+  pub fn register_checked(&self, mods: Vec<Mod>, code: KeyCode) -> RegistrationResult {
+    lock_hotk(&self.hotk).register_checked(mods, code)
   }
 
   /**
-   * Allows the Node.js process to exit naturally if no other tasks are pending.
-   *
-   * This detaches the internal hotkey event callback from the event loop,
-   * meaning the process won't be held open just to listen for hotkey events.
+   * Registers a global hotkey like `register`, returning a handle that unregisters exactly this registration.
    *
-   * @returns `true` if the operation succeeded, `false` otherwise.
+   * Check `registered` or `error` on the handle to know whether registering succeeded.
    *
    * @example
    * ```js
+   * import { hotk, KeyCode, Mod } from '@hotk/core';
+   *
    * const manager = hotk();
-   * manager.init(console.log);
-   * manager.unref(); // Now the process can exit if nothing else is running.
+   * const handle = manager.registerHandle([Mod.Control], KeyCode.KeyS);
+   * if (!handle.registered) console.error(handle.error);
+   *
+   * // Later, without remembering Control+S.
+   * handle.unregister();
    * ```
    */
   #[napi]
-  pub fn unref(&self, env: Env) -> bool {
-    let mut lock = self.hotk.lock().unwrap();
+  pub fn register_handle(&self, mods: Vec<Mod>, code: KeyCode) -> RegistrationHandle {
+    let response = lock_hotk(&self.hotk).register(mods.clone(), code);
 
-    if let Some(tsfn) = &mut lock.tsfn {
-      tsfn.unref(&env).is_ok()
-    } else {
-      false
+    RegistrationHandle {
+      hotk: self.hotk.clone(),
+      desc: Desc::new(code, mods),
+      registered: response.is_ok(),
+      response,
     }
   }
 
   /**
-   * Keeps the Node.js process alive to continue listening for hotkey events.
-   *
-   * This ensures that the process will not exit until `destroy()` is called
-   * or `unref()` is used to detach the event callback from the event loop.
+   * Registers a hotkey from the name of a `global-hotkey` key code (e.g. `"MediaPlayPause"`), for bindings
+   * stored as plain strings.
    *
-   * @returns `true` if the operation succeeded, `false` otherwise.
+   * `KeyCode` mirrors every code of the `global-hotkey` version this build ships with, so this behaves like
+   * `register` with the matching `KeyCode`. Names are matched exactly, like `KeyCode` variants; a name the
+   * backend doesn't recognize fails with `InvalidArgument` and id `0`. Codes without a `KeyCode` counterpart,
+   * which only a newer backend could add, are not registered: events and `listRegistered` report a `Desc`, which
+   * could not describe them. They fail with `Unsupported` and the id they would register as.
    *
    * @example
    * ```js
+   * import { hotk, Mod } from '@hotk/core';
+   *
    * const manager = hotk();
-   * manager.init(console.log);
-   * manager.refer(); // Keeps the process alive
+   * const response = manager.registerRaw([Mod.Control], 'MediaPlayPause');
+   * if (!response.isOk()) console.error(response.error);
    * ```
    */
   #[napi]
-  pub fn refer(&self, env: Env) -> bool {
-    let mut lock = self.hotk.lock().unwrap();
+  pub fn register_raw(&self, mods: Vec<Mod>, code_name: String) -> HotkReponse {
+    let code = match global_hotkey::hotkey::Code::from_str(&code_name) {
+      Ok(code) => code,
+      Err(_) => {
+        return HotkReponse::error(
+          Operation::Register,
+          0,
+          ErrorKind::InvalidArgument,
+          format!("Unknown key code {}", code_name),
+        )
+      }
+    };
 
-    if let Some(tsfn) = &mut lock.tsfn {
-      tsfn.refer(&env).is_ok()
-    } else {
-      false
+    match KeyCode::from_global_hotkeys(code) {
+      Some(code) => lock_hotk(&self.hotk).register(mods, code),
+      None => HotkReponse::error(
+        Operation::Register,
+        HotKey::new(
+          Some(
+            mods
+              .iter()
+              .fold(Modifiers::empty(), |acc, m| acc | m.global_hotkeys()),
+          ),
+          code,
+        )
+        .id,
+        ErrorKind::Unsupported,
+        format!("Key code {} has no KeyCode counterpart", code_name),
+      ),
     }
   }
 
   /**
-   * Stops listening for hotkey events.
+   * Unregisters a global hotkey.
    *
-   * This is required to allow the Node.js process to exit when `init` was called without `unref: true`.
+   * A combination registered several times stays registered until it is unregistered as many times.
    *
    * @example
    * ```js
@@ -285,43 +869,2119 @@ impl HotkManager {
    * // Register Ctrl + A as a hotkey
    * const result = manager.register([Mod.Control], KeyCode.KeyA);
    *
-   * if (result.isOk()) {
-   *   console.log('Hotkey successfully registered');
-   * } else {
-   *   console.error('Failed to register hotkey');
-   * }
-   *
-   * // Start listening for hotkey events without unref
+   * // Listen for hotkey events
    * manager.init((event) => {
    *   console.log('Received event:', event);
    * });
    *
-   * // Stop listening after 3 seconds so the process can exit
+   * // Unregister the hotkey after 3 seconds
    * setTimeout(() => {
-   *   manager.destroy();
+   *   manager.unregister([Mod.Control], KeyCode.KeyA);
    * }, 3000);
    * ```
    */
   #[napi]
-  pub fn destroy(&mut self, env: Env) -> napi::Result<()> {
-    self.unref(env);
-    Ok(())
+  pub fn unregister(&self, mods: Vec<Mod>, code: KeyCode) -> HotkReponse {
+    lock_hotk(&self.hotk).unregister(mods, code)
   }
-}
 
-/**
- * Returns a singleton instance of `HotkManager`.
- *
- * All `HotkManager` instances share the same internal state, allowing global coordination of hotkeys across your application.
- *
- * @example
- * ```js
- * import { hotk } from '@hotk/core';
- *
- * const manager = hotk();
- * ```
- */
-#[napi]
-pub fn hotk() -> Option<HotkManager> {
-  HOTK.clone().map(|hotk| HotkManager { hotk })
+  /**
+   * Sets the modifiers stripped from registrations and from the live state comparison of `exactModifiers`,
+   * so states like NumLock or Fn held on laptops don't cause spurious mismatches. Defaults to the lock
+   * modifiers (`CapsLock`, `NumLock`, `ScrollLock`, `FnLock` and `SymbolLock`).
+   *
+   * A registration that intentionally uses an ignored modifier registers without it, so
+   * `[Mod.Control, Mod.NumLock]` + `KeyA` becomes `Control+KeyA` and shares its id. Only later registrations
+   * are affected; pass `[]` to keep every modifier.
+   *
+   * @example
+   * ```js
+   * import { hotk, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setIgnoredModifiers([Mod.CapsLock, Mod.NumLock, Mod.Fn]);
+   * ```
+   */
+  #[napi]
+  pub fn set_ignored_modifiers(&self, mods: Vec<Mod>) {
+    let mut lock = lock_hotk(&self.hotk);
+    lock.dispatcher.set_ignored_modifiers(&mods);
+    lock.ignored_modifiers = mods;
+  }
+
+  /**
+   * Unregisters every hotkey, e.g. to tear down the bindings before installing a new set.
+   *
+   * Combinations registered several times are removed at once. Hotkeys that fail to unregister stay
+   * registered, so calling this again retries them.
+   *
+   * @returns One response per hotkey, sorted by id.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const failed = manager.unregisterAll().filter(response => !response.isOk());
+   * ```
+   */
+  #[napi]
+  pub fn unregister_all(&self) -> Vec<HotkReponse> {
+    lock_hotk(&self.hotk).unregister_all()
+  }
+
+  /**
+   * Checks whether a combination is registered, without touching the OS.
+   *
+   * @example
+   * ```js
+   * import { hotk, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * button.disabled = manager.isRegistered([Mod.Control], KeyCode.KeyS);
+   * ```
+   */
+  #[napi]
+  pub fn is_registered(&self, mods: Vec<Mod>, code: KeyCode) -> bool {
+    let lock = lock_hotk(&self.hotk);
+    let id = get_hotkey_id(code, lock.strip_ignored(mods));
+    let hotkeys = lock.hotkeys.lock().unwrap();
+    hotkeys.contains_key(&id)
+  }
+
+  /**
+   * Checks whether registering a combination would clash with a hotkey of this process, before asking the OS.
+   *
+   * This is process-local conflict detection: it compares the computed id with the hotkeys registered here,
+   * so it also catches equivalent spellings (`Meta` and `Super`, ignored lock modifiers). It can't see the
+   * hotkeys of other applications; registering can still fail with `AlreadyRegistered` because of them.
+   *
+   * @example
+   * ```js
+   * import { hotk, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * if (manager.wouldConflict([Mod.Control], KeyCode.KeyS)) {
+   *   showError('Control+S is already bound');
+   * }
+   * ```
+   */
+  #[napi]
+  pub fn would_conflict(&self, mods: Vec<Mod>, code: KeyCode) -> bool {
+    self.is_registered(mods, code)
+  }
+
+  /**
+   * Enqueues the registration of a hotkey and returns its id right away, without waiting for the backend.
+   *
+   * Registrations are performed in order on a background thread, and each response is reported to the
+   * callback set with `setOnDeferredResult`. Suited for fire-and-forget bulk registration from the JS thread.
+   *
+   * @returns The id the hotkey will have, as computed by `getHotkeyId`.
+   *
+   * @example
+   * ```js
+   * import { hotk, Mod, KeyCode } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setOnDeferredResult(response => {
+   *   if (!response.isOk()) console.error(response.id, response.error);
+   * });
+   * const id = manager.registerDeferred([Mod.Control], KeyCode.KeyD);
+   * ```
+   */
+  #[napi]
+  pub fn register_deferred(&self, mods: Vec<Mod>, code: KeyCode) -> u32 {
+    let mut lock = lock_hotk(&self.hotk);
+    let id = get_hotkey_id(code, lock.strip_ignored(mods.clone()));
+
+    let hotk = self.hotk.clone();
+    let deferred = lock
+      .deferred
+      .get_or_insert_with(|| deferred::spawn_worker(hotk));
+    let _ = deferred.send(Desc::new(code, mods));
+
+    id
+  }
+
+  /**
+   * Sets the callback receiving the response of every `registerDeferred` call. Pass `null` to remove it.
+   *
+   * Responses of registrations completed while no callback is set are dropped.
+   * The callback does not keep the process alive.
+   */
+  #[napi(ts_args_type = "onResult: ((response: HotkReponse) => void) | null")]
+  pub fn set_on_deferred_result(
+    &self,
+    env: Env,
+    on_result: Option<JsFunction>,
+  ) -> napi::Result<()> {
+    let callback = match on_result {
+      Some(on_result) => {
+        let mut tsfn: DeferredCallback =
+          on_result.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+        tsfn.unref(&env)?;
+        Some(tsfn)
+      }
+      None => None,
+    };
+
+    lock_hotk(&self.hotk).on_deferred_result = callback;
+    Ok(())
+  }
+
+  /**
+   * Sets a callback receiving the events of every registered hotkey on `code`, whatever its modifiers,
+   * e.g. to highlight a key in an overlay. Pass `null` to remove it.
+   *
+   * It is called in addition to the `init` callback and the per-hotkey ones.
+   * The callback does not keep the process alive.
+   *
+   * @example
+   * ```js
+   * import { hotk, KeyCode } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setOnKey(KeyCode.KeyA, event => overlay.flash(event.code));
+   * ```
+   */
+  #[napi(ts_args_type = "code: KeyCode, onEvent: ((event: Event) => void) | null")]
+  pub fn set_on_key(
+    &self,
+    env: Env,
+    code: KeyCode,
+    on_event: Option<JsFunction>,
+  ) -> napi::Result<()> {
+    let callback = match on_event {
+      Some(on_event) => {
+        let mut tsfn: HotkeyCallback = on_event.create_threadsafe_function(0, |ctx| {
+          dispatch::event_object(ctx.env, ctx.value).map(|obj| vec![obj])
+        })?;
+        tsfn.unref(&env)?;
+        Some(tsfn)
+      }
+      None => None,
+    };
+
+    let dispatcher = lock_hotk(&self.hotk).dispatcher.clone();
+    dispatcher.set_key_callback(code, callback);
+    Ok(())
+  }
+
+  /**
+   * Attaches a handler to a combination, registering it first if it is not registered yet.
+   *
+   * Events of the combination go to `onEvent` instead of the `init` callback, so there is no need to switch
+   * on `event.id`. Calling it again for the same combination replaces the handler. The handler stays until
+   * `off` is called, even if the hotkey is unregistered, and does not keep the process alive.
+   *
+   * @returns `false` if the hotkey could not be registered; no handler is attached then.
+   *
+   * @example
+   * ```js
+   * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.on([Mod.Control], KeyCode.KeyS, event => {
+   *   if (event.eventType === EventType.Pressed) save();
+   * });
+   * ```
+   */
+  #[napi(ts_args_type = "mods: Array<Mod>, code: KeyCode, onEvent: (event: Event) => void")]
+  pub fn on(
+    &self,
+    env: Env,
+    mods: Vec<Mod>,
+    code: KeyCode,
+    on_event: JsFunction,
+  ) -> napi::Result<bool> {
+    let mut handler: HotkeyCallback = on_event.create_threadsafe_function(0, |ctx| {
+      dispatch::event_object(ctx.env, ctx.value).map(|obj| vec![obj])
+    })?;
+    handler.unref(&env)?;
+
+    let (dispatcher, id) = {
+      let mut lock = lock_hotk(&self.hotk);
+      let id = get_hotkey_id(code, lock.strip_ignored(mods.clone()));
+      if matches!(
+        lock.register_checked(mods, code),
+        RegistrationResult::Failed
+      ) {
+        return Ok(false);
+      }
+      (lock.dispatcher.clone(), id)
+    };
+
+    dispatcher.set_handler(id, Some(handler));
+    Ok(true)
+  }
+
+  /**
+   * Detaches the handler attached with `on`, so the events of the combination go to the `init` callback again.
+   *
+   * The hotkey stays registered; use `unregister` to release it.
+   *
+   * @returns `false` if the combination had no handler.
+   */
+  #[napi]
+  pub fn off(&self, mods: Vec<Mod>, code: KeyCode) -> bool {
+    let (dispatcher, id) = {
+      let lock = lock_hotk(&self.hotk);
+      let id = get_hotkey_id(code, lock.strip_ignored(mods));
+      (lock.dispatcher.clone(), id)
+    };
+
+    dispatcher.set_handler(id, None)
+  }
+
+  /**
+   * Creates an empty hotkey profile.
+   *
+   * Profiles hold whole sets of hotkeys (e.g. one per game) that are switched with `activateProfile`.
+   *
+   * @returns `false` if a profile with that name already exists.
+   */
+  #[napi]
+  pub fn create_profile(&self, name: String) -> bool {
+    let mut lock = lock_hotk(&self.hotk);
+
+    if lock.profiles.contains_key(&name) {
+      return false;
+    }
+    lock.profiles.insert(name.clone(), vec![]);
+    lock.profile_callbacks.insert(name, HashMap::new());
+
+    true
+  }
+
+  /**
+   * Adds a hotkey to a profile, with an optional callback for its events.
+   *
+   * The hotkey is only registered while the profile is active; if it already is, it is registered right away.
+   * The callback receives the same events as the `init` callback, but only for this hotkey and only while
+   * the profile is active. It does not keep the process alive.
+   *
+   * @returns The registration response if the profile is active, otherwise an `Ok` response with the hotkey id.
+   * An `Error` response if the profile does not exist.
+   *
+   * @example
+   * ```js
+   * import { hotk, Mod, KeyCode } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.createProfile('game');
+   * manager.registerIn('game', [Mod.Control], KeyCode.KeyG, event => console.log('Game hotkey', event));
+   * manager.activateProfile('game');
+   * ```
+   */
+  #[napi(
+    ts_args_type = "profile: string, mods: Array<Mod>, code: KeyCode, onEvent?: ((event: Event) => void) | undefined | null"
+  )]
+  pub fn register_in(
+    &self,
+    env: Env,
+    profile: String,
+    mods: Vec<Mod>,
+    code: KeyCode,
+    on_event: Option<JsFunction>,
+  ) -> napi::Result<HotkReponse> {
+    let mut lock = lock_hotk(&self.hotk);
+    let mods = lock.strip_ignored(mods);
+    let id = get_hotkey_id(code, mods.clone());
+
+    if !lock.profiles.contains_key(&profile) {
+      return Ok(HotkReponse::error(
+        Operation::Register,
+        id,
+        ErrorKind::InvalidArgument,
+        format!("Unknown profile {}", profile),
+      ));
+    }
+
+    let callback = match on_event {
+      Some(on_event) => {
+        let mut tsfn: HotkeyCallback = on_event.create_threadsafe_function(0, |ctx| {
+          dispatch::event_object(ctx.env, ctx.value).map(|obj| vec![obj])
+        })?;
+        tsfn.unref(&env)?;
+        Some(tsfn)
+      }
+      None => None,
+    };
+
+    let descs = lock.profiles.get_mut(&profile).unwrap();
+    descs.retain(|desc| get_hotkey_id(desc.code, desc.mods.clone()) != id);
+    descs.push(Desc::new(code, mods.clone()));
+
+    let callbacks = lock.profile_callbacks.entry(profile.clone()).or_default();
+    match callback {
+      Some(callback) => callbacks.insert(id, callback),
+      None => callbacks.remove(&id),
+    };
+
+    if lock.active_profile.as_deref() != Some(profile.as_str()) {
+      return Ok(
+        HotkReponse::ok(Operation::Register, id).with_warnings(support::warnings(&mods, code)),
+      );
+    }
+
+    let callbacks = lock.profile_callbacks[&profile].clone();
+    lock.dispatcher.set_hotkey_callbacks(callbacks);
+
+    Ok(lock.register(mods, code))
+  }
+
+  /**
+   * Switches the active profile: unregisters the hotkeys of the previously active profile,
+   * then registers the ones of `name`. Only one profile is active at a time.
+   *
+   * @returns One registration response per hotkey of the profile, or `null` if it does not exist.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.activateProfile('editor');
+   * ```
+   */
+  #[napi]
+  pub fn activate_profile(&self, name: String) -> Option<Vec<HotkReponse>> {
+    lock_hotk(&self.hotk).activate_profile(&name)
+  }
+
+  /**
+   * Returns the name of the active profile, or `null` if none was activated.
+   */
+  #[napi]
+  pub fn active_profile(&self) -> Option<String> {
+    lock_hotk(&self.hotk).active_profile.clone()
+  }
+
+  /**
+   * Lists every registered combination, ordered by hotkey id, e.g. for a "current shortcuts" screen.
+   *
+   * @example
+   * ```js
+   * import { hotk, descToAccelerator } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const rows = manager.listRegistered().map(descToAccelerator);
+   * ```
+   */
+  #[napi]
+  pub fn list_registered(&self) -> Vec<Desc> {
+    let lock = lock_hotk(&self.hotk);
+    let hotkeys = lock.hotkeys.lock().unwrap();
+
+    let mut entries: Vec<(&u32, &Desc)> = hotkeys.iter().collect();
+    entries.sort_by_key(|(id, _)| **id);
+    entries.into_iter().map(|(_, desc)| desc.clone()).collect()
+  }
+
+  /**
+   * Lists every registered combination as an accelerator string, ordered by hotkey id.
+   *
+   * Each string is in the canonical form of `descToAccelerator`, so it round-trips through `parseHotkey`.
+   *
+   * @example
+   * ```js
+   * import { hotk, parseHotkey } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const saved = manager.exportAccelerators();
+   * // Later
+   * saved.map(parseHotkey).forEach(desc => manager.register(desc.mods, desc.code));
+   * ```
+   */
+  #[napi]
+  pub fn export_accelerators(&self) -> Vec<String> {
+    let lock = lock_hotk(&self.hotk);
+    let hotkeys = lock.hotkeys.lock().unwrap();
+
+    let mut entries: Vec<(&u32, &Desc)> = hotkeys.iter().collect();
+    entries.sort_by_key(|(id, _)| **id);
+    entries
+      .into_iter()
+      .map(|(_, desc)| desc.to_accelerator())
+      .collect()
+  }
+
+  /**
+   * Returns a fingerprint of the registered combinations, to detect whether the keymap changed,
+   * e.g. before persisting it.
+   *
+   * It hashes the sorted canonical accelerators (see `exportAccelerators`), so it doesn't depend on
+   * registration order and is stable across runs and platforms.
+   *
+   * @returns {string} 16 hex digits.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const fingerprint = hotk().keymapFingerprint();
+   * if (fingerprint !== settings.keymapFingerprint) {
+   *   save(hotk().exportAccelerators(), fingerprint);
+   * }
+   * ```
+   */
+  #[napi]
+  pub fn keymap_fingerprint(&self) -> String {
+    let lock = lock_hotk(&self.hotk);
+    let hotkeys = lock.hotkeys.lock().unwrap();
+
+    let mut accelerators: Vec<String> = hotkeys.values().map(Desc::to_accelerator).collect();
+    accelerators.sort();
+
+    // FNV-1a, which unlike `DefaultHasher` is specified and won't change between builds.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in accelerators.join("\n").bytes() {
+      hash ^= byte as u64;
+      hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+  }
+
+  /**
+   * Returns the accelerator label of every registered hotkey, so UIs keyed by event ids can render them
+   * without a call per id.
+   *
+   * Labels are the same strings as `exportAccelerators`, in the same order (sorted by id).
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const labels = new Map(manager.labelsById().map(({ id, label }) => [id, label]));
+   * manager.init(event => console.log(labels.get(event.id)));
+   * ```
+   */
+  #[napi]
+  pub fn labels_by_id(&self) -> Vec<HotkeyLabel> {
+    let lock = lock_hotk(&self.hotk);
+    let hotkeys = lock.hotkeys.lock().unwrap();
+
+    let mut labels: Vec<HotkeyLabel> = hotkeys
+      .iter()
+      .map(|(id, desc)| HotkeyLabel {
+        id: *id,
+        label: desc.to_accelerator(),
+      })
+      .collect();
+    labels.sort_by_key(|label| label.id);
+    labels
+  }
+
+  /**
+   * Returns the upstream `global_hotkey` view of hotkey `id`, to correlate with code using that crate directly.
+   *
+   * @returns The hotkey info, or `null` if `id` is not registered.
+   *
+   * @example
+   * ```js
+   * import { hotk, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyK);
+   * manager.hotkeyForId(id); // { rawId: id, code: 'KeyK', mods: ['Control', 'Shift'], modifiersBits: 520 }
+   * ```
+   */
+  #[napi]
+  pub fn hotkey_for_id(&self, id: u32) -> Option<HotKeyInfo> {
+    let lock = lock_hotk(&self.hotk);
+    let desc = lock.hotkeys.lock().unwrap().get(&id).cloned()?;
+    let hotkey = desc.to_hotkey();
+
+    Some(HotKeyInfo {
+      raw_id: hotkey.id,
+      code: desc.code,
+      mods: desc.mods,
+      modifiers_bits: hotkey.mods.bits(),
+    })
+  }
+
+  /**
+   * Generates a TypeScript module with one constant per registered hotkey, for build pipelines that want
+   * type-safe references to the bindings.
+   *
+   * Hotkeys carry no action name, so each constant is named after its accelerator
+   * (`Control+Shift+KeyK` becomes `Control_Shift_KeyK`). Constants are sorted by id.
+   *
+   * @returns {string} The module source.
+   *
+   * @example
+   * ```js
+   * import { writeFileSync } from 'node:fs';
+   * import { hotk } from '@hotk/core';
+   *
+   * writeFileSync('src/keymap.ts', hotk().exportKeymapTs());
+   * // export const Control_Shift_KeyK = { mods: [Mod.Control, Mod.Shift], code: KeyCode.KeyK } as const;
+   * ```
+   */
+  #[napi]
+  pub fn export_keymap_ts(&self) -> String {
+    let lock = lock_hotk(&self.hotk);
+    let hotkeys = lock.hotkeys.lock().unwrap();
+
+    let mut entries: Vec<(&u32, &Desc)> = hotkeys.iter().collect();
+    entries.sort_by_key(|(id, _)| **id);
+
+    let mut ts = String::from("import { KeyCode, Mod } from '@hotk/core';\n\n");
+    for (_, desc) in entries {
+      ts.push_str(&format!(
+        "export const {} = {} as const;\n",
+        ts_identifier(&desc.to_accelerator()),
+        desc.to_ts_literal()
+      ));
+    }
+    ts
+  }
+
+  /**
+   * Returns how many logical registrations share the OS registration of hotkey `id`, `0` if it is not registered.
+   */
+  #[napi]
+  pub fn registrants(&self, id: u32) -> u32 {
+    lock_hotk(&self.hotk)
+      .registrants
+      .get(&id)
+      .copied()
+      .unwrap_or(0)
+  }
+
+  /**
+   * Resumes delivering the events of hotkey `id` after `disarm`.
+   *
+   * Hotkeys are armed when registered.
+   *
+   * @returns `true` if `id` is registered.
+   */
+  #[napi]
+  pub fn arm(&self, id: u32) -> bool {
+    let lock = lock_hotk(&self.hotk);
+
+    if !lock.registrants.contains_key(&id) {
+      return false;
+    }
+    lock.dispatcher.arm(id);
+    true
+  }
+
+  /**
+   * Ignores the presses of hotkey `id` for `cooldownMs` after each one that fires, like a game ability.
+   *
+   * Presses during the cooldown are dropped along with their `Released`. Pass `0` to disable it.
+   * The cooldown is forgotten when the hotkey is unregistered.
+   *
+   * @returns `true` if `id` is registered.
+   *
+   * @example
+   * ```js
+   * import { hotk, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const { id } = manager.register([Mod.Alt], KeyCode.KeyQ);
+   * manager.setCooldown(id, 5000);
+   * ```
+   */
+  #[napi]
+  pub fn set_cooldown(&self, id: u32, cooldown_ms: u32) -> bool {
+    let lock = lock_hotk(&self.hotk);
+
+    if !lock.registrants.contains_key(&id) {
+      return false;
+    }
+    lock
+      .dispatcher
+      .set_cooldown(id, Duration::from_millis(cooldown_ms as u64));
+    true
+  }
+
+  /**
+   * Registers a hotkey that also fires a `MultiTap` event when it is pressed `taps` times in a row,
+   * each press within `windowMs` of the first, e.g. a double tap to toggle an overlay.
+   *
+   * The `MultiTap` event follows the `Pressed` of the last tap and carries the count in `taps`; every press
+   * is still delivered as usual. A press after the window starts counting again. Timing is tracked as the
+   * presses arrive from the OS, so event loop jitter doesn't affect it. Registering it again updates `taps`
+   * and `windowMs`; they are forgotten when the hotkey is unregistered.
+   *
+   * @returns The registration response. An `InvalidArgument` error if `taps` is less than `2`.
+   *
+   * @example
+   * ```js
+   * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.registerMultiTap([Mod.Control], KeyCode.Space, 2, 300);
+   * manager.init(event => {
+   *   if (event.eventType === EventType.MultiTap) overlay.toggle();
+   * });
+   * ```
+   */
+  #[napi]
+  pub fn register_multi_tap(
+    &self,
+    mods: Vec<Mod>,
+    code: KeyCode,
+    taps: u32,
+    window_ms: u32,
+  ) -> HotkReponse {
+    let mut lock = lock_hotk(&self.hotk);
+
+    if taps < 2 {
+      let id = get_hotkey_id(code, lock.strip_ignored(mods));
+      return HotkReponse::error(
+        Operation::Register,
+        id,
+        ErrorKind::InvalidArgument,
+        format!("A multi-tap needs at least 2 taps, got {}", taps),
+      );
+    }
+
+    let response = lock.register(mods, code);
+    if response.is_ok() {
+      lock
+        .dispatcher
+        .set_multi_tap(response.id, taps, Duration::from_millis(window_ms as u64));
+    }
+    response
+  }
+
+  /**
+   * Registers a sequence of hotkeys, e.g. `Ctrl+K` then `Ctrl+S`, that fires a `Sequence` event when its
+   * steps are pressed in order, all within `windowMs` of the first.
+   *
+   * Each step is registered as a regular hotkey, so its events are still delivered as usual. The `Sequence`
+   * event follows the `Pressed` of the last step; its `id` is the one returned here and its `code` and `mods`
+   * are those of the last step. Pressing another registered hotkey, or the next step after the window, starts
+   * the sequence over. Registering the same sequence again only updates `windowMs`.
+   *
+   * @returns The registration response, with the id of the sequence. If a step fails, the steps registered
+   * before it are unregistered and its response is returned. An `InvalidArgument` error for fewer than
+   * two steps.
+   *
+   * @example
+   * ```js
+   * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const { id } = manager.registerSequence(
+   *   [
+   *     { mods: [Mod.Control], code: KeyCode.KeyK },
+   *     { mods: [Mod.Control], code: KeyCode.KeyS },
+   *   ],
+   *   1000,
+   * );
+   * manager.init(event => {
+   *   if (event.id === id && event.eventType === EventType.Sequence) saveAll();
+   * });
+   * ```
+   */
+  #[napi]
+  pub fn register_sequence(&self, steps: Vec<Desc>, window_ms: u32) -> HotkReponse {
+    lock_hotk(&self.hotk).register_sequence(steps, Duration::from_millis(window_ms as u64))
+  }
+
+  /**
+   * Removes a sequence registered with `registerSequence` and unregisters its steps.
+   *
+   * @returns `false` if `id` is not a registered sequence.
+   */
+  #[napi]
+  pub fn unregister_sequence(&self, id: u32) -> bool {
+    lock_hotk(&self.hotk).unregister_sequence(id)
+  }
+
+  /**
+   * Withholds the `Released` events of hotkey `id` while `enabled`, for "hold to drag, we'll tell you when
+   * to finish" interactions: `Pressed` is still delivered right away.
+   *
+   * Disabling it delivers the most recent `Released` withheld meanwhile, if any, or drops it with
+   * `dropWithheld`. The deferral is forgotten when the hotkey is unregistered.
+   *
+   * @param {DeferReleasedOptions} [options] - What to do with the withheld event when disabling it.
+   * @returns `true` if `id` is registered.
+   *
+   * @example
+   * ```js
+   * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const { id } = manager.register([Mod.Alt], KeyCode.KeyD);
+   * manager.init(event => {
+   *   if (event.eventType === EventType.Pressed) {
+   *     manager.deferReleased(id, true);
+   *     drag.start().then(() => manager.deferReleased(id, false));
+   *   }
+   * });
+   * ```
+   */
+  #[napi]
+  pub fn defer_released(
+    &self,
+    id: u32,
+    enabled: bool,
+    options: Option<DeferReleasedOptions>,
+  ) -> bool {
+    let drop_withheld = options.and_then(|o| o.drop_withheld).unwrap_or(false);
+    let lock = lock_hotk(&self.hotk);
+
+    if !lock.registrants.contains_key(&id) {
+      return false;
+    }
+    lock.dispatcher.defer_released(id, enabled, drop_withheld);
+    true
+  }
+
+  /**
+   * Registers a hotkey that also fires a `LongPress` event when it is held for longer than `holdMs`,
+   * e.g. a "hold Alt+Space" gesture.
+   *
+   * The `LongPress` event comes right before the `Released` that ends the hold, and its `deltaMs` is
+   * how long the hotkey was held. Shorter presses only produce the usual `Pressed` and `Released`.
+   * The hold is timed as events arrive from the OS, so event loop latency doesn't affect it.
+   * Registering it again updates `holdMs`; it is forgotten when the hotkey is unregistered.
+   *
+   * @example
+   * ```js
+   * import { hotk, EventType, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.registerLongPress([Mod.Alt], KeyCode.Space, 500);
+   * manager.init(event => {
+   *   if (event.eventType === EventType.LongPress) launcher.open();
+   * });
+   * ```
+   */
+  #[napi]
+  pub fn register_long_press(&self, mods: Vec<Mod>, code: KeyCode, hold_ms: u32) -> HotkReponse {
+    let mut lock = lock_hotk(&self.hotk);
+
+    let response = lock.register(mods, code);
+    if response.is_ok() {
+      lock
+        .dispatcher
+        .set_long_press(response.id, Duration::from_millis(hold_ms as u64));
+    }
+    response
+  }
+
+  /**
+   * Drops every event of hotkey `id`, keeping it registered, until `arm` is called.
+   *
+   * Useful for actions that must only fire once explicitly enabled, e.g. behind a safety toggle.
+   *
+   * @returns `true` if `id` is registered.
+   *
+   * @example
+   * ```js
+   * import { hotk, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const { id } = manager.register([Mod.Control, Mod.Shift], KeyCode.KeyK);
+   * manager.disarm(id);
+   * // Later, once the user enables it
+   * manager.arm(id);
+   * ```
+   */
+  #[napi]
+  pub fn disarm(&self, id: u32) -> bool {
+    let lock = lock_hotk(&self.hotk);
+
+    if !lock.registrants.contains_key(&id) {
+      return false;
+    }
+    lock.dispatcher.disarm(id);
+    true
+  }
+
+  /**
+   * Registers every entry of a keymap in the background.
+   *
+   * Registration happens off the JS thread, so applying hundreds of bindings doesn't block the UI.
+   * `on_progress` is called with `{ done, total }` as entries are processed.
+   *
+   * @returns A promise resolving to one response per entry, in the same order as `descs`.
+   *
+   * @example
+   * ```js
+   * import { hotk, Mod, KeyCode } from '@hotk/core';
+   *
+   * const manager = hotk();
+   *
+   * const responses = await manager.applyKeymapAsync(
+   *   [
+   *     { mods: [Mod.Control], code: KeyCode.KeyA },
+   *     { mods: [Mod.Control], code: KeyCode.KeyB },
+   *   ],
+   *   ({ done, total }) => console.log(`${done}/${total}`),
+   * );
+   * ```
+   */
+  #[napi(ts_args_type = "descs: Array<Desc>, on_progress?: (progress: KeymapProgress) => void")]
+  pub fn apply_keymap_async(
+    &self,
+    descs: Vec<Desc>,
+    on_progress: Option<JsFunction>,
+  ) -> napi::Result<AsyncTask<ApplyKeymap>> {
+    let on_progress = on_progress
+      .map(|on_progress| {
+        on_progress.create_threadsafe_function(0, |ctx| {
+          let progress: KeymapProgress = ctx.value;
+          Ok(vec![progress])
+        })
+      })
+      .transpose()?;
+
+    Ok(AsyncTask::new(ApplyKeymap {
+      hotk: self.hotk.clone(),
+      descs,
+      on_progress,
+    }))
+  }
+
+  /**
+   * Frees the OS grabs of every registered hotkey, so other applications can register the same combinations,
+   * e.g. while another tool needs them.
+   *
+   * The hotkeys stay registered here (`registrants`, `exportAccelerators`, ...) but no longer fire until
+   * `reacquireOsGrabs` is called. Unregistering one of them meanwhile just forgets it.
+   *
+   * @returns One response per released hotkey, sorted by id.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.releaseOsGrabs();
+   * await otherToolFinished();
+   * manager.reacquireOsGrabs();
+   * ```
+   */
+  #[napi]
+  pub fn release_os_grabs(&self) -> Vec<HotkReponse> {
+    lock_hotk(&self.hotk).release_os_grabs()
+  }
+
+  /**
+   * Registers again at the OS level the hotkeys freed by `releaseOsGrabs`.
+   *
+   * A combination grabbed by another application in the meantime fails and stays released,
+   * so calling this again retries it.
+   *
+   * @returns One response per released hotkey, sorted by id.
+   */
+  #[napi]
+  pub fn reacquire_os_grabs(&self) -> Vec<HotkReponse> {
+    lock_hotk(&self.hotk).reacquire_os_grabs()
+  }
+
+  /**
+   * Pauses every global hotkey, e.g. while a text field captures shortcuts, without losing the registrations.
+   *
+   * Works like `releaseOsGrabs`: the OS grabs are freed, but the hotkeys stay registered here, so
+   * `listRegistered` and `isRegistered` still reflect the intended state. Call `resume` to restore them.
+   *
+   * A hotkey the OS fails to unregister keeps its grab and keeps firing; calling `suspend` again retries it.
+   *
+   * @returns The responses of the hotkeys that failed to unregister, empty if all were paused.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * input.addEventListener('focus', () => {
+   *   manager.suspend().forEach(response => console.error('Still active', response.id, response.error));
+   * });
+   * input.addEventListener('blur', () => {
+   *   manager.resume().forEach(response => console.error('Lost', response.id, response.error));
+   * });
+   * ```
+   */
+  #[napi]
+  pub fn suspend(&self) -> Vec<HotkReponse> {
+    lock_hotk(&self.hotk)
+      .release_os_grabs()
+      .into_iter()
+      .filter(|response| !response.is_ok())
+      .collect()
+  }
+
+  /**
+   * Registers again with the OS the hotkeys paused by `suspend`.
+   *
+   * A combination another application grabbed in the meantime can't be restored; it stays paused,
+   * so calling `resume` again retries it.
+   *
+   * @returns The responses of the hotkeys that failed to re-register, empty if all were restored.
+   */
+  #[napi]
+  pub fn resume(&self) -> Vec<HotkReponse> {
+    lock_hotk(&self.hotk)
+      .reacquire_os_grabs()
+      .into_iter()
+      .filter(|response| !response.is_ok())
+      .collect()
+  }
+
+  /**
+   * Replaces the backend with a fresh one and registers every hotkey again, e.g. once `isHealthy` reports
+   * that the Windows backend thread stopped.
+   *
+   * Hotkeys paused by `suspend` stay paused. One that can't be restored is paused as well, so `resume`
+   * retries it. The layout change and error callbacks and the repeat setting carry over; the backend priority
+   * and `setWatchUnmapped` must be set again. Only Windows runs the backend on its own thread; elsewhere
+   * this does nothing. The old backend is stopped first, as a process runs a single one; throws if the new
+   * one can't be created, leaving `isHealthy` false. A backend thread that doesn't stop within a second is
+   * left behind to exit on its own, and the new one starts anyway.
+   *
+   * @returns The responses of the hotkeys that failed to re-register, empty if all were restored.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * if (!manager.isHealthy()) {
+   *   manager.recreate().forEach(response => console.error('Lost', response.id, response.error));
+   * }
+   * ```
+   */
+  #[napi]
+  pub fn recreate(&self) -> napi::Result<Vec<HotkReponse>> {
+    lock_hotk(&self.hotk)
+      .recreate()
+      .map_err(|error| napi::Error::from_reason(error.to_string()))
+  }
+
+  /**
+   * Allows the Node.js process to exit naturally if no other tasks are pending.
+   *
+   * This detaches the internal hotkey event callback from the event loop,
+   * meaning the process won't be held open just to listen for hotkey events.
+   *
+   * @returns `true` if the operation succeeded, `false` otherwise.
+   *
+   * @example
+   * ```js
+   * const manager = hotk();
+   * manager.init(console.log);
+   * manager.unref(); // Now the process can exit if nothing else is running.
+   * ```
+   */
+  #[napi]
+  pub fn unref(&self, env: Env) -> bool {
+    let lock = lock_hotk(&self.hotk);
+
+    lock.dispatcher.set_referenced(env, false)
+  }
+
+  /**
+   * Keeps the Node.js process alive to continue listening for hotkey events.
+   *
+   * This ensures that the process will not exit until `destroy()` is called
+   * or `unref()` is used to detach the event callback from the event loop.
+   *
+   * @returns `true` if the operation succeeded, `false` otherwise.
+   *
+   * @example
+   * ```js
+   * const manager = hotk();
+   * manager.init(console.log);
+   * manager.refer(); // Keeps the process alive
+   * ```
+   */
+  #[napi]
+  pub fn refer(&self, env: Env) -> bool {
+    let lock = lock_hotk(&self.hotk);
+
+    lock.dispatcher.set_referenced(env, true)
+  }
+
+  /**
+   * Returns when `init` started listening, in milliseconds since the Unix epoch, or `null` if it was not
+   * called or `destroy` was called since. Replacing the callback with another `init` keeps the original time.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const since = hotk().listeningSince();
+   * console.log(since === null ? 'Not listening' : `Listening since ${new Date(since).toISOString()}`);
+   * ```
+   */
+  #[napi]
+  pub fn listening_since(&self) -> Option<f64> {
+    let dispatcher = lock_hotk(&self.hotk).dispatcher.clone();
+    let (since, _) = dispatcher.listening_since()?;
+
+    // Whole milliseconds, like `Date.now()`.
+    Some(since.duration_since(UNIX_EPOCH).ok()?.as_millis() as f64)
+  }
+
+  /**
+   * Returns for how many milliseconds the manager has been listening, see `listeningSince`.
+   *
+   * Measured with a monotonic clock, so changes of the system time don't affect it.
+   */
+  #[napi]
+  pub fn listening_uptime_ms(&self) -> Option<f64> {
+    let dispatcher = lock_hotk(&self.hotk).dispatcher.clone();
+    let (_, started) = dispatcher.listening_since()?;
+
+    Some(started.elapsed().as_secs_f64() * 1000.0)
+  }
+
+  /**
+   * Enables or disables buffering of events that fire before `init` is called.
+   *
+   * While enabled and no callback is installed yet, events for registered hotkeys are kept
+   * and delivered, in order, as soon as `init` installs the callback. At most 64 events are
+   * kept; once the buffer is full the oldest event is dropped to make room for the newest.
+   * Disabling buffering discards anything buffered so far.
+   *
+   * Buffering is disabled by default.
+   *
+   * @example
+   * ```js
+   * const manager = hotk();
+   * manager.setBufferPreinit(true);
+   * manager.register([Mod.Control], KeyCode.KeyA);
+   *
+   * // Presses of Ctrl + A made before this point are delivered here.
+   * manager.init(console.log);
+   * ```
+   */
+  #[napi]
+  pub fn set_buffer_preinit(&self, enabled: bool) {
+    lock_hotk(&self.hotk).dispatcher.set_buffer_preinit(enabled);
+  }
+
+  /**
+   * Delivers events in batches instead of one call per event, for apps that process input once per frame.
+   *
+   * Events accumulate and `onBatch` is called with all of them every `intervalMs`, or as soon as `maxSize`
+   * of them are waiting. While set, the `init` callback receives no events. Pass `null` to go back to
+   * per-event delivery; events still waiting are delivered to the previous `onBatch` first.
+   *
+   * The callback does not keep the process alive.
+   *
+   * @param {BatchOptions} [options] - Defaults to a batch every 16 ms, of at most 256 events.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setBatchCallback(events => events.forEach(handle), { intervalMs: 16 });
+   * ```
+   */
+  #[napi(ts_args_type = "onBatch: ((events: Event[]) => void) | null, options?: BatchOptions")]
+  pub fn set_batch_callback(
+    &self,
+    env: Env,
+    on_batch: Option<JsFunction>,
+    options: Option<BatchOptions>,
+  ) -> napi::Result<()> {
+    let interval_ms = options.as_ref().and_then(|o| o.interval_ms).unwrap_or(16);
+    let max_size = options.as_ref().and_then(|o| o.max_size).unwrap_or(256);
+
+    let callback = match on_batch {
+      Some(on_batch) => {
+        let mut tsfn: BatchCallback =
+          on_batch.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+        tsfn.unref(&env)?;
+        Some(tsfn)
+      }
+      None => None,
+    };
+
+    let dispatcher = lock_hotk(&self.hotk).dispatcher.clone();
+    dispatcher.set_batch(
+      callback,
+      Duration::from_millis(interval_ms.max(1) as u64),
+      max_size as usize,
+    );
+    Ok(())
+  }
+
+  /**
+   * Returns how many events were dropped on their way to the `init` callback: those that could not be
+   * converted to JS objects, and those a full queue dropped (see `setQueueLimit`). A conversion failure
+   * only skips its own event; the following ones are still delivered.
+   */
+  #[napi]
+  pub fn dropped_events(&self) -> u32 {
+    lock_hotk(&self.hotk).dispatcher.dropped()
+  }
+
+  /**
+   * Exempts the events of hotkey `id` from the queue limit set by `setQueueLimit`, e.g. for a critical
+   * "stop recording" key that must never be dropped. The other hotkeys are still dropped once the queue is full.
+   *
+   * The setting is forgotten when the hotkey is unregistered.
+   *
+   * @returns `true` if `id` is registered.
+   *
+   * @example
+   * ```js
+   * import { hotk, KeyCode, Mod } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const { id } = manager.register([Mod.Control, Mod.Alt], KeyCode.KeyR);
+   * manager.setQueueExempt(id, true);
+   * ```
+   */
+  #[napi]
+  pub fn set_queue_exempt(&self, id: u32, enabled: bool) -> bool {
+    let lock = lock_hotk(&self.hotk);
+
+    if !lock.registrants.contains_key(&id) {
+      return false;
+    }
+    lock.dispatcher.set_queue_exempt(id, enabled);
+    true
+  }
+
+  /**
+   * Whether the events of hotkey `id` are exempt from the queue limit, see `setQueueExempt`.
+   */
+  #[napi]
+  pub fn is_queue_exempt(&self, id: u32) -> bool {
+    lock_hotk(&self.hotk).dispatcher.is_queue_exempt(id)
+  }
+
+  /**
+   * Bounds how many events wait for the `init` callback, e.g. while the JS thread is busy under sustained
+   * input. Once `limit` events are waiting, `policy` decides which are dropped; each one counts in
+   * `droppedEvents`. Pass `0` to remove the bound, the default.
+   *
+   * Events delivered through `setBatchCallback` and those of hotkeys exempted with `setQueueExempt` are not
+   * affected.
+   *
+   * @example
+   * ```js
+   * import { hotk, QueuePolicy } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setQueueLimit(64, QueuePolicy.DropOldest);
+   * ```
+   */
+  #[napi]
+  pub fn set_queue_limit(&self, limit: u32, policy: QueuePolicy) {
+    let dispatcher = lock_hotk(&self.hotk).dispatcher.clone();
+    dispatcher.set_queue_limit(limit, policy);
+  }
+
+  /**
+   * Returns how often the locks of the manager were found held by another thread, and the longest wait for
+   * them, to tell whether heavy registration traffic delays event dispatch or the other way around.
+   *
+   * An uncontended lock costs a counter increment; waits are only timed when the lock is already held.
+   * Counters accumulate for the lifetime of the process.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const { contended, maxWaitMs } = manager.lockStats().dispatch;
+   * if (maxWaitMs > 16) console.warn(`Events waited up to ${maxWaitMs}ms, ${contended} times`);
+   * ```
+   */
+  #[napi]
+  pub fn lock_stats(&self) -> LockStats {
+    let manager = HOTK_CONTENTION.counters();
+    let dispatcher = lock_hotk(&self.hotk).dispatcher.clone();
+    LockStats {
+      manager,
+      dispatch: dispatcher.state_contention(),
+    }
+  }
+
+  /**
+   * Feeds an event through the same path as events coming from the OS.
+   *
+   * Useful to test handlers without pressing keys. The event is handled like a real one, except that it has
+   * `synthetic` set to `true`, as does everything derived from it.
+   *
+   * @returns `true` if `id` belongs to a registered hotkey, or one still in the late event grace window.
+   * `false` for `Registered`, `Unregistered`, `MultiTap`, `LongPress` and `Sequence`, which the OS never sends.
+   *
+   * @example
+   * ```js
+   * const { id } = manager.register([Mod.Control], KeyCode.KeyA);
+   * manager.simulate(id, EventType.Pressed);
+   * ```
+   */
+  #[napi]
+  pub fn simulate(&self, id: u32, event_type: EventType) -> bool {
+    let state = match event_type {
+      EventType::Pressed => HotKeyState::Pressed,
+      EventType::Released => HotKeyState::Released,
+      EventType::Registered
+      | EventType::Unregistered
+      | EventType::MultiTap
+      | EventType::LongPress
+      | EventType::Sequence => return false,
+    };
+
+    let lock = lock_hotk(&self.hotk);
+    lock
+      .dispatcher
+      .dispatch(GlobalHotKeyEvent { id, state }, true)
+  }
+
+  /**
+   * Also sends a synthetic `Registered` or `Unregistered` event through the event callbacks whenever a hotkey
+   * is registered with or unregistered from the OS, so a UI can follow the bindings from the event stream alone.
+   *
+   * Repeated registrations of a combination only produce an event for the first one, and unregistrations
+   * for the last one. Disabled by default.
+   *
+   * @example
+   * ```js
+   * import { EventType, hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setEmitLifecycle(true);
+   * manager.init(event => {
+   *   if (event.eventType === EventType.Registered) addBinding(event.id);
+   * });
+   * ```
+   */
+  #[napi]
+  pub fn set_emit_lifecycle(&self, enabled: bool) {
+    lock_hotk(&self.hotk).dispatcher.set_emit_lifecycle(enabled);
+  }
+
+  /**
+   * Preallocates room for at least `additional` more registered hotkeys.
+   *
+   * Registering hundreds of hotkeys at once otherwise grows the internal map several times.
+   * This is only a performance hint; registration works the same without it.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.reserveCapacity(keymap.length);
+   * await manager.applyKeymapAsync(keymap);
+   * ```
+   */
+  #[napi]
+  pub fn reserve_capacity(&self, additional: u32) {
+    let lock = lock_hotk(&self.hotk);
+
+    lock.hotkeys.lock().unwrap().reserve(additional as usize);
+  }
+
+  /**
+   * Keeps delivering events of an unregistered hotkey for `graceMs` after it was unregistered.
+   *
+   * An event can reach the manager just after its hotkey was unregistered, e.g. the key-up of a hotkey
+   * unregistered on key-down; by default it is dropped. Within the grace window it is delivered as usual.
+   * Pass `0` to disable it, which is the default.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setLateEventGraceMs(250);
+   * ```
+   */
+  #[napi]
+  pub fn set_late_event_grace_ms(&self, grace_ms: u32) {
+    lock_hotk(&self.hotk)
+      .dispatcher
+      .set_late_grace(Duration::from_millis(grace_ms as u64));
+  }
+
+  /**
+   * Enables a watchdog that emits a synthetic `Released` for a hotkey still pressed after `timeoutMs`.
+   *
+   * A lost `Released` (e.g. focus changed mid keypress) otherwise leaves the hotkey held forever,
+   * breaking hold and tap logic. The real `Released`, if it arrives later, is not delivered again.
+   * Disabled by default; pass `null` or `0` to disable it.
+   *
+   * @param {number | null} timeoutMs - Milliseconds a hotkey may stay pressed.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setReleaseWatchdog(5000);
+   * ```
+   */
+  #[napi]
+  pub fn set_release_watchdog(&self, timeout_ms: Option<u32>) {
+    let timeout = timeout_ms
+      .filter(|ms| *ms > 0)
+      .map(|ms| Duration::from_millis(ms as u64));
+
+    lock_hotk(&self.hotk)
+      .dispatcher
+      .set_release_timeout(timeout);
+  }
+
+  /**
+   * Forgets which hotkeys are currently pressed, cancelling any pending watchdog `Released`.
+   *
+   * Use it after the app knows keys were released without being told, e.g. on focus loss.
+   */
+  #[napi]
+  pub fn reset_key_state(&self) {
+    lock_hotk(&self.hotk).dispatcher.reset_key_state();
+  }
+
+  /**
+   * Checks whether the key and every modifier of a combination are physically down right now.
+   *
+   * The combination doesn't need to be registered, so this can poll "is the user holding X".
+   * Other keys being down don't matter.
+   *
+   * Only Windows exposes the live key state; elsewhere this returns `false`.
+   *
+   * @example
+   * ```js
+   * import { hotk, KeyCode, Mod } from '@hotk/core';
+   *
+   * if (hotk().isComboHeld([Mod.Shift], KeyCode.Space)) {
+   *   boost();
+   * }
+   * ```
+   */
+  #[napi]
+  pub fn is_combo_held(&self, mods: Vec<Mod>, code: KeyCode) -> bool {
+    lock_hotk(&self.hotk).manager.is_combo_held(
+      mods.iter().map(|m| m.global_hotkeys()).collect(),
+      code.global_hotkeys(),
+    )
+  }
+
+  /**
+   * Whether the auto-repeat of a held hotkey is ignored, so each press yields a single `Pressed`.
+   *
+   * Windows sends the hotkey again on every key repeat while it is held; the other platforms only report
+   * the first press. This is enabled by default, so all platforms behave the same.
+   *
+   * Only Windows reports repeats; elsewhere this does nothing and returns `false`.
+   *
+   * @returns `true` if the setting was applied.
+   */
+  #[napi]
+  pub fn set_ignore_os_repeat(&self, enabled: bool) -> bool {
+    lock_hotk(&self.hotk).manager.set_ignore_os_repeat(enabled)
+  }
+
+  /**
+   * Changes the scheduling priority of the thread running the hotkey backend.
+   *
+   * Lowering it reduces contention in apps where hotkeys are low priority, e.g. to save battery.
+   * The priority is left unchanged unless this is called.
+   *
+   * Only Windows runs the backend on its own thread; elsewhere this does nothing and returns `false`.
+   *
+   * @returns `true` if the priority was changed.
+   *
+   * @example
+   * ```js
+   * import { hotk, BackendPriority } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setBackendPriority(BackendPriority.BelowNormal);
+   * ```
+   */
+  #[napi]
+  pub fn set_backend_priority(&self, priority: BackendPriority) -> bool {
+    lock_hotk(&self.hotk).manager.set_priority(priority)
+  }
+
+  /**
+   * Returns how many actions (registrations, unregistrations, ...) were sent to the backend thread
+   * and not processed yet.
+   *
+   * It drops back to `0` once each call completes; a number that keeps growing means the backend is stalled.
+   * Only Windows runs the backend on its own thread; elsewhere this is always `0`.
+   */
+  #[napi]
+  pub fn pending_actions(&self) -> u32 {
+    lock_hotk(&self.hotk).manager.pending_actions()
+  }
+
+  /**
+   * Whether the backend can still process registrations.
+   *
+   * On Windows this is `false` once the thread running the backend message loop has exited, e.g. after a
+   * panic; nothing registers or fires after that, so the app should warn or restart. Elsewhere the backend
+   * has no thread of its own and this is always `true`.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * if (!manager.isHealthy()) console.error('The hotkey backend stopped');
+   * ```
+   */
+  #[napi]
+  pub fn is_healthy(&self) -> bool {
+    lock_hotk(&self.hotk).manager.is_healthy()
+  }
+
+  /**
+   * Returns the current scheduling priority of the backend thread, or `null` where there is none.
+   */
+  #[napi]
+  pub fn backend_priority(&self) -> Option<BackendPriority> {
+    lock_hotk(&self.hotk).manager.priority()
+  }
+
+  /**
+   * Returns the identifier of the active keyboard layout, or `null` where it cannot be read.
+   *
+   * On Windows this is the layout name (KLID) of the window in the foreground, e.g. `"00000409"` for US
+   * English, as last polled by the backend every 250 ms. Compare it over time to warn when
+   * layout-dependent bindings may have shifted. Other platforms return `null`.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * console.log(manager.currentLayout()); // "00000409"
+   * ```
+   */
+  #[napi]
+  pub fn current_layout(&self) -> Option<String> {
+    lock_hotk(&self.hotk).manager.layout()
+  }
+
+  /**
+   * Sets a callback fired with the new `currentLayout()` whenever the keyboard layout changes,
+   * so layout-dependent bindings can be re-resolved. Pass `null` to remove it.
+   *
+   * The callback does not keep the process alive. Only Windows reports layout changes, noticed within
+   * 250 ms of the switch; elsewhere this does nothing and returns `false`.
+   *
+   * @returns `true` if the callback was installed.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setOnLayoutChange(layout => console.log('Layout changed to', layout));
+   * ```
+   */
+  #[napi(ts_args_type = "onChange: ((layout: string | null) => void) | null")]
+  pub fn set_on_layout_change(
+    &self,
+    env: Env,
+    on_change: Option<JsFunction>,
+  ) -> napi::Result<bool> {
+    let hook: Option<LayoutHook> = match on_change {
+      Some(on_change) => {
+        let mut tsfn: ThreadsafeFunction<Option<String>, ErrorStrategy::Fatal> =
+          on_change.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+        tsfn.unref(&env)?;
+        Some(Box::new(move |layout| {
+          tsfn.call(layout, ThreadsafeFunctionCallMode::NonBlocking);
+        }))
+      }
+      None => None,
+    };
+
+    Ok(lock_hotk(&self.hotk).manager.set_on_layout_change(hook))
+  }
+
+  /**
+   * Sets a callback fired with the hotkey id and the reason when the backend fails on a registration after
+   * the call that requested it already returned, so the failure can't be reported by that call. Pass `null`
+   * to remove it.
+   *
+   * This happens when the backend thread handles a `register` its caller stopped waiting for, or can't restore
+   * a hotkey whose `unregister` was reported as failed. The callback does not keep the process alive. Only the
+   * Windows backend registers on its own thread; elsewhere this does nothing and returns `false`.
+   *
+   * @returns `true` if the callback was installed.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.onError((id, reason) => console.error(`Hotkey ${id} failed: ${reason}`));
+   * ```
+   */
+  #[napi(ts_args_type = "onError: ((id: number, reason: string) => void) | null")]
+  pub fn on_error(&self, env: Env, on_error: Option<JsFunction>) -> napi::Result<bool> {
+    let hook: Option<FailureHook> = match on_error {
+      Some(on_error) => {
+        let mut tsfn: ThreadsafeFunction<(u32, String), ErrorStrategy::Fatal> = on_error
+          .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<(u32, String)>| {
+            let (id, reason) = ctx.value;
+            Ok(vec![
+              ctx.env.create_uint32(id)?.into_unknown(),
+              ctx.env.create_string(&reason)?.into_unknown(),
+            ])
+          })?;
+        tsfn.unref(&env)?;
+        Some(Box::new(move |id, reason| {
+          tsfn.call((id, reason), ThreadsafeFunctionCallMode::NonBlocking);
+        }))
+      }
+      None => None,
+    };
+
+    Ok(lock_hotk(&self.hotk).manager.set_on_failure(hook))
+  }
+
+  /**
+   * Reports the combinations the user attempts that aren't registered, e.g. for kiosk apps that log
+   * blocked shortcuts. `onAttempt` receives every key pressed while a modifier is held, once per press,
+   * unless the combination is registered.
+   *
+   * Only Windows supports it, through a low-level keyboard hook (`WH_KEYBOARD_LL`) that sees every
+   * keystroke of the session: security software may flag it, and each keystroke waits on the hook, so
+   * keep it off when not needed. Elsewhere this does nothing and returns `false`.
+   *
+   * @param {boolean} enabled - Whether to install the hook; `false` removes it.
+   * @param onAttempt - Receives the attempted combination. Required when `enabled`.
+   * @returns `true` if the hook was installed or removed.
+   *
+   * @example
+   * ```js
+   * import { hotk, descToAccelerator } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setWatchUnmapped(true, desc => console.warn('Blocked', descToAccelerator(desc)));
+   * ```
+   */
+  #[napi(ts_args_type = "enabled: boolean, onAttempt?: ((desc: Desc) => void) | undefined | null")]
+  pub fn set_watch_unmapped(
+    &self,
+    env: Env,
+    enabled: bool,
+    on_attempt: Option<JsFunction>,
+  ) -> napi::Result<bool> {
+    let lock = lock_hotk(&self.hotk);
+
+    let hook: Option<UnmappedHook> = match (enabled, on_attempt) {
+      (false, _) => None,
+      (true, None) => return Ok(false),
+      (true, Some(on_attempt)) => {
+        let mut tsfn: ThreadsafeFunction<Desc, ErrorStrategy::Fatal> =
+          on_attempt.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+        tsfn.unref(&env)?;
+        let hotkeys = lock.hotkeys.clone();
+        Some(Box::new(move |mods, key| {
+          if hotkeys
+            .lock()
+            .unwrap()
+            .contains_key(&HotKey::new(Some(mods), key).id)
+          {
+            return;
+          }
+          let Some(code) = KeyCode::from_global_hotkeys(key) else {
+            return;
+          };
+          let mods = mods
+            .iter()
+            .filter_map(Mod::from_global_hotkeys)
+            .collect::<Vec<_>>();
+          tsfn.call(
+            Desc::new(code, mods),
+            ThreadsafeFunctionCallMode::NonBlocking,
+          );
+        }))
+      }
+    };
+
+    Ok(lock.manager.set_watch_unmapped(hook))
+  }
+
+  /**
+   * Starts appending every dispatched event to the file at `path`, one line per event
+   * (`<unix ms> <id> <event type> <mods+code>`), to debug hotkeys that fire unexpectedly.
+   *
+   * Lines are written from a background thread, so tracing never delays event delivery.
+   * Starting a new trace stops the previous one.
+   *
+   * @param {string} path - File to append to. It is created if it does not exist.
+   * @returns {TraceResult} `Ok`, or `Error` with the reason the file could not be opened.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const result = manager.startEventTrace('hotkeys.log');
+   * if (result.code !== 'Ok') console.error(result.error);
+   * ```
+   */
+  #[napi]
+  pub fn start_event_trace(&self, path: String) -> TraceResult {
+    match EventTrace::start(&path) {
+      Ok(trace) => {
+        lock_hotk(&self.hotk).dispatcher.start_trace(trace);
+        TraceResult {
+          code: ResponseCode::Ok,
+          error: None,
+        }
+      }
+      Err(error) => TraceResult {
+        code: ResponseCode::Error,
+        error: Some(format!("Failed to open {}: {}", path, error)),
+      },
+    }
+  }
+
+  /**
+   * Applies several settings at once. Every field set in `options` is applied while holding the manager lock,
+   * so no event or other call sees only part of them; unset fields keep their current value.
+   *
+   * @param {HotkOptions} options - Settings to apply.
+   *
+   * @example
+   * ```js
+   * import { hotk, QueuePolicy } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.configure({
+   *   emitLifecycle: true,
+   *   queueLimit: 64,
+   *   queuePolicy: QueuePolicy.DropOldest,
+   *   instanceName: 'settings-window',
+   * });
+   * ```
+   */
+  #[napi]
+  pub fn configure(&self, options: HotkOptions) {
+    let mut lock = lock_hotk(&self.hotk);
+
+    lock.dispatcher.configure(&options);
+    if let Some(mods) = options.ignored_modifiers {
+      lock.ignored_modifiers = mods;
+    }
+    if let Some(enabled) = options.ignore_os_repeat {
+      lock.manager.set_ignore_os_repeat(enabled);
+    }
+  }
+
+  /**
+   * Returns the current value of every setting `configure` accepts, whether it was set through `configure` or
+   * the individual setters. Passing the result back to `configure` restores them.
+   *
+   * Disabled settings are reported with their disabling value: `0` for `lateEventGraceMs`, `releaseWatchdogMs`
+   * and `queueLimit`, and an empty string for `instanceName`. `ignoreOsRepeat` is `false` where unsupported.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * const saved = manager.currentConfig();
+   * manager.configure({ emitLifecycle: true });
+   * manager.configure(saved);
+   * ```
+   */
+  #[napi]
+  pub fn current_config(&self) -> HotkOptions {
+    let lock = lock_hotk(&self.hotk);
+
+    HotkOptions {
+      ignore_os_repeat: Some(lock.manager.ignores_os_repeat()),
+      ignored_modifiers: Some(lock.ignored_modifiers.clone()),
+      ..lock.dispatcher.options()
+    }
+  }
+
+  /**
+   * Names this instance in trace lines (`[name] <unix ms> <id> <event type> <mods+code>`), to tell apart the
+   * logs of several managers, e.g. across worker threads. Pass an empty string to remove the name.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.setInstanceName('settings-window');
+   * manager.enableConsoleTrace(true);
+   * ```
+   */
+  #[napi]
+  pub fn set_instance_name(&self, name: String) {
+    let name = Some(name).filter(|name| !name.is_empty());
+    lock_hotk(&self.hotk).dispatcher.set_instance_name(name);
+  }
+
+  /**
+   * Prints every dispatched event to stderr, in the same format as `startEventTrace`, to check whether a
+   * hotkey fires at all. Events are still delivered to the callbacks as usual. Off by default.
+   *
+   * Lines are printed from a background thread, so this never delays event delivery.
+   *
+   * @example
+   * ```js
+   * import { hotk } from '@hotk/core';
+   *
+   * const manager = hotk();
+   * manager.enableConsoleTrace(true);
+   * ```
+   */
+  #[napi]
+  pub fn enable_console_trace(&self, enabled: bool) {
+    let dispatcher = lock_hotk(&self.hotk).dispatcher.clone();
+    dispatcher.set_console_trace(enabled);
+  }
+
+  /**
+   * Stops the event trace started with `startEventTrace`, once every pending line is written.
+   *
+   * @returns `true` if a trace was running.
+   */
+  #[napi]
+  pub fn stop_event_trace(&self) -> bool {
+    let dispatcher = lock_hotk(&self.hotk).dispatcher.clone();
+    dispatcher.stop_trace()
+  }
+
+  /**
+   * Stops listening for hotkey events.
+   *
+   * This is required to allow the Node.js process to exit when `init` was called without `unref: true`.
+   *
+   * Teardown happens in a fixed order: events stop being routed to JS, the `init` callback is aborted and
+   * released, then the per-hotkey (`registerIn`, `on`, `setOnKey`) and batch callbacks and every pending
+   * event are dropped. No callback fires afterwards, even for events that reach the manager late. Hotkeys
+   * stay registered, and `init` can be called again to listen anew, as many times as needed.
+   *
+   * The process-wide `global_hotkey` event handler is left installed: the crate keeps it in a `OnceCell`,
+   * so it can't be replaced or removed once set. It holds no JS callback, only the routing to the manager.
+   *
+   * Events fired but not yet delivered are dropped. With `flush`, they are first delivered to the `init`
+   * callback synchronously, in the order they were fired, so they are all observed before `destroy` returns.
+   * Events still waiting in the pre-init buffer (`init` never called) have no callback to go to and are
+   * dropped either way.
+   *
+   * @param {DestroyOptions} [options] - Optional teardown behaviour.
+   *
+   * @example
+   * ```js
+   * import { hotk, Mod, KeyCode } from '@hotk/core';
+   *
+   * // Get the singleton instance
+   * const manager = hotk();
+   *
+   * // Register Ctrl + A as a hotkey
+   * const result = manager.register([Mod.Control], KeyCode.KeyA);
+   *
+   * if (result.isOk()) {
+   *   console.log('Hotkey successfully registered');
+   * } else {
+   *   console.error('Failed to register hotkey');
+   * }
+   *
+   * // Start listening for hotkey events without unref
+   * manager.init((event) => {
+   *   console.log('Received event:', event);
+   * });
+   *
+   * // Stop listening after 3 seconds so the process can exit
+   * setTimeout(() => {
+   *   manager.destroy();
+   * }, 3000);
+   * ```
+   */
+  #[napi]
+  pub fn destroy(&mut self, env: Env, options: Option<DestroyOptions>) -> napi::Result<()> {
+    let flush = options.and_then(|o| o.flush).unwrap_or(false);
+
+    // A callback that throws during the flush still leaves the manager detached.
+    let flushed = if flush {
+      let dispatcher = lock_hotk(&self.hotk).dispatcher.clone();
+      dispatcher.deliver(env)
+    } else {
+      Ok(())
+    };
+
+    let mut lock = lock_hotk(&self.hotk);
+    lock.profile_callbacks.clear();
+    let dispatcher = lock.dispatcher.clone();
+    drop(lock);
+    dispatcher.detach(env)?;
+    flushed
+  }
+}
+
+// Fault injection for the specs, only built with the `test-hooks` feature.
+#[cfg(feature = "test-hooks")]
+#[napi]
+impl HotkManager {
+  /**
+   * Makes the backend report the current keyboard layout as a switch on its next poll.
+   *
+   * @returns `false` where layout changes are not reported.
+   */
+  #[napi]
+  pub fn simulate_layout_change(&self) -> bool {
+    lock_hotk(&self.hotk).manager.simulate_layout_change()
+  }
+
+  /**
+   * Makes the conversion of the next `count` events delivered to the `init` callback fail,
+   * as a failing property assignment would. Useful to test `droppedEvents`.
+   */
+  #[napi]
+  pub fn simulate_conversion_failures(&self, count: u32) {
+    lock_hotk(&self.hotk).dispatcher.fail_conversions(count);
+  }
+
+  /**
+   * Holds the manager lock for `holdMs` from another thread, so the next calls wait for it. Useful to test
+   * `lockStats`.
+   */
+  #[napi]
+  pub fn simulate_lock_contention(&self, hold_ms: u32) {
+    let hotk = self.hotk.clone();
+    let (taken, on_taken) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+      let _hotk = hotk.lock().unwrap();
+      let _ = taken.send(());
+      std::thread::sleep(Duration::from_millis(hold_ms as u64));
+    });
+    let _ = on_taken.recv();
+  }
+}
+
+/**
+ * A single registration made by `HotkManager.registerHandle`, to unregister it without keeping track of
+ * its combination.
+ *
+ * Dropping the handle doesn't unregister the hotkey, as it would then depend on when the garbage collector
+ * runs; it stays registered until `unregister` is called on the handle or on the manager. The handle itself
+ * holds no resource, so an unused one is simply collected.
+ */
+#[napi]
+pub struct RegistrationHandle {
+  hotk: Arc<Mutex<InnerHotk>>,
+  desc: Desc,
+  response: HotkReponse,
+  registered: bool,
+}
+
+#[napi]
+impl RegistrationHandle {
+  /**
+   * Id of the hotkey, as returned by `getHotkeyId`.
+   */
+  #[napi(getter)]
+  pub fn id(&self) -> u32 {
+    self.response.id
+  }
+
+  /**
+   * Whether the handle still holds its registration: `false` if registering failed or once `unregister`
+   * was called.
+   */
+  #[napi(getter)]
+  pub fn registered(&self) -> bool {
+    self.registered
+  }
+
+  /**
+   * Why registering failed, or `null` if it succeeded.
+   */
+  #[napi(getter)]
+  pub fn error(&self) -> Option<String> {
+    self.response.error.clone()
+  }
+
+  /**
+   * Removes the registration of this handle. Like `HotkManager.unregister`, a combination registered several
+   * times stays registered until every registration is removed.
+   *
+   * @returns The response of the unregistration; an error if the handle holds no registration.
+   */
+  #[napi]
+  pub fn unregister(&mut self) -> HotkReponse {
+    if !self.registered {
+      return HotkReponse::error(
+        Operation::Unregister,
+        self.response.id,
+        ErrorKind::NotRegistered,
+        "The handle holds no registration".to_string(),
+      );
+    }
+
+    let response = lock_hotk(&self.hotk).unregister(self.desc.mods.clone(), self.desc.code);
+    if response.is_ok() {
+      self.registered = false;
+    }
+    response
+  }
+}
+
+/**
+ * Options for `HotkManager.destroy`.
+ *
+ * Properties:
+ * - `flush` (boolean, optional): Deliver pending events before detaching the callback. Defaults to `false`.
+ *   If the callback throws, the remaining events are still delivered and `destroy` rethrows the first error.
+ */
+#[napi(object)]
+pub struct DestroyOptions {
+  pub flush: Option<bool>,
+}
+
+/**
+ * Options for `HotkManager.register`.
+ *
+ * Properties:
+ * - `exactModifiers` (boolean, optional): Drop the events of the hotkey unless exactly its modifiers are held,
+ *   checked against the live key state, so `Ctrl+A` doesn't fire while `Ctrl+Shift+A` is held.
+ *   Only Windows exposes the live key state; elsewhere the events are never dropped. Defaults to `false`.
+ * - `idempotent` (boolean, optional): Registering a combination that is already registered succeeds with its id
+ *   without counting another registration, so a single `unregister` releases it. Suited for re-applying a
+ *   configuration. Defaults to `false`.
+ */
+#[napi(object)]
+pub struct RegisterOptions {
+  pub exact_modifiers: Option<bool>,
+  pub idempotent: Option<bool>,
+}
+
+/**
+ * Settings for `HotkManager.configure`. Fields left unset keep their current value.
+ *
+ * Properties:
+ * - `bufferPreinit` (boolean, optional): See `setBufferPreinit`.
+ * - `emitLifecycle` (boolean, optional): See `setEmitLifecycle`.
+ * - `lateEventGraceMs` (number, optional): See `setLateEventGraceMs`.
+ * - `releaseWatchdogMs` (number, optional): See `setReleaseWatchdog`; `0` disables it.
+ * - `queueLimit` (number, optional): See `setQueueLimit`; `0` removes the bound.
+ * - `queuePolicy` (QueuePolicy, optional): Policy of the queue bound. Without a `queueLimit` it applies to the
+ *   current one. Defaults to the current policy, or `DropNewest` if there is none.
+ * - `ignoreOsRepeat` (boolean, optional): See `setIgnoreOsRepeat`.
+ * - `ignoredModifiers` (Mod[], optional): See `setIgnoredModifiers`.
+ * - `instanceName` (string, optional): See `setInstanceName`; an empty string removes the name.
+ * - `consoleTrace` (boolean, optional): See `enableConsoleTrace`.
+ */
+#[napi(object)]
+pub struct HotkOptions {
+  pub buffer_preinit: Option<bool>,
+  pub emit_lifecycle: Option<bool>,
+  pub late_event_grace_ms: Option<u32>,
+  pub release_watchdog_ms: Option<u32>,
+  pub queue_limit: Option<u32>,
+  pub queue_policy: Option<QueuePolicy>,
+  pub ignore_os_repeat: Option<bool>,
+  pub ignored_modifiers: Option<Vec<Mod>>,
+  pub instance_name: Option<String>,
+  pub console_trace: Option<bool>,
+}
+
+/**
+ * Options for `HotkManager.deferReleased`.
+ *
+ * Properties:
+ * - `dropWithheld` (boolean, optional): Drop the withheld `Released` instead of delivering it when the deferral
+ *   is disabled. Defaults to `false`.
+ */
+#[napi(object)]
+pub struct DeferReleasedOptions {
+  pub drop_withheld: Option<bool>,
+}
+
+/**
+ * Options for `HotkManager.setBatchCallback`.
+ *
+ * Properties:
+ * - `intervalMs` (number, optional): Milliseconds between batches. Defaults to `16`.
+ * - `maxSize` (number, optional): Number of waiting events that triggers a batch right away. Defaults to `256`.
+ */
+#[napi(object)]
+pub struct BatchOptions {
+  pub interval_ms: Option<u32>,
+  pub max_size: Option<u32>,
+}
+
+/**
+ * Label of a registered hotkey, see `HotkManager.labelsById`.
+ *
+ * Properties:
+ * - `id` (number): The hotkey id, as found in events.
+ * - `label` (string): Its canonical accelerator, e.g. `Control+Shift+KeyK`.
+ */
+#[napi(object)]
+pub struct HotkeyLabel {
+  pub id: u32,
+  pub label: String,
+}
+
+/**
+ * Upstream view of a registered hotkey, see `HotkManager.hotkeyForId`.
+ *
+ * Properties:
+ * - `rawId` (number): The `global_hotkey` `HotKey` id.
+ * - `code` (KeyCode): The key code it was registered with.
+ * - `mods` (Mod[]): The modifiers it was registered with.
+ * - `modifiersBits` (number): The `global_hotkey` `Modifiers` bits, with `Meta` folded into `Super`.
+ */
+#[napi(object)]
+pub struct HotKeyInfo {
+  pub raw_id: u32,
+  pub code: KeyCode,
+  pub mods: Vec<Mod>,
+  pub modifiers_bits: u32,
+}
+
+/**
+ * Returns a singleton instance of `HotkManager`.
+ *
+ * All `HotkManager` instances share the same internal state, allowing global coordination of hotkeys across your application.
+ *
+ * Returns `null` if the hotkey backend could not be created, e.g. on Linux without an X server; `hotkError()`
+ * tells why. The backend is created once, so later calls return the same result.
+ *
+ * @example
+ * ```js
+ * import { hotk, hotkError } from '@hotk/core';
+ *
+ * const manager = hotk();
+ * if (!manager) throw new Error(hotkError());
+ * ```
+ */
+#[napi]
+pub fn hotk() -> Option<HotkManager> {
+  let hotk = HOTK.as_ref().ok()?.clone();
+  Some(HotkManager { hotk })
+}
+
+/**
+ * Returns why the manager could not be created, when `hotk()` returns `null`.
+ *
+ * @returns The reason, or `null` if the manager was created.
+ *
+ * @example
+ * ```js
+ * import { hotk, hotkError } from '@hotk/core';
+ *
+ * const manager = hotk();
+ * if (!manager) console.error(hotkError());
+ * ```
+ */
+#[napi]
+pub fn hotk_error() -> Option<String> {
+  HOTK.as_ref().err().map(|error| error.to_string())
 }
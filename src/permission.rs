@@ -0,0 +1,99 @@
+#[cfg(target_os = "macos")]
+mod macos {
+  use std::ffi::c_void;
+
+  #[repr(C)]
+  pub struct CFDictionaryCallBacks {
+    _private: [u8; 0],
+  }
+
+  #[link(name = "ApplicationServices", kind = "framework")]
+  extern "C" {
+    pub static kAXTrustedCheckOptionPrompt: *const c_void;
+    pub fn AXIsProcessTrusted() -> u8;
+    pub fn AXIsProcessTrustedWithOptions(options: *const c_void) -> u8;
+  }
+
+  #[link(name = "CoreFoundation", kind = "framework")]
+  extern "C" {
+    pub static kCFBooleanTrue: *const c_void;
+    pub static kCFTypeDictionaryKeyCallBacks: CFDictionaryCallBacks;
+    pub static kCFTypeDictionaryValueCallBacks: CFDictionaryCallBacks;
+    pub fn CFDictionaryCreate(
+      allocator: *const c_void,
+      keys: *const *const c_void,
+      values: *const *const c_void,
+      count: isize,
+      key_callbacks: *const CFDictionaryCallBacks,
+      value_callbacks: *const CFDictionaryCallBacks,
+    ) -> *const c_void;
+    pub fn CFRelease(cf: *const c_void);
+  }
+}
+
+/**
+ * Checks whether the process may observe global keyboard input.
+ *
+ * On macOS global hotkeys need the Accessibility / Input Monitoring permission; without it registration
+ * succeeds but no event is ever received. Other platforms need no permission and always return `true`.
+ *
+ * @returns {boolean} `true` if the process is trusted for input.
+ *
+ * @example
+ * ```js
+ * import { hasInputPermission, requestInputPermission } from '@hotk/core';
+ *
+ * if (!hasInputPermission()) {
+ *   requestInputPermission();
+ * }
+ * ```
+ */
+#[napi]
+#[cfg_attr(test, allow(dead_code))]
+pub fn has_input_permission() -> bool {
+  #[cfg(target_os = "macos")]
+  let trusted = unsafe { macos::AXIsProcessTrusted() != 0 };
+
+  #[cfg(not(target_os = "macos"))]
+  let trusted = true;
+
+  trusted
+}
+
+/**
+ * Asks the user to grant input permission, showing the system prompt if it is not granted yet.
+ *
+ * The prompt does not block: the returned value is the permission at the time of the call, so
+ * poll `hasInputPermission` to know when it is granted. Other platforms always return `true`.
+ *
+ * @returns {boolean} `true` if the permission is already granted.
+ */
+#[napi]
+#[cfg_attr(test, allow(dead_code))]
+pub fn request_input_permission() -> bool {
+  #[cfg(target_os = "macos")]
+  let trusted = unsafe {
+    use std::ptr::null;
+
+    let keys = [macos::kAXTrustedCheckOptionPrompt];
+    let values = [macos::kCFBooleanTrue];
+    let options = macos::CFDictionaryCreate(
+      null(),
+      keys.as_ptr(),
+      values.as_ptr(),
+      1,
+      &macos::kCFTypeDictionaryKeyCallBacks,
+      &macos::kCFTypeDictionaryValueCallBacks,
+    );
+    let trusted = macos::AXIsProcessTrustedWithOptions(options) != 0;
+    if !options.is_null() {
+      macos::CFRelease(options);
+    }
+    trusted
+  };
+
+  #[cfg(not(target_os = "macos"))]
+  let trusted = true;
+
+  trusted
+}
@@ -1,17 +1,38 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
 use global_hotkey::hotkey::{Code, HotKey, Modifiers};
 use global_hotkey::GlobalHotKeyManager;
 
+use crate::code::BackendPriority;
+use crate::events::CreateError;
 use crate::events::Response;
 
+pub type LayoutHook = Box<dyn Fn(Option<String>) + Send>;
+pub type UnmappedHook = Box<dyn Fn(Modifiers, Code) + Send>;
+pub type FailureHook = Box<dyn Fn(u32, String) + Send>;
+
+/**
+ * Modifiers physically held right now, `None` as this backend can't read the key state.
+ */
+pub fn held_modifiers() -> Option<Modifiers> {
+  None
+}
+
 pub struct Manager {
   manager: GlobalHotKeyManager,
+  // The X11 and macOS backends report success when unregistering an unknown hotkey, unlike Windows.
+  registered: Mutex<HashSet<u32>>,
 }
 
 impl Manager {
-  pub fn new() -> Option<Self> {
+  pub fn new() -> Result<Self, CreateError> {
     GlobalHotKeyManager::new()
-      .map(|manager| Self { manager })
-      .ok()
+      .map(|manager| Self {
+        manager,
+        registered: Mutex::new(HashSet::new()),
+      })
+      .map_err(CreateError::Backend)
   }
 
   pub fn register(&self, mods: Vec<Modifiers>, key: Code) -> (HotKey, Response) {
@@ -19,7 +40,10 @@ impl Manager {
     let hotkey = HotKey::new(Some(mods), key);
 
     let r = match self.manager.register(hotkey) {
-      Ok(()) => Response::OkRegister { id: hotkey.id },
+      Ok(()) => {
+        self.registered.lock().unwrap().insert(hotkey.id);
+        Response::OkRegister { id: hotkey.id }
+      }
       Err(error) => Response::ErrorRegister {
         id: hotkey.id,
         error,
@@ -29,12 +53,34 @@ impl Manager {
     (hotkey, r)
   }
 
+  pub fn register_many(&self, combos: Vec<(Vec<Modifiers>, Code)>) -> Vec<(HotKey, Response)> {
+    combos
+      .into_iter()
+      .map(|(mods, key)| self.register(mods, key))
+      .collect()
+  }
+
   pub fn unregister(&self, mods: Vec<Modifiers>, key: Code) -> (HotKey, Response) {
     let mods = mods.into_iter().fold(Modifiers::empty(), |acc, m| acc | m);
     let hotkey = HotKey::new(Some(mods), key);
 
-    let r = match self.manager.register(hotkey) {
-      Ok(()) => Response::OkUnregister { id: hotkey.id },
+    let mut registered = self.registered.lock().unwrap();
+    if !registered.contains(&hotkey.id) {
+      let error = global_hotkey::Error::FailedToUnRegister(hotkey);
+      return (
+        hotkey,
+        Response::ErrorUnregister {
+          id: hotkey.id,
+          error,
+        },
+      );
+    }
+
+    let r = match self.manager.unregister(hotkey) {
+      Ok(()) => {
+        registered.remove(&hotkey.id);
+        Response::OkUnregister { id: hotkey.id }
+      }
       Err(error) => Response::ErrorUnregister {
         id: hotkey.id,
         error,
@@ -43,4 +89,57 @@ impl Manager {
 
     (hotkey, r)
   }
+
+  pub fn set_priority(&self, _priority: BackendPriority) -> bool {
+    false
+  }
+
+  pub fn priority(&self) -> Option<BackendPriority> {
+    None
+  }
+
+  pub fn pending_actions(&self) -> u32 {
+    0
+  }
+
+  pub fn is_healthy(&self) -> bool {
+    true
+  }
+
+  pub fn recreate(&mut self) -> Result<bool, CreateError> {
+    Ok(false)
+  }
+
+  pub fn layout(&self) -> Option<String> {
+    None
+  }
+
+  pub fn set_on_layout_change(&self, _hook: Option<LayoutHook>) -> bool {
+    false
+  }
+
+  pub fn set_on_failure(&self, _hook: Option<FailureHook>) -> bool {
+    false
+  }
+
+  pub fn is_combo_held(&self, _mods: Vec<Modifiers>, _key: Code) -> bool {
+    false
+  }
+
+  pub fn set_ignore_os_repeat(&self, _enabled: bool) -> bool {
+    false
+  }
+
+  pub fn ignores_os_repeat(&self) -> bool {
+    false
+  }
+
+  pub fn set_watch_unmapped(&self, _hook: Option<UnmappedHook>) -> bool {
+    false
+  }
+
+  #[cfg(feature = "test-hooks")]
+  pub fn simulate_layout_change(&self) -> bool {
+    false
+  }
 }
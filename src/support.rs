@@ -0,0 +1,688 @@
+use global_hotkey::hotkey::Modifiers;
+
+use crate::code::platform_primary_meta;
+use crate::code::Desc;
+use crate::code::KeyCategory;
+use crate::code::KeyCode;
+use crate::code::Mod;
+use crate::events::Warning;
+use crate::events::WarningCode;
+
+/**
+ * Whether the OS backend of the current platform can register `code`.
+ *
+ * Mirrors the key tables of the `global_hotkey` backends; anything else is rejected at registration time.
+ */
+#[cfg(target_os = "windows")]
+pub fn key_supported(code: KeyCode) -> bool {
+  use KeyCode::*;
+
+  matches!(
+    code,
+    KeyA
+      | KeyB
+      | KeyC
+      | KeyD
+      | KeyE
+      | KeyF
+      | KeyG
+      | KeyH
+      | KeyI
+      | KeyJ
+      | KeyK
+      | KeyL
+      | KeyM
+      | KeyN
+      | KeyO
+      | KeyP
+      | KeyQ
+      | KeyR
+      | KeyS
+      | KeyT
+      | KeyU
+      | KeyV
+      | KeyW
+      | KeyX
+      | KeyY
+      | KeyZ
+      | Digit0
+      | Digit1
+      | Digit2
+      | Digit3
+      | Digit4
+      | Digit5
+      | Digit6
+      | Digit7
+      | Digit8
+      | Digit9
+      | Equal
+      | Comma
+      | Minus
+      | Period
+      | Semicolon
+      | Slash
+      | Backquote
+      | BracketLeft
+      | Backslash
+      | BracketRight
+      | Quote
+      | Backspace
+      | Tab
+      | Space
+      | Enter
+      | CapsLock
+      | Escape
+      | PageUp
+      | PageDown
+      | End
+      | Home
+      | ArrowLeft
+      | ArrowUp
+      | ArrowRight
+      | ArrowDown
+      | PrintScreen
+      | Insert
+      | Delete
+      | F1
+      | F2
+      | F3
+      | F4
+      | F5
+      | F6
+      | F7
+      | F8
+      | F9
+      | F10
+      | F11
+      | F12
+      | F13
+      | F14
+      | F15
+      | F16
+      | F17
+      | F18
+      | F19
+      | F20
+      | F21
+      | F22
+      | F23
+      | F24
+      | NumLock
+      | Numpad0
+      | Numpad1
+      | Numpad2
+      | Numpad3
+      | Numpad4
+      | Numpad5
+      | Numpad6
+      | Numpad7
+      | Numpad8
+      | Numpad9
+      | NumpadAdd
+      | NumpadDecimal
+      | NumpadDivide
+      | NumpadEnter
+      | NumpadEqual
+      | NumpadMultiply
+      | NumpadSubtract
+      | ScrollLock
+      | AudioVolumeDown
+      | AudioVolumeUp
+      | AudioVolumeMute
+      | MediaPlay
+      | MediaPause
+      | MediaPlayPause
+      | MediaStop
+      | MediaTrackNext
+      | MediaTrackPrevious
+      | Pause
+  )
+}
+
+#[cfg(target_os = "macos")]
+pub fn key_supported(code: KeyCode) -> bool {
+  use KeyCode::*;
+
+  matches!(
+    code,
+    KeyA
+      | KeyB
+      | KeyC
+      | KeyD
+      | KeyE
+      | KeyF
+      | KeyG
+      | KeyH
+      | KeyI
+      | KeyJ
+      | KeyK
+      | KeyL
+      | KeyM
+      | KeyN
+      | KeyO
+      | KeyP
+      | KeyQ
+      | KeyR
+      | KeyS
+      | KeyT
+      | KeyU
+      | KeyV
+      | KeyW
+      | KeyX
+      | KeyY
+      | KeyZ
+      | Digit0
+      | Digit1
+      | Digit2
+      | Digit3
+      | Digit4
+      | Digit5
+      | Digit6
+      | Digit7
+      | Digit8
+      | Digit9
+      | Equal
+      | Minus
+      | BracketRight
+      | BracketLeft
+      | Enter
+      | Quote
+      | Semicolon
+      | Backslash
+      | Comma
+      | Slash
+      | Period
+      | Tab
+      | Space
+      | Backquote
+      | Backspace
+      | Escape
+      | F1
+      | F2
+      | F3
+      | F4
+      | F5
+      | F6
+      | F7
+      | F8
+      | F9
+      | F10
+      | F11
+      | F12
+      | F13
+      | F14
+      | F15
+      | F16
+      | F17
+      | F18
+      | F19
+      | F20
+      | NumpadDecimal
+      | NumpadMultiply
+      | NumpadAdd
+      | NumLock
+      | AudioVolumeUp
+      | AudioVolumeDown
+      | AudioVolumeMute
+      | NumpadDivide
+      | NumpadEnter
+      | NumpadSubtract
+      | NumpadEqual
+      | Numpad0
+      | Numpad1
+      | Numpad2
+      | Numpad3
+      | Numpad4
+      | Numpad5
+      | Numpad6
+      | Numpad7
+      | Numpad8
+      | Numpad9
+      | Insert
+      | Home
+      | PageUp
+      | Delete
+      | End
+      | PageDown
+      | ArrowLeft
+      | ArrowRight
+      | ArrowDown
+      | ArrowUp
+      | CapsLock
+      | PrintScreen
+      | MediaPlayPause
+      | MediaTrackNext
+      | MediaTrackPrevious
+      | MediaFastForward
+      | MediaRewind
+  )
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub fn key_supported(code: KeyCode) -> bool {
+  use KeyCode::*;
+
+  matches!(
+    code,
+    KeyA
+      | KeyB
+      | KeyC
+      | KeyD
+      | KeyE
+      | KeyF
+      | KeyG
+      | KeyH
+      | KeyI
+      | KeyJ
+      | KeyK
+      | KeyL
+      | KeyM
+      | KeyN
+      | KeyO
+      | KeyP
+      | KeyQ
+      | KeyR
+      | KeyS
+      | KeyT
+      | KeyU
+      | KeyV
+      | KeyW
+      | KeyX
+      | KeyY
+      | KeyZ
+      | Backslash
+      | BracketLeft
+      | BracketRight
+      | Backquote
+      | Comma
+      | Digit0
+      | Digit1
+      | Digit2
+      | Digit3
+      | Digit4
+      | Digit5
+      | Digit6
+      | Digit7
+      | Digit8
+      | Digit9
+      | Equal
+      | Minus
+      | Period
+      | Quote
+      | Semicolon
+      | Slash
+      | Backspace
+      | CapsLock
+      | Enter
+      | Space
+      | Tab
+      | Delete
+      | End
+      | Home
+      | Insert
+      | PageDown
+      | PageUp
+      | ArrowDown
+      | ArrowLeft
+      | ArrowRight
+      | ArrowUp
+      | Numpad0
+      | Numpad1
+      | Numpad2
+      | Numpad3
+      | Numpad4
+      | Numpad5
+      | Numpad6
+      | Numpad7
+      | Numpad8
+      | Numpad9
+      | NumpadAdd
+      | NumpadDecimal
+      | NumpadDivide
+      | NumpadMultiply
+      | NumpadSubtract
+      | Escape
+      | PrintScreen
+      | ScrollLock
+      | NumLock
+      | F1
+      | F2
+      | F3
+      | F4
+      | F5
+      | F6
+      | F7
+      | F8
+      | F9
+      | F10
+      | F11
+      | F12
+      | AudioVolumeDown
+      | AudioVolumeMute
+      | AudioVolumeUp
+      | MediaPlay
+      | MediaPause
+      | MediaStop
+      | MediaTrackNext
+      | MediaTrackPrevious
+      | Pause
+  )
+}
+
+/**
+ * Whether the OS backends honour `modifier`.
+ *
+ * Every backend only grabs Control, Alt, Shift and Super (Meta is folded into Super),
+ * the rest only change the computed id.
+ */
+pub fn mod_supported(modifier: Mod) -> bool {
+  matches!(
+    modifier,
+    Mod::Control | Mod::Alt | Mod::Shift | Mod::Meta | Mod::Super | Mod::CommandOrControl
+  )
+}
+
+/**
+ * Returns the modifiers that can meaningfully be combined with `code` on this platform,
+ * so modifier pickers can constrain their choices.
+ *
+ * Only modifiers the OS honours are listed (`Meta` on macOS, `Super` elsewhere, for the same key), and
+ * none for keys that can't be registered. Numpad digits and the decimal key leave out `Shift`, which
+ * toggles them to their navigation meaning while NumLock is on.
+ *
+ * @example
+ * ```js
+ * import { validModifiersFor, KeyCode } from '@hotk/core';
+ *
+ * validModifiersFor(KeyCode.KeyA); // ['Control', 'Alt', 'Shift', 'Super'] on Windows and Linux
+ * ```
+ */
+#[napi]
+#[cfg_attr(test, allow(dead_code))]
+pub fn valid_modifiers_for(code: KeyCode) -> Vec<Mod> {
+  if !key_supported(code) || code.is_modifier() {
+    return vec![];
+  }
+
+  let command = platform_primary_meta();
+
+  // Operators and Enter are not affected by NumLock.
+  let numpad_value = code.category() == KeyCategory::Numpad
+    && matches!(
+      code,
+      KeyCode::Numpad0
+        | KeyCode::Numpad1
+        | KeyCode::Numpad2
+        | KeyCode::Numpad3
+        | KeyCode::Numpad4
+        | KeyCode::Numpad5
+        | KeyCode::Numpad6
+        | KeyCode::Numpad7
+        | KeyCode::Numpad8
+        | KeyCode::Numpad9
+        | KeyCode::NumpadDecimal
+    );
+
+  [Mod::Control, Mod::Alt, Mod::Shift, command]
+    .into_iter()
+    .filter(|m| !(numpad_value && matches!(m, Mod::Shift)))
+    .collect()
+}
+
+/**
+ * Modifiers the OS actually grabs for `mods`: `CommandOrControl` resolved, `Meta` folded into `Super`
+ * and modifiers the backends ignore left out.
+ */
+fn os_modifiers(mods: &[Mod]) -> Modifiers {
+  let mut mods = mods
+    .iter()
+    .filter(|m| mod_supported(**m))
+    .map(|m| m.global_hotkeys())
+    .fold(Modifiers::empty(), |acc, m| acc | m);
+  if mods.contains(Modifiers::META) {
+    mods.remove(Modifiers::META);
+    mods.insert(Modifiers::SUPER);
+  }
+  mods
+}
+
+/**
+ * Key the OS actually grabs for `code`: the Windows backend registers some codes with the same virtual key.
+ */
+fn os_key(code: KeyCode) -> KeyCode {
+  #[cfg(target_os = "windows")]
+  let key = match code {
+    KeyCode::NumpadEnter => KeyCode::Enter,
+    KeyCode::MediaPause => KeyCode::Pause,
+    // `global_hotkey` maps it to the virtual key of E.
+    KeyCode::NumpadEqual => KeyCode::KeyE,
+    other => other,
+  };
+  #[cfg(not(target_os = "windows"))]
+  let key = code;
+
+  key
+}
+
+/**
+ * Checks whether two combinations would be grabbed as the same hotkey by the OS, so they can't both
+ * be registered or one would shadow the other.
+ *
+ * Every backend matches modifiers exactly: `Control+Shift+KeyA` doesn't conflict with `Control+KeyA`.
+ * Combinations still conflict when they only differ in what the OS ignores: modifiers it doesn't grab
+ * (e.g. `Hyper`), `Meta` and `Super` (the same key), `CommandOrControl` and its resolved modifier,
+ * and on Windows keys sharing a virtual key (`NumpadEnter` and `Enter`).
+ *
+ * @example
+ * ```js
+ * import { combosConflict, KeyCode, Mod } from '@hotk/core';
+ *
+ * combosConflict({ mods: [Mod.Control], code: KeyCode.KeyA }, { mods: [Mod.Control, Mod.Hyper], code: KeyCode.KeyA }); // true
+ * combosConflict({ mods: [Mod.Control], code: KeyCode.KeyA }, { mods: [Mod.Control, Mod.Shift], code: KeyCode.KeyA }); // false
+ * ```
+ */
+#[napi]
+#[cfg_attr(test, allow(dead_code))]
+pub fn combos_conflict(a: Desc, b: Desc) -> bool {
+  os_key(a.code) == os_key(b.code) && os_modifiers(&a.mods) == os_modifiers(&b.mods)
+}
+
+/**
+ * Returns a description of the OS feature that owns the combination, if it is reserved on this platform.
+ */
+pub fn reserved_combo(mods: &[Mod], code: KeyCode) -> Option<&'static str> {
+  let mut mods = mods
+    .iter()
+    .map(|m| m.global_hotkeys())
+    .fold(Modifiers::empty(), |acc, m| acc | m);
+  // Same folding `HotKey::new` applies.
+  if mods.contains(Modifiers::META) {
+    mods.remove(Modifiers::META);
+    mods.insert(Modifiers::SUPER);
+  }
+  let is = |expected: Modifiers| mods == expected;
+
+  #[cfg(target_os = "windows")]
+  let reason = match code {
+    KeyCode::Delete if is(Modifiers::CONTROL | Modifiers::ALT) => Some("Secure attention sequence"),
+    KeyCode::Escape if is(Modifiers::CONTROL | Modifiers::SHIFT) => Some("Task Manager"),
+    KeyCode::KeyL if is(Modifiers::SUPER) => Some("Lock workstation"),
+    KeyCode::Tab if is(Modifiers::ALT) => Some("Window switcher"),
+    _ => None,
+  };
+
+  #[cfg(target_os = "macos")]
+  let reason = match code {
+    KeyCode::Tab if is(Modifiers::SUPER) => Some("Application switcher"),
+    KeyCode::Space if is(Modifiers::SUPER) => Some("Spotlight"),
+    KeyCode::Digit3 | KeyCode::Digit4 | KeyCode::Digit5
+      if is(Modifiers::SUPER | Modifiers::SHIFT) =>
+    {
+      Some("Screenshot")
+    }
+    KeyCode::KeyQ if is(Modifiers::SUPER | Modifiers::CONTROL) => Some("Lock screen"),
+    KeyCode::Escape if is(Modifiers::SUPER | Modifiers::ALT) => Some("Force quit"),
+    _ => None,
+  };
+
+  #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+  let reason = match code {
+    KeyCode::Delete if is(Modifiers::CONTROL | Modifiers::ALT) => Some("Secure attention sequence"),
+    KeyCode::Tab if is(Modifiers::ALT) => Some("Window switcher"),
+    KeyCode::F1
+    | KeyCode::F2
+    | KeyCode::F3
+    | KeyCode::F4
+    | KeyCode::F5
+    | KeyCode::F6
+    | KeyCode::F7
+    | KeyCode::F8
+    | KeyCode::F9
+    | KeyCode::F10
+    | KeyCode::F11
+    | KeyCode::F12
+      if is(Modifiers::CONTROL | Modifiers::ALT) =>
+    {
+      Some("Virtual terminal switch")
+    }
+    _ => None,
+  };
+
+  reason
+}
+
+/**
+ * Keys other applications rely on for text entry and navigation.
+ */
+pub fn risky_key(code: KeyCode) -> bool {
+  matches!(
+    code,
+    KeyCode::Escape
+      | KeyCode::Enter
+      | KeyCode::NumpadEnter
+      | KeyCode::Tab
+      | KeyCode::Space
+      | KeyCode::Backspace
+      | KeyCode::Delete
+      | KeyCode::CapsLock
+      | KeyCode::PrintScreen
+  )
+}
+
+/**
+ * Collects every non-fatal problem of registering `mods` + `code` on this platform.
+ */
+pub fn warnings(mods: &[Mod], code: KeyCode) -> Vec<Warning> {
+  let mut warnings = vec![];
+
+  if mods.is_empty() {
+    warnings.push(Warning::new(
+      WarningCode::NoModifier,
+      format!("{} has no modifiers and will be captured system-wide", code),
+    ));
+  }
+
+  if risky_key(code) && mods.iter().all(|m| matches!(m, Mod::Shift)) {
+    warnings.push(Warning::new(
+      WarningCode::RiskyKey,
+      format!("{} is used by other applications for text entry", code),
+    ));
+  }
+
+  for m in mods.iter().filter(|m| !mod_supported(**m)) {
+    warnings.push(Warning::new(
+      WarningCode::UnsupportedModifier,
+      format!("{} is ignored by the OS on this platform", m),
+    ));
+  }
+
+  if code.category() == KeyCategory::Ime {
+    warnings.push(Warning::new(
+      WarningCode::ImeKey,
+      format!(
+        "{} is an input method key and is usually consumed before it can fire",
+        code
+      ),
+    ));
+  }
+
+  if let Some(owner) = reserved_combo(mods, code) {
+    warnings.push(Warning::new(
+      WarningCode::ReservedCombo,
+      format!("Reserved by the operating system ({})", owner),
+    ));
+  }
+
+  warnings
+}
+
+/**
+ * What this build supports on the current platform, see `capabilities`.
+ *
+ * Properties:
+ * - `backend` (string): The hotkey backend in use: `"windows"`, `"macos"` or `"x11"`.
+ * - `sideSpecificModifiers` (boolean): Whether left and right modifiers can be told apart.
+ * - `mediaKeys` (boolean): Whether media keys (e.g. `MediaPlayPause`) can be registered.
+ * - `liveKeyState` (boolean): Whether the modifiers currently held can be read, e.g. for `exact` hotkeys.
+ * - `focusGating` (boolean): Whether hotkeys can be limited to when a given window is focused.
+ * - `layoutQueries` (boolean): Whether `currentLayout` and `setOnLayoutChange` report the keyboard layout.
+ * - `lowLevelHook` (boolean): Whether a low-level keyboard hook is available, e.g. for `setWatchUnmapped`.
+ * - `osRepeatControl` (boolean): Whether `setIgnoreOsRepeat` has any effect.
+ */
+#[napi(object)]
+pub struct Capabilities {
+  pub backend: String,
+  pub side_specific_modifiers: bool,
+  pub media_keys: bool,
+  pub live_key_state: bool,
+  pub focus_gating: bool,
+  pub layout_queries: bool,
+  pub low_level_hook: bool,
+  pub os_repeat_control: bool,
+}
+
+/**
+ * Reports what this build supports on the current platform, so features can be detected up front
+ * instead of by calling them and checking for `false` or `null`.
+ *
+ * @example
+ * ```js
+ * import { capabilities } from '@hotk/core';
+ *
+ * if (!capabilities().layoutQueries) {
+ *   console.warn('Bindings will not follow keyboard layout changes');
+ * }
+ * ```
+ */
+#[napi]
+#[cfg_attr(test, allow(dead_code))]
+pub fn capabilities() -> Capabilities {
+  #[cfg(target_os = "windows")]
+  let capabilities = Capabilities {
+    backend: "windows".to_string(),
+    side_specific_modifiers: false,
+    media_keys: true,
+    live_key_state: true,
+    focus_gating: false,
+    layout_queries: true,
+    low_level_hook: true,
+    os_repeat_control: true,
+  };
+
+  #[cfg(not(target_os = "windows"))]
+  let capabilities = Capabilities {
+    #[cfg(target_os = "macos")]
+    backend: "macos".to_string(),
+    #[cfg(not(target_os = "macos"))]
+    backend: "x11".to_string(),
+    side_specific_modifiers: false,
+    media_keys: true,
+    live_key_state: false,
+    focus_gating: false,
+    layout_queries: false,
+    low_level_hook: false,
+    os_repeat_control: false,
+  };
+
+  capabilities
+}
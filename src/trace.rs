@@ -0,0 +1,55 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::mpsc::channel;
+use std::sync::mpsc::Sender;
+use std::thread::JoinHandle;
+
+/**
+ * Appends log lines to a file or stderr from a background thread, so the dispatch path never waits on I/O.
+ */
+pub struct EventTrace {
+  sender: Sender<String>,
+  writer: JoinHandle<()>,
+}
+
+impl EventTrace {
+  /**
+   * Opens `path` for appending, creating it if needed. Fails if the file cannot be opened.
+   */
+  pub fn start(path: &str) -> std::io::Result<Self> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    Ok(Self::spawn(file))
+  }
+
+  /**
+   * Writes to stderr.
+   */
+  pub fn console() -> Self {
+    Self::spawn(std::io::stderr())
+  }
+
+  fn spawn(mut out: impl Write + Send + 'static) -> Self {
+    let (sender, receiver) = channel::<String>();
+
+    let writer = std::thread::spawn(move || {
+      for line in receiver {
+        let _ = writeln!(out, "{}", line);
+      }
+      let _ = out.flush();
+    });
+
+    Self { sender, writer }
+  }
+
+  pub fn write(&self, line: String) {
+    let _ = self.sender.send(line);
+  }
+
+  /**
+   * Stops accepting lines and waits until every pending one is written.
+   */
+  pub fn stop(self) {
+    drop(self.sender);
+    let _ = self.writer.join();
+  }
+}
@@ -1,14 +1,59 @@
-use std::sync::mpsc::{channel, Receiver, Sender};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::os::windows::io::AsRawHandle;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, SendError, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use global_hotkey::hotkey::{Code, HotKey, Modifiers};
 use global_hotkey::GlobalHotKeyManager;
+use strum::IntoEnumIterator;
 
-use std::ptr::null_mut;
-use winapi::um::processthreadsapi::GetCurrentThreadId;
-use winapi::um::winuser::{self, DispatchMessageW, PostThreadMessageW, TranslateMessage, MSG};
+use std::ptr::{null, null_mut};
+use winapi::ctypes::c_int;
+use winapi::shared::minwindef::{LPARAM, LRESULT, WPARAM};
+use winapi::shared::windef::HHOOK;
+use winapi::um::libloaderapi::GetModuleHandleW;
+use winapi::um::processthreadsapi::{
+  GetCurrentThread, GetCurrentThreadId, GetThreadId, GetThreadPriority, SetThreadPriority,
+};
+use winapi::um::winuser::{
+  self, ActivateKeyboardLayout, CallNextHookEx, DispatchMessageW, GetAsyncKeyState,
+  GetForegroundWindow, GetKeyboardLayout, GetKeyboardLayoutNameW, GetWindowThreadProcessId,
+  KillTimer, PostThreadMessageW, RegisterWindowMessageW, SetTimer, SetWindowsHookExW,
+  TranslateMessage, UnhookWindowsHookEx, KBDLLHOOKSTRUCT, KL_NAMELENGTH, MSG,
+};
 
-use crate::events::Response;
+use crate::code::BackendPriority;
+use crate::code::KeyCode;
+use crate::events::{CreateError, Response};
+
+const NOTIFY_ATTEMPTS: u32 = 50;
+const DROP_TIMEOUT: Duration = Duration::from_secs(1);
+// How often held hotkeys are polled to notice their key-up.
+const REPEAT_POLL_MS: u32 = 20;
+// How often the foreground keyboard layout is polled to notice a switch.
+const LAYOUT_POLL_MS: u32 = 250;
+
+// Generation of the loop thread owning the backend, 0 when none runs. Two loops would compete for the same
+// global hotkeys, so every manager of the process shares a single one.
+static LOOP_OWNER: AtomicU64 = AtomicU64::new(0);
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+// Gives up the ownership when the loop thread ends, even by a panic, unless a newer loop took it over.
+struct LoopGuard(u64);
+
+impl Drop for LoopGuard {
+  fn drop(&mut self) {
+    let _ = LOOP_OWNER.compare_exchange(self.0, 0, Ordering::SeqCst, Ordering::SeqCst);
+  }
+}
+
+pub type LayoutHook = Box<dyn Fn(Option<String>) + Send>;
+pub type UnmappedHook = Box<dyn Fn(Modifiers, Code) + Send>;
+pub type FailureHook = Box<dyn Fn(u32, String) + Send>;
 
 enum Action {
   Register {
@@ -19,80 +64,667 @@ enum Action {
     hotkey: HotKey,
     channel: Sender<Response>,
   },
+  SetPriority {
+    priority: i32,
+    channel: Sender<bool>,
+  },
+  GetPriority {
+    channel: Sender<i32>,
+  },
+  WatchUnmapped {
+    hook: Option<UnmappedHook>,
+    channel: Sender<bool>,
+  },
   Exit,
 }
 
-unsafe fn event_loop(receiver_handle: Receiver<Action>, tx: Sender<u32>) {
-  let manager = GlobalHotKeyManager::new().unwrap();
+// THREAD_PRIORITY_* values from winbase.h
+fn priority_value(priority: &BackendPriority) -> i32 {
+  match priority {
+    BackendPriority::Idle => -15,
+    BackendPriority::Lowest => -2,
+    BackendPriority::BelowNormal => -1,
+    BackendPriority::Normal => 0,
+    BackendPriority::AboveNormal => 1,
+    BackendPriority::Highest => 2,
+  }
+}
+
+fn priority_from_value(value: i32) -> Option<BackendPriority> {
+  match value {
+    -15 => Some(BackendPriority::Idle),
+    -2 => Some(BackendPriority::Lowest),
+    -1 => Some(BackendPriority::BelowNormal),
+    0 => Some(BackendPriority::Normal),
+    1 => Some(BackendPriority::AboveNormal),
+    2 => Some(BackendPriority::Highest),
+    _ => None,
+  }
+}
+
+// KLID of the layout the user is typing with, e.g. "00000409" for US English.
+//
+// Every thread has its own layout, and only the focused window is told when it changes, so the
+// calling thread adopts the foreground window's layout to read its name.
+unsafe fn foreground_layout() -> Option<String> {
+  let thread = GetWindowThreadProcessId(GetForegroundWindow(), null_mut());
+  let layout = GetKeyboardLayout(thread);
+  if layout.is_null() {
+    return None;
+  }
+
+  let mut name = [0u16; KL_NAMELENGTH as usize];
+  if ActivateKeyboardLayout(layout, 0).is_null() || GetKeyboardLayoutNameW(name.as_mut_ptr()) == 0 {
+    // The low word of the handle is the language, which is the KLID of its default layout.
+    return Some(format!("{:08X}", layout as usize & 0xFFFF));
+  }
+  let len = name.iter().position(|c| *c == 0).unwrap_or(name.len());
+  Some(String::from_utf16_lossy(&name[..len]))
+}
+
+// Last layout seen by the loop and the callback told when it changes.
+#[derive(Default)]
+struct LayoutState {
+  current: Option<String>,
+  on_change: Option<LayoutHook>,
+}
+
+// Polls the foreground layout, as the loop thread has no window to receive WM_INPUTLANGCHANGE.
+struct LayoutPoll {
+  timer: usize,
+}
+
+impl LayoutPoll {
+  unsafe fn start(state: &Mutex<LayoutState>) -> Self {
+    state.lock().unwrap().current = foreground_layout();
+    Self {
+      timer: SetTimer(null_mut(), 0, LAYOUT_POLL_MS, None),
+    }
+  }
+
+  // Reports a layout switch; returns `false` if `msg` is not our timer.
+  unsafe fn poll(&self, msg: &MSG, state: &Mutex<LayoutState>) -> bool {
+    if self.timer == 0 || msg.message != winuser::WM_TIMER || msg.wParam != self.timer {
+      return false;
+    }
+    let layout = foreground_layout();
+    let mut state = state.lock().unwrap();
+    if state.current != layout {
+      state.current = layout.clone();
+      if let Some(hook) = state.on_change.as_ref() {
+        hook(layout);
+      }
+    }
+    true
+  }
+}
+
+// Virtual key of `code`, mirroring the table the `global_hotkey` backend registers with.
+fn virtual_key(code: Code) -> Option<i32> {
+  Some(match code {
+    Code::KeyA => b'A' as i32,
+    Code::KeyB => b'B' as i32,
+    Code::KeyC => b'C' as i32,
+    Code::KeyD => b'D' as i32,
+    Code::KeyE => b'E' as i32,
+    Code::KeyF => b'F' as i32,
+    Code::KeyG => b'G' as i32,
+    Code::KeyH => b'H' as i32,
+    Code::KeyI => b'I' as i32,
+    Code::KeyJ => b'J' as i32,
+    Code::KeyK => b'K' as i32,
+    Code::KeyL => b'L' as i32,
+    Code::KeyM => b'M' as i32,
+    Code::KeyN => b'N' as i32,
+    Code::KeyO => b'O' as i32,
+    Code::KeyP => b'P' as i32,
+    Code::KeyQ => b'Q' as i32,
+    Code::KeyR => b'R' as i32,
+    Code::KeyS => b'S' as i32,
+    Code::KeyT => b'T' as i32,
+    Code::KeyU => b'U' as i32,
+    Code::KeyV => b'V' as i32,
+    Code::KeyW => b'W' as i32,
+    Code::KeyX => b'X' as i32,
+    Code::KeyY => b'Y' as i32,
+    Code::KeyZ => b'Z' as i32,
+    Code::Digit0 => b'0' as i32,
+    Code::Digit1 => b'1' as i32,
+    Code::Digit2 => b'2' as i32,
+    Code::Digit3 => b'3' as i32,
+    Code::Digit4 => b'4' as i32,
+    Code::Digit5 => b'5' as i32,
+    Code::Digit6 => b'6' as i32,
+    Code::Digit7 => b'7' as i32,
+    Code::Digit8 => b'8' as i32,
+    Code::Digit9 => b'9' as i32,
+    Code::Equal => winuser::VK_OEM_PLUS,
+    Code::Comma => winuser::VK_OEM_COMMA,
+    Code::Minus => winuser::VK_OEM_MINUS,
+    Code::Period => winuser::VK_OEM_PERIOD,
+    Code::Semicolon => winuser::VK_OEM_1,
+    Code::Slash => winuser::VK_OEM_2,
+    Code::Backquote => winuser::VK_OEM_3,
+    Code::BracketLeft => winuser::VK_OEM_4,
+    Code::Backslash => winuser::VK_OEM_5,
+    Code::BracketRight => winuser::VK_OEM_6,
+    Code::Quote => winuser::VK_OEM_7,
+    Code::Backspace => winuser::VK_BACK,
+    Code::Tab => winuser::VK_TAB,
+    Code::Space => winuser::VK_SPACE,
+    Code::Enter => winuser::VK_RETURN,
+    Code::CapsLock => winuser::VK_CAPITAL,
+    Code::Escape => winuser::VK_ESCAPE,
+    Code::PageUp => winuser::VK_PRIOR,
+    Code::PageDown => winuser::VK_NEXT,
+    Code::End => winuser::VK_END,
+    Code::Home => winuser::VK_HOME,
+    Code::ArrowLeft => winuser::VK_LEFT,
+    Code::ArrowUp => winuser::VK_UP,
+    Code::ArrowRight => winuser::VK_RIGHT,
+    Code::ArrowDown => winuser::VK_DOWN,
+    Code::PrintScreen => winuser::VK_SNAPSHOT,
+    Code::Insert => winuser::VK_INSERT,
+    Code::Delete => winuser::VK_DELETE,
+    Code::F1 => winuser::VK_F1,
+    Code::F2 => winuser::VK_F2,
+    Code::F3 => winuser::VK_F3,
+    Code::F4 => winuser::VK_F4,
+    Code::F5 => winuser::VK_F5,
+    Code::F6 => winuser::VK_F6,
+    Code::F7 => winuser::VK_F7,
+    Code::F8 => winuser::VK_F8,
+    Code::F9 => winuser::VK_F9,
+    Code::F10 => winuser::VK_F10,
+    Code::F11 => winuser::VK_F11,
+    Code::F12 => winuser::VK_F12,
+    Code::F13 => winuser::VK_F13,
+    Code::F14 => winuser::VK_F14,
+    Code::F15 => winuser::VK_F15,
+    Code::F16 => winuser::VK_F16,
+    Code::F17 => winuser::VK_F17,
+    Code::F18 => winuser::VK_F18,
+    Code::F19 => winuser::VK_F19,
+    Code::F20 => winuser::VK_F20,
+    Code::F21 => winuser::VK_F21,
+    Code::F22 => winuser::VK_F22,
+    Code::F23 => winuser::VK_F23,
+    Code::F24 => winuser::VK_F24,
+    Code::NumLock => winuser::VK_NUMLOCK,
+    Code::Numpad0 => winuser::VK_NUMPAD0,
+    Code::Numpad1 => winuser::VK_NUMPAD1,
+    Code::Numpad2 => winuser::VK_NUMPAD2,
+    Code::Numpad3 => winuser::VK_NUMPAD3,
+    Code::Numpad4 => winuser::VK_NUMPAD4,
+    Code::Numpad5 => winuser::VK_NUMPAD5,
+    Code::Numpad6 => winuser::VK_NUMPAD6,
+    Code::Numpad7 => winuser::VK_NUMPAD7,
+    Code::Numpad8 => winuser::VK_NUMPAD8,
+    Code::Numpad9 => winuser::VK_NUMPAD9,
+    Code::NumpadAdd => winuser::VK_ADD,
+    Code::NumpadDecimal => winuser::VK_DECIMAL,
+    Code::NumpadDivide => winuser::VK_DIVIDE,
+    Code::NumpadEnter => winuser::VK_RETURN,
+    Code::NumpadMultiply => winuser::VK_MULTIPLY,
+    Code::NumpadSubtract => winuser::VK_SUBTRACT,
+    Code::ScrollLock => winuser::VK_SCROLL,
+    Code::AudioVolumeDown => winuser::VK_VOLUME_DOWN,
+    Code::AudioVolumeUp => winuser::VK_VOLUME_UP,
+    Code::AudioVolumeMute => winuser::VK_VOLUME_MUTE,
+    Code::MediaPlay => winuser::VK_PLAY,
+    Code::MediaPause => winuser::VK_PAUSE,
+    Code::MediaPlayPause => winuser::VK_MEDIA_PLAY_PAUSE,
+    Code::MediaStop => winuser::VK_MEDIA_STOP,
+    Code::MediaTrackNext => winuser::VK_MEDIA_NEXT_TRACK,
+    Code::MediaTrackPrevious => winuser::VK_MEDIA_PREV_TRACK,
+    Code::Pause => winuser::VK_PAUSE,
+    _ => return None,
+  })
+}
+
+fn is_key_down(vk: i32) -> bool {
+  unsafe { GetAsyncKeyState(vk) as u16 & 0x8000 != 0 }
+}
+
+/**
+ * Modifiers physically held right now.
+ */
+pub fn held_modifiers() -> Option<Modifiers> {
+  let mut held = Modifiers::empty();
+  if is_key_down(winuser::VK_CONTROL) {
+    held |= Modifiers::CONTROL;
+  }
+  if is_key_down(winuser::VK_MENU) {
+    held |= Modifiers::ALT;
+  }
+  if is_key_down(winuser::VK_SHIFT) {
+    held |= Modifiers::SHIFT;
+  }
+  if is_key_down(winuser::VK_LWIN) || is_key_down(winuser::VK_RWIN) {
+    held |= Modifiers::SUPER;
+  }
+  Some(held)
+}
+
+/**
+ * Low-level keyboard hook reporting every key pressed with modifiers held.
+ */
+struct Unmapped {
+  hook: HHOOK,
+  // Key for each virtual key code.
+  keys: HashMap<u32, Code>,
+  // Virtual keys currently down, so auto-repeat is reported once.
+  down: HashSet<u32>,
+  on_attempt: UnmappedHook,
+}
+
+thread_local! {
+  // Hooks run on the thread that installed them, the event loop.
+  static UNMAPPED: RefCell<Option<Unmapped>> = const { RefCell::new(None) };
+}
+
+/**
+ * Installs the low-level hook calling `on_attempt`, or removes it if `None`. Returns `false` if it
+ * could not be installed.
+ */
+unsafe fn watch_unmapped(on_attempt: Option<UnmappedHook>) -> bool {
+  UNMAPPED.with(|unmapped| {
+    if let Some(previous) = unmapped.borrow_mut().take() {
+      UnhookWindowsHookEx(previous.hook);
+    }
+    let Some(on_attempt) = on_attempt else {
+      return true;
+    };
+
+    let hook = SetWindowsHookExW(
+      winuser::WH_KEYBOARD_LL,
+      Some(unmapped_proc),
+      GetModuleHandleW(null()),
+      0,
+    );
+    if hook.is_null() {
+      return false;
+    }
+
+    let mut keys = HashMap::new();
+    for code in KeyCode::iter().filter(|code| !code.is_modifier()) {
+      let key = code.global_hotkeys();
+      if let Some(vk) = virtual_key(key) {
+        keys.entry(vk as u32).or_insert(key);
+      }
+    }
+
+    *unmapped.borrow_mut() = Some(Unmapped {
+      hook,
+      keys,
+      down: HashSet::new(),
+      on_attempt,
+    });
+    true
+  })
+}
+
+unsafe extern "system" fn unmapped_proc(code: c_int, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+  if code == winuser::HC_ACTION {
+    let vk = (*(lparam as *const KBDLLHOOKSTRUCT)).vkCode;
+    let message = wparam as u32;
+
+    UNMAPPED.with(|unmapped| {
+      // The callback can't reenter the hook, but a failed borrow must not unwind across FFI.
+      let Ok(mut unmapped) = unmapped.try_borrow_mut() else {
+        return;
+      };
+      let Some(unmapped) = unmapped.as_mut() else {
+        return;
+      };
+
+      if message == winuser::WM_KEYUP || message == winuser::WM_SYSKEYUP {
+        unmapped.down.remove(&vk);
+      } else if (message == winuser::WM_KEYDOWN || message == winuser::WM_SYSKEYDOWN)
+        && unmapped.down.insert(vk)
+      {
+        if let (Some(key), Some(mods)) = (unmapped.keys.get(&vk), held_modifiers()) {
+          if !mods.is_empty() {
+            (unmapped.on_attempt)(mods, *key);
+          }
+        }
+      }
+    });
+  }
+
+  CallNextHookEx(null_mut(), code, wparam, lparam)
+}
+
+// Message that tells the loop an action is waiting. Registered instead of the bare `WM_USER`,
+// which other components posting to the same thread may use too.
+fn action_message() -> u32 {
+  let name: Vec<u16> = "hotk-core.action\0".encode_utf16().collect();
+  match unsafe { RegisterWindowMessageW(name.as_ptr()) } {
+    0 => winuser::WM_APP + 0x3F00,
+    message => message,
+  }
+}
+
+// Hotkeys whose key is still down, to tell auto-repeated WM_HOTKEY apart from new presses.
+#[derive(Default)]
+struct Held {
+  // Virtual key of each held hotkey id.
+  keys: HashMap<usize, i32>,
+  timer: usize,
+}
+
+impl Held {
+  // Returns `true` if the WM_HOTKEY in `msg` repeats a press that was not released yet.
+  unsafe fn is_repeat(&mut self, msg: &MSG) -> bool {
+    if self.keys.contains_key(&msg.wParam) {
+      return true;
+    }
+    self
+      .keys
+      .insert(msg.wParam, ((msg.lParam as u32 >> 16) & 0xFFFF) as i32);
+    if self.timer == 0 {
+      self.timer = SetTimer(null_mut(), 0, REPEAT_POLL_MS, None);
+    }
+    false
+  }
+
+  // Forgets the hotkeys whose key went up; returns `false` if `msg` is not our timer.
+  unsafe fn poll(&mut self, msg: &MSG) -> bool {
+    if self.timer == 0 || msg.message != winuser::WM_TIMER || msg.wParam != self.timer {
+      return false;
+    }
+    self.keys.retain(|_, vk| is_key_down(*vk));
+    if self.keys.is_empty() {
+      KillTimer(null_mut(), self.timer);
+      self.timer = 0;
+    }
+    true
+  }
+}
+
+unsafe fn event_loop(
+  message: u32,
+  receiver_handle: Receiver<Action>,
+  tx: Sender<Result<u32, global_hotkey::Error>>,
+  layout: Arc<Mutex<LayoutState>>,
+  pending: Arc<AtomicU32>,
+  ignore_os_repeat: Arc<AtomicBool>,
+  on_failure: Arc<Mutex<Option<FailureHook>>>,
+) {
+  let manager = match GlobalHotKeyManager::new() {
+    Ok(manager) => manager,
+    Err(error) => {
+      let _ = tx.send(Err(error));
+      return;
+    }
+  };
   let _ = manager.register(HotKey::new(Some(Modifiers::CONTROL), Code::KeyE));
   let mut msg: MSG = std::mem::zeroed();
+  let mut held = Held::default();
+  let layout_poll = LayoutPoll::start(&layout);
   let id = GetCurrentThreadId();
-  let _ = tx.send(id);
+  let _ = tx.send(Ok(id));
 
   while winuser::GetMessageW(&mut msg, null_mut(), 0, 0) > 0 {
+    if held.poll(&msg) || layout_poll.poll(&msg, &layout) {
+      continue;
+    }
+    // Holding a hotkey makes Windows send WM_HOTKEY again on every key repeat. Not dispatching them
+    // keeps a single Pressed per press, as on the other platforms.
+    if msg.message == winuser::WM_HOTKEY
+      && ignore_os_repeat.load(Ordering::SeqCst)
+      && held.is_repeat(&msg)
+    {
+      continue;
+    }
     TranslateMessage(&msg);
     DispatchMessageW(&msg);
-    if msg.message != winuser::WM_USER {
+    if msg.message != message {
       continue;
     }
-    if let Ok(action) = receiver_handle.recv() {
-      match action {
-        Action::Register { hotkey, channel } => {
-          let response = if let Err(error) = manager.register(hotkey) {
-            Response::ErrorRegister {
-              id: hotkey.id,
-              error,
-            }
-          } else {
-            Response::OkRegister { id: hotkey.id }
-          };
-          let _ = channel.send(response);
+    // Drain every pending action, so a lost or duplicated notification can't desync the queue.
+    loop {
+      match receiver_handle.try_recv() {
+        Ok(action) => {
+          pending.fetch_sub(1, Ordering::SeqCst);
+          if !handle_action(&manager, action, &on_failure) {
+            return;
+          }
         }
-        Action::Unregister { hotkey, channel } => {
-          let response = if let Err(error) = manager.unregister(hotkey) {
-            Response::ErrorUnregister {
-              id: hotkey.id,
-              error,
-            }
-          } else {
-            Response::OkUnregister { id: hotkey.id }
-          };
-          let _ = channel.send(response);
+        Err(TryRecvError::Empty) => break,
+        Err(TryRecvError::Disconnected) => return,
+      }
+    }
+  }
+}
+
+// The loop thread exited, e.g. after a panic, dropping the action unanswered.
+fn backend_gone() -> global_hotkey::Error {
+  std::io::Error::other("The hotkey backend thread is not running").into()
+}
+
+// Reports a failure the caller of the action can no longer learn about.
+fn report_failure(on_failure: &Mutex<Option<FailureHook>>, id: u32, error: &global_hotkey::Error) {
+  if let Some(hook) = on_failure.lock().unwrap().as_ref() {
+    hook(id, error.to_string());
+  }
+}
+
+// Returns `false` when the loop must exit.
+unsafe fn handle_action(
+  manager: &GlobalHotKeyManager,
+  action: Action,
+  on_failure: &Mutex<Option<FailureHook>>,
+) -> bool {
+  match action {
+    Action::Register { hotkey, channel } => {
+      let response = if let Err(error) = manager.register(hotkey) {
+        Response::ErrorRegister {
+          id: hotkey.id,
+          error,
         }
-        Action::Exit => return,
+      } else {
+        Response::OkRegister { id: hotkey.id }
+      };
+      // The caller gave up waiting (see `notify_thread`), roll back.
+      match channel.send(response) {
+        Err(SendError(Response::OkRegister { .. })) => {
+          let _ = manager.unregister(hotkey);
+        }
+        Err(SendError(Response::ErrorRegister { id, error })) => {
+          report_failure(on_failure, id, &error);
+        }
+        _ => {}
       }
-    } else {
-      return;
     }
+    Action::Unregister { hotkey, channel } => {
+      let response = if let Err(error) = manager.unregister(hotkey) {
+        Response::ErrorUnregister {
+          id: hotkey.id,
+          error,
+        }
+      } else {
+        Response::OkUnregister { id: hotkey.id }
+      };
+      // The caller was told the hotkey stays registered, so losing it must be reported.
+      if let Err(SendError(Response::OkUnregister { .. })) = channel.send(response) {
+        if let Err(error) = manager.register(hotkey) {
+          report_failure(on_failure, hotkey.id, &error);
+        }
+      }
+    }
+    Action::SetPriority { priority, channel } => {
+      let _ = channel.send(SetThreadPriority(GetCurrentThread(), priority) != 0);
+    }
+    Action::GetPriority { channel } => {
+      let _ = channel.send(GetThreadPriority(GetCurrentThread()));
+    }
+    Action::WatchUnmapped { hook, channel } => {
+      let _ = channel.send(watch_unmapped(hook));
+    }
+    Action::Exit => return false,
   }
+
+  true
 }
 
 pub struct Manager {
   handler: Option<JoinHandle<()>>,
   sender: Sender<Action>,
+  // Generation of the loop thread, see `LOOP_OWNER`.
+  generation: u64,
   thread_id: u32,
+  message: u32,
+  layout: Arc<Mutex<LayoutState>>,
+  // Actions sent to the loop and not processed yet.
+  pending: Arc<AtomicU32>,
+  ignore_os_repeat: Arc<AtomicBool>,
+  on_failure: Arc<Mutex<Option<FailureHook>>>,
 }
 
 impl Manager {
-  pub fn new() -> Option<Self> {
+  pub fn new() -> Result<Self, CreateError> {
+    let generation = NEXT_GENERATION.fetch_add(1, Ordering::SeqCst);
+    if LOOP_OWNER
+      .compare_exchange(0, generation, Ordering::SeqCst, Ordering::SeqCst)
+      .is_err()
+    {
+      return Err(CreateError::BackendRunning);
+    }
+
     let (sender_handle, receiver_handle) = channel();
     let (tx, rx) = channel();
+    let layout: Arc<Mutex<LayoutState>> = Default::default();
 
+    let message = action_message();
+
+    let pending: Arc<AtomicU32> = Default::default();
+    let ignore_os_repeat = Arc::new(AtomicBool::new(true));
+    let on_failure: Arc<Mutex<Option<FailureHook>>> = Default::default();
+
+    let loop_layout = layout.clone();
+    let loop_pending = pending.clone();
+    let loop_ignore_os_repeat = ignore_os_repeat.clone();
+    let loop_on_failure = on_failure.clone();
     let handler = std::thread::spawn(move || {
-      unsafe { event_loop(receiver_handle, tx) };
+      let _running = LoopGuard(generation);
+      unsafe {
+        event_loop(
+          message,
+          receiver_handle,
+          tx,
+          loop_layout,
+          loop_pending,
+          loop_ignore_os_repeat,
+          loop_on_failure,
+        )
+      };
     });
 
-    let thread_id = rx.recv().unwrap();
+    let thread_id = match rx.recv() {
+      Ok(started) => started.map_err(CreateError::Backend)?,
+      Err(_) => return Err(CreateError::BackendThread),
+    };
+    // With the `test-hooks` feature, setting `HOTK_INVALID_THREAD_ID` fakes a bogus id, to exercise this path.
+    #[cfg(feature = "test-hooks")]
+    let thread_id = match std::env::var_os("HOTK_INVALID_THREAD_ID") {
+      Some(_) => 0,
+      None => thread_id,
+    };
+
+    // Actions are posted to `thread_id`, so anything but the spawned thread would never reach the loop.
+    let spawned_id = unsafe { GetThreadId(handler.as_raw_handle() as _) };
+    if thread_id == 0 || thread_id != spawned_id {
+      unsafe { PostThreadMessageW(spawned_id, winuser::WM_QUIT, 0, 0) };
+      return Err(CreateError::InvalidThreadId(thread_id));
+    }
 
-    Some(Self {
+    Ok(Self {
       handler: Some(handler),
       sender: sender_handle,
+      generation,
       thread_id,
+      message,
+      layout,
+      pending,
+      ignore_os_repeat,
+      on_failure,
     })
   }
 
-  fn notify_thread(&self) {
-    unsafe { PostThreadMessageW(self.thread_id, winuser::WM_USER, 0, 0) };
+  /**
+   * Replaces the loop thread with a fresh one, keeping the callbacks and the repeat setting. Hotkeys registered
+   * on the old thread are lost with it.
+   *
+   * The old loop is stopped first, as only one may run; if the fresh one can't be created, none is left.
+   * A loop wedged past the stop timeout is detached and gives up its ownership, so the fresh one still
+   * starts; the old thread exits once it gets to its `Exit` action.
+   */
+  pub fn recreate(&mut self) -> Result<bool, CreateError> {
+    self.stop();
+    let fresh = Manager::new()?;
+
+    fresh
+      .ignore_os_repeat
+      .store(self.ignores_os_repeat(), Ordering::SeqCst);
+    fresh.layout.lock().unwrap().on_change = self.layout.lock().unwrap().on_change.take();
+    *fresh.on_failure.lock().unwrap() = self.on_failure.lock().unwrap().take();
+    *self = fresh;
+    Ok(true)
+  }
+
+  fn stop(&mut self) {
+    let Some(join) = self.handler.take() else {
+      return;
+    };
+    self.send(Action::Exit);
+    let _ = self.notify_thread();
+    // Actions queued before `Exit` still run first; give them a bounded time.
+    let deadline = Instant::now() + DROP_TIMEOUT;
+    while !join.is_finished() && Instant::now() < deadline {
+      std::thread::sleep(Duration::from_millis(1));
+    }
+    // A wedged thread is detached rather than blocking the caller forever, handing the ownership over
+    // to the next loop.
+    if join.is_finished() {
+      let _ = join.join();
+    } else {
+      let _ = LOOP_OWNER.compare_exchange(self.generation, 0, Ordering::SeqCst, Ordering::SeqCst);
+    }
+  }
+
+  fn send(&self, action: Action) {
+    self.pending.fetch_add(1, Ordering::SeqCst);
+    if self.sender.send(action).is_err() {
+      self.pending.fetch_sub(1, Ordering::SeqCst);
+    }
+  }
+
+  pub fn pending_actions(&self) -> u32 {
+    self.pending.load(Ordering::SeqCst)
+  }
+
+  pub fn is_healthy(&self) -> bool {
+    self
+      .handler
+      .as_ref()
+      .is_some_and(|handler| !handler.is_finished())
+  }
+
+  // The thread may not have a message queue yet right after it is spawned,
+  // so failed posts are retried for a short while before giving up.
+  fn notify_thread(&self) -> std::io::Result<()> {
+    let mut attempts = 0;
+    loop {
+      if unsafe { PostThreadMessageW(self.thread_id, self.message, 0, 0) } != 0 {
+        return Ok(());
+      }
+      attempts += 1;
+      if attempts == NOTIFY_ATTEMPTS {
+        return Err(std::io::Error::last_os_error());
+      }
+      std::thread::sleep(Duration::from_millis(1));
+    }
   }
 
   pub fn register(&self, mods: Vec<Modifiers>, key: Code) -> (HotKey, Response) {
@@ -100,40 +732,181 @@ impl Manager {
     let hotkey = HotKey::new(Some(mods), key);
 
     let (sender_handle, receiver_handle) = channel();
-    let _ = self.sender.send(Action::Register {
+    self.send(Action::Register {
       hotkey,
       channel: sender_handle,
     });
 
-    self.notify_thread();
-    let r = receiver_handle.recv().unwrap();
+    let r = match self.notify_thread() {
+      Ok(()) => receiver_handle
+        .recv()
+        .unwrap_or_else(|_| Response::ErrorRegister {
+          id: hotkey.id,
+          error: backend_gone(),
+        }),
+      Err(error) => Response::ErrorRegister {
+        id: hotkey.id,
+        error: error.into(),
+      },
+    };
 
     (hotkey, r)
   }
 
+  /**
+   * Registers every combination with a single notification of the event loop, which handles them in order.
+   */
+  pub fn register_many(&self, combos: Vec<(Vec<Modifiers>, Code)>) -> Vec<(HotKey, Response)> {
+    let pending: Vec<(HotKey, Receiver<Response>)> = combos
+      .into_iter()
+      .map(|(mods, key)| {
+        let mods = mods.into_iter().fold(Modifiers::empty(), |acc, m| acc | m);
+        let hotkey = HotKey::new(Some(mods), key);
+
+        let (sender_handle, receiver_handle) = channel();
+        self.send(Action::Register {
+          hotkey,
+          channel: sender_handle,
+        });
+        (hotkey, receiver_handle)
+      })
+      .collect();
+
+    let notified = self.notify_thread();
+    pending
+      .into_iter()
+      .map(|(hotkey, receiver_handle)| {
+        let r = match &notified {
+          Ok(()) => receiver_handle
+            .recv()
+            .unwrap_or_else(|_| Response::ErrorRegister {
+              id: hotkey.id,
+              error: backend_gone(),
+            }),
+          Err(error) => Response::ErrorRegister {
+            id: hotkey.id,
+            error: std::io::Error::new(error.kind(), error.to_string()).into(),
+          },
+        };
+        (hotkey, r)
+      })
+      .collect()
+  }
+
   pub fn unregister(&self, mods: Vec<Modifiers>, key: Code) -> (HotKey, Response) {
     let mods = mods.into_iter().fold(Modifiers::empty(), |acc, m| acc | m);
     let hotkey = HotKey::new(Some(mods), key);
 
     let (sender_handle, receiver_handle) = channel();
-    let _ = self.sender.send(Action::Unregister {
+    self.send(Action::Unregister {
       hotkey,
       channel: sender_handle,
     });
 
-    self.notify_thread();
-    let r = receiver_handle.recv().unwrap();
+    let r = match self.notify_thread() {
+      Ok(()) => receiver_handle
+        .recv()
+        .unwrap_or_else(|_| Response::ErrorUnregister {
+          id: hotkey.id,
+          error: backend_gone(),
+        }),
+      Err(error) => Response::ErrorUnregister {
+        id: hotkey.id,
+        error: error.into(),
+      },
+    };
 
     (hotkey, r)
   }
+
+  pub fn set_priority(&self, priority: BackendPriority) -> bool {
+    let (sender_handle, receiver_handle) = channel();
+    self.send(Action::SetPriority {
+      priority: priority_value(&priority),
+      channel: sender_handle,
+    });
+
+    if self.notify_thread().is_err() {
+      return false;
+    }
+    receiver_handle.recv().unwrap_or(false)
+  }
+
+  pub fn priority(&self) -> Option<BackendPriority> {
+    let (sender_handle, receiver_handle) = channel();
+    self.send(Action::GetPriority {
+      channel: sender_handle,
+    });
+
+    self.notify_thread().ok()?;
+    receiver_handle.recv().ok().and_then(priority_from_value)
+  }
+
+  pub fn layout(&self) -> Option<String> {
+    self.layout.lock().unwrap().current.clone()
+  }
+
+  pub fn set_on_layout_change(&self, hook: Option<LayoutHook>) -> bool {
+    self.layout.lock().unwrap().on_change = hook;
+    true
+  }
+
+  pub fn set_on_failure(&self, hook: Option<FailureHook>) -> bool {
+    *self.on_failure.lock().unwrap() = hook;
+    true
+  }
+
+  pub fn is_combo_held(&self, mods: Vec<Modifiers>, key: Code) -> bool {
+    let mods = mods.into_iter().fold(Modifiers::empty(), |acc, m| acc | m);
+    let Some(vk) = virtual_key(key) else {
+      return false;
+    };
+
+    let held = |modifier: Modifiers, vks: &[i32]| {
+      !mods.intersects(modifier) || vks.iter().any(|vk| is_key_down(*vk))
+    };
+    is_key_down(vk)
+      && held(Modifiers::CONTROL, &[winuser::VK_CONTROL])
+      && held(Modifiers::ALT, &[winuser::VK_MENU])
+      && held(Modifiers::SHIFT, &[winuser::VK_SHIFT])
+      && held(
+        Modifiers::SUPER | Modifiers::META,
+        &[winuser::VK_LWIN, winuser::VK_RWIN],
+      )
+  }
+
+  pub fn set_ignore_os_repeat(&self, enabled: bool) -> bool {
+    self.ignore_os_repeat.store(enabled, Ordering::SeqCst);
+    true
+  }
+
+  pub fn ignores_os_repeat(&self) -> bool {
+    self.ignore_os_repeat.load(Ordering::SeqCst)
+  }
+
+  pub fn set_watch_unmapped(&self, hook: Option<UnmappedHook>) -> bool {
+    let (sender_handle, receiver_handle) = channel();
+    self.send(Action::WatchUnmapped {
+      hook,
+      channel: sender_handle,
+    });
+
+    if self.notify_thread().is_err() {
+      return false;
+    }
+    receiver_handle.recv().unwrap_or(false)
+  }
+
+  // Forgets the last layout seen, so the next poll reports the current one as a switch.
+  #[cfg(feature = "test-hooks")]
+  pub fn simulate_layout_change(&self) -> bool {
+    self.layout.lock().unwrap().current = None;
+    true
+  }
 }
 
 impl Drop for Manager {
   fn drop(&mut self) {
-    let _ = self.sender.send(Action::Exit);
-    self.notify_thread();
-    if let Some(join) = self.handler.take() {
-      let _ = join.join();
-    }
+    self.stop();
   }
 }